@@ -431,3 +431,41 @@ where
 {
     T::four() * bulge.atan()
 }
+
+/// Returns the bulge of the circular arc going from `start` through `mid` to `end`.
+///
+/// The arc sweep is found from the inscribed angle at `mid` so the arc center is never computed.
+/// Returns zero if the points are colinear (or coincident). Note the bulge returned is negative
+/// (clockwise arc) if `mid` lies to the left of the line from `start` to `end`.
+///
+/// # Examples
+/// ```
+/// # use cavalier_contours::*;
+/// # use cavalier_contours::core_math::*;
+/// // quarter circle going counter clockwise from (1, 0) to (0, 1)
+/// let start = Vector2::new(1.0, 0.0);
+/// let mid = point_on_circle(1.0, Vector2::new(0.0, 0.0), std::f64::consts::FRAC_PI_4);
+/// let end = Vector2::new(0.0, 1.0);
+/// let bulge = bulge_from_3_points(start, mid, end);
+/// assert!(bulge.fuzzy_eq(bulge_from_angle(std::f64::consts::FRAC_PI_2)));
+/// // reversed direction gives a clockwise arc
+/// assert!(bulge_from_3_points(end, mid, start).fuzzy_eq(-bulge));
+/// // colinear points
+/// let line_mid = Vector2::new(0.5, 0.5);
+/// assert!(bulge_from_3_points(start, line_mid, end).fuzzy_eq(0.0));
+/// ```
+pub fn bulge_from_3_points<T>(start: Vector2<T>, mid: Vector2<T>, end: Vector2<T>) -> T
+where
+    T: Real,
+{
+    let v1 = start - mid;
+    let v2 = end - mid;
+    let cross = v1.perp_dot(v2);
+    if cross.fuzzy_eq_zero() {
+        return T::zero();
+    }
+
+    // inscribed angle at mid is `a` and arc sweep angle is `2 * (pi - a)` so
+    // bulge = tan((pi - a) / 2) = cot(a / 2) = (1 + cos(a)) / sin(a)
+    -(v1.length() * v2.length() + v1.dot(v2)) / cross
+}
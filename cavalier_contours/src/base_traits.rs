@@ -4,18 +4,18 @@ pub trait Real:
     num_traits::real::Real
     + num_traits::Bounded
     + FuzzyOrd
-    + std::default::Default
-    + std::fmt::Debug
+    + core::default::Default
+    + core::fmt::Debug
     + IndexableNum
 {
     #[inline]
     fn pi() -> Self {
-        Self::from(std::f64::consts::PI).unwrap()
+        Self::from(core::f64::consts::PI).unwrap()
     }
 
     #[inline]
     fn tau() -> Self {
-        Self::from(std::f64::consts::TAU).unwrap()
+        Self::from(core::f64::consts::TAU).unwrap()
     }
 
     #[inline]
@@ -39,15 +39,25 @@ pub trait Real:
     }
 }
 
+/// Returns true if `x` is finite (not NaN or infinite).
+#[inline]
+pub(crate) fn is_finite<T>(x: T) -> bool
+where
+    T: Real,
+{
+    // NaN fails all comparisons so this also catches NaN values
+    x.abs() <= Real::max_value()
+}
+
 impl Real for f32 {
     #[inline]
     fn pi() -> Self {
-        std::f32::consts::PI
+        core::f32::consts::PI
     }
 
     #[inline]
     fn tau() -> Self {
-        std::f32::consts::TAU
+        core::f32::consts::TAU
     }
 
     #[inline]
@@ -64,12 +74,12 @@ impl Real for f32 {
 impl Real for f64 {
     #[inline]
     fn pi() -> Self {
-        std::f64::consts::PI
+        core::f64::consts::PI
     }
 
     #[inline]
     fn tau() -> Self {
-        std::f64::consts::TAU
+        core::f64::consts::TAU
     }
 
     #[inline]
@@ -0,0 +1,370 @@
+//! Reading and writing polylines as DXF files (LWPOLYLINE entities).
+use std::{
+    fmt,
+    io::{self, Read, Write},
+};
+
+use crate::{Polyline, Real};
+
+/// Error returned when reading or writing DXF fails.
+#[derive(Debug)]
+pub enum DxfError {
+    /// Error from the underlying reader or writer.
+    Io(io::Error),
+    /// A group code line that could not be parsed as an integer.
+    InvalidGroupCode {
+        /// Line number (starting at 1) of the group code.
+        line: usize,
+    },
+    /// A group value that could not be parsed for its group code.
+    InvalidValue {
+        /// Line number (starting at 1) of the value.
+        line: usize,
+        /// Group code the value belongs to.
+        code: i32,
+    },
+    /// The input ended part way through a group code and value pair.
+    UnexpectedEof,
+}
+
+impl fmt::Display for DxfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DxfError::Io(err) => write!(f, "DXF io error: {}", err),
+            DxfError::InvalidGroupCode { line } => {
+                write!(f, "invalid DXF group code at line {}", line)
+            }
+            DxfError::InvalidValue { line, code } => write!(
+                f,
+                "invalid DXF value for group code {} at line {}",
+                code, line
+            ),
+            DxfError::UnexpectedEof => write!(f, "unexpected end of DXF input"),
+        }
+    }
+}
+
+impl std::error::Error for DxfError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DxfError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for DxfError {
+    fn from(err: io::Error) -> Self {
+        DxfError::Io(err)
+    }
+}
+
+/// Read all the LWPOLYLINE entities from DXF (ASCII) `reader` as polylines.
+///
+/// Vertex positions are read from group codes 10 and 20, bulge values from group code 42 and the
+/// closed flag from bit 1 of group code 70. Only entities in the ENTITIES section are read (block
+/// definitions in the BLOCKS section are not top level geometry), all other entity types and group
+/// codes are skipped. Text that is not valid UTF-8 (e.g. strings written in a legacy code page) is
+/// decoded lossily since only numeric values are used.
+///
+/// # Examples
+///
+/// ```
+/// # use cavalier_contours::*;
+/// # use cavalier_contours::dxf::*;
+/// let dxf = "0\nSECTION\n2\nENTITIES\n0\nLWPOLYLINE\n90\n2\n70\n1\n\
+///            10\n0.0\n20\n0.0\n42\n1.0\n10\n2.0\n20\n0.0\n42\n1.0\n0\nENDSEC\n0\nEOF\n";
+/// let plines: Vec<Polyline> = from_dxf_lwpolyline(dxf.as_bytes()).unwrap();
+/// assert_eq!(plines.len(), 1);
+/// assert!(plines[0].is_closed());
+/// assert!(plines[0][1].fuzzy_eq(PlineVertex::new(2.0, 0.0, 1.0)));
+/// ```
+pub fn from_dxf_lwpolyline<T>(mut reader: impl Read) -> Result<Vec<Polyline<T>>, DxfError>
+where
+    T: Real,
+{
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    let input = String::from_utf8_lossy(&bytes);
+
+    let mut result = Vec::new();
+    // polyline currently being read (if inside of an LWPOLYLINE entity)
+    let mut current: Option<Polyline<T>> = None;
+    // true if the previous entity was SECTION (next group code 2 holds the section name)
+    let mut at_section_start = false;
+    let mut in_entities_section = false;
+    let mut lines = input.lines().enumerate();
+    while let Some((code_index, code_line)) = lines.next() {
+        let (value_index, value_line) = lines.next().ok_or(DxfError::UnexpectedEof)?;
+        let code: i32 = code_line
+            .trim()
+            .parse()
+            .map_err(|_| DxfError::InvalidGroupCode {
+                line: code_index + 1,
+            })?;
+        let value = value_line.trim();
+        let parse_real = || -> Result<T, DxfError> {
+            value
+                .parse::<f64>()
+                .ok()
+                .and_then(T::from)
+                .ok_or(DxfError::InvalidValue {
+                    line: value_index + 1,
+                    code,
+                })
+        };
+
+        if code == 0 {
+            // start of next entity
+            if let Some(pline) = current.take() {
+                result.push(pline);
+            }
+
+            at_section_start = value == "SECTION";
+            if value == "ENDSEC" {
+                in_entities_section = false;
+            } else if in_entities_section && value == "LWPOLYLINE" {
+                current = Some(Polyline::new());
+            }
+
+            continue;
+        }
+
+        if at_section_start {
+            at_section_start = false;
+            if code == 2 {
+                in_entities_section = value == "ENTITIES";
+            }
+            continue;
+        }
+
+        let pline = match current.as_mut() {
+            Some(pline) => pline,
+            None => continue,
+        };
+
+        match code {
+            70 => {
+                let flags: i32 = value.parse().map_err(|_| DxfError::InvalidValue {
+                    line: value_index + 1,
+                    code,
+                })?;
+                pline.set_is_closed(flags & 1 != 0);
+            }
+            10 => {
+                pline.add(parse_real()?, T::zero(), T::zero());
+            }
+            20 => {
+                if let Some(v) = pline.last_mut() {
+                    v.y = parse_real()?;
+                }
+            }
+            42 => {
+                if let Some(v) = pline.last_mut() {
+                    v.bulge = parse_real()?;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(pline) = current.take() {
+        result.push(pline);
+    }
+
+    Ok(result)
+}
+
+/// Write `plines` as LWPOLYLINE entities (on layer "0") in the ENTITIES section of a minimal
+/// DXF (ASCII) file to `writer`.
+///
+/// Bulge values (group code 42) are only written for vertexes with a non-zero bulge. See also
+/// [Polyline::to_dxf_lwpolyline].
+pub fn write_dxf<T>(plines: &[Polyline<T>], mut writer: impl Write) -> Result<(), DxfError>
+where
+    T: Real,
+{
+    let mut write_group = |code: i32, value: &dyn fmt::Display| -> io::Result<()> {
+        write!(writer, "{:>3}\n{}\n", code, value)
+    };
+
+    write_group(0, &"SECTION")?;
+    write_group(2, &"ENTITIES")?;
+    for pline in plines {
+        write_group(0, &"LWPOLYLINE")?;
+        write_group(8, &"0")?;
+        write_group(90, &pline.len())?;
+        write_group(70, &(if pline.is_closed() { 1 } else { 0 }))?;
+        for v in pline.iter() {
+            write_group(10, &v.x.to_f64().unwrap())?;
+            write_group(20, &v.y.to_f64().unwrap())?;
+            if !v.bulge_is_zero() {
+                write_group(42, &v.bulge.to_f64().unwrap())?;
+            }
+        }
+    }
+    write_group(0, &"ENDSEC")?;
+    write_group(0, &"EOF")?;
+
+    Ok(())
+}
+
+impl<T> Polyline<T>
+where
+    T: Real,
+{
+    /// Write the polyline as a single LWPOLYLINE entity in a minimal DXF (ASCII) file to `writer`
+    /// (see [write_dxf]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// # use cavalier_contours::dxf::*;
+    /// let mut polyline: Polyline = Polyline::new_closed();
+    /// polyline.add(0.0, 0.0, 1.0);
+    /// polyline.add(2.0, 0.0, 1.0);
+    /// let mut output = Vec::new();
+    /// polyline.to_dxf_lwpolyline(&mut output).unwrap();
+    /// let plines: Vec<Polyline> = from_dxf_lwpolyline(output.as_slice()).unwrap();
+    /// assert!(plines[0].fuzzy_eq(&polyline));
+    /// ```
+    pub fn to_dxf_lwpolyline(&self, writer: impl Write) -> Result<(), DxfError> {
+        write_dxf(core::slice::from_ref(self), writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PlineVertex;
+
+    const MINIMAL_DXF: &str = "  0
+SECTION
+  2
+ENTITIES
+  0
+LINE
+  8
+0
+ 10
+0.0
+ 20
+0.0
+ 11
+1.0
+ 21
+1.0
+  0
+LWPOLYLINE
+  5
+2F
+  8
+0
+ 90
+        3
+ 70
+     1
+ 43
+0.0
+ 10
+1.5
+ 20
+2.0
+ 42
+-0.5
+ 10
+4.0
+ 20
+2.0
+ 10
+4.0
+ 20
+5.25
+  0
+ENDSEC
+  0
+EOF
+";
+
+    #[test]
+    fn from_dxf_lwpolyline_closed_with_bulge() {
+        let plines: Vec<Polyline> = from_dxf_lwpolyline(MINIMAL_DXF.as_bytes()).unwrap();
+        // line entity is skipped
+        assert_eq!(plines.len(), 1);
+        let pline = &plines[0];
+        assert!(pline.is_closed());
+        assert_eq!(pline.len(), 3);
+        assert!(pline[0].fuzzy_eq(PlineVertex::new(1.5, 2.0, -0.5)));
+        assert!(pline[1].fuzzy_eq(PlineVertex::new(4.0, 2.0, 0.0)));
+        assert!(pline[2].fuzzy_eq(PlineVertex::new(4.0, 5.25, 0.0)));
+    }
+
+    #[test]
+    fn from_dxf_lwpolyline_skips_blocks_and_non_utf8() {
+        let mut input = Vec::new();
+        input.extend_from_slice(
+            b"0\nSECTION\n2\nHEADER\n9\n$PROJECTNAME\n1\nPr\xfcfung\n0\nENDSEC\n",
+        );
+        input.extend_from_slice(b"0\nSECTION\n2\nBLOCKS\n0\nBLOCK\n2\nB1\n");
+        input.extend_from_slice(b"0\nLWPOLYLINE\n90\n1\n10\n9.0\n20\n9.0\n0\nENDBLK\n0\nENDSEC\n");
+        input.extend_from_slice(b"0\nSECTION\n2\nENTITIES\n0\nLWPOLYLINE\n90\n2\n");
+        input.extend_from_slice(b"10\n1.0\n20\n2.0\n10\n3.0\n20\n4.0\n0\nENDSEC\n0\nEOF\n");
+        let plines: Vec<Polyline> = from_dxf_lwpolyline(input.as_slice()).unwrap();
+        assert_eq!(plines.len(), 1);
+        assert!(!plines[0].is_closed());
+        assert_eq!(plines[0].len(), 2);
+        assert!(plines[0][0].fuzzy_eq(PlineVertex::new(1.0, 2.0, 0.0)));
+        assert!(plines[0][1].fuzzy_eq(PlineVertex::new(3.0, 4.0, 0.0)));
+    }
+
+    #[test]
+    fn from_dxf_lwpolyline_errors() {
+        let result: Result<Vec<Polyline>, _> =
+            from_dxf_lwpolyline("0\nLWPOLYLINE\n10\n".as_bytes());
+        assert!(matches!(result, Err(DxfError::UnexpectedEof)));
+
+        let result: Result<Vec<Polyline>, _> =
+            from_dxf_lwpolyline("0\nSECTION\n2\nENTITIES\n0\nLWPOLYLINE\n10\nabc\n".as_bytes());
+        assert!(matches!(
+            result,
+            Err(DxfError::InvalidValue { line: 8, code: 10 })
+        ));
+
+        let result: Result<Vec<Polyline>, _> = from_dxf_lwpolyline("x\nLWPOLYLINE\n".as_bytes());
+        assert!(matches!(
+            result,
+            Err(DxfError::InvalidGroupCode { line: 1 })
+        ));
+    }
+
+    #[test]
+    fn dxf_round_trip() {
+        let mut closed = Polyline::new_closed();
+        closed.add(0.1, -2.0, 0.0);
+        closed.add(3.25, -2.0, 0.414_213_562_373_095);
+        closed.add(4.0, 1.0 / 3.0, -1.0);
+        closed.add(-1.0, 1.0e-7, 0.0);
+
+        let mut output = Vec::new();
+        closed.to_dxf_lwpolyline(&mut output).unwrap();
+        let plines: Vec<Polyline> = from_dxf_lwpolyline(output.as_slice()).unwrap();
+        assert_eq!(plines.len(), 1);
+        assert!(plines[0].is_closed());
+        assert_eq!(plines[0].len(), closed.len());
+        assert!(plines[0].fuzzy_eq(&closed));
+
+        // multiple polylines in one file
+        let mut open = Polyline::new();
+        open.add(5.0, 5.0, 0.5);
+        open.add(6.0, 5.0, 0.0);
+        let mut output = Vec::new();
+        write_dxf(&[closed.clone(), open.clone()], &mut output).unwrap();
+        let plines: Vec<Polyline> = from_dxf_lwpolyline(output.as_slice()).unwrap();
+        assert_eq!(plines.len(), 2);
+        assert!(plines[0].fuzzy_eq(&closed));
+        assert!(!plines[1].is_closed());
+        assert_eq!(plines[1].len(), open.len());
+        assert!(plines[1].fuzzy_eq(&open));
+    }
+}
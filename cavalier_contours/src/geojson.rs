@@ -0,0 +1,310 @@
+//! Converting polylines to and from GeoJSON geometries (requires the `geojson` feature).
+//!
+//! GeoJSON has no arc segments so arcs are approximated by line segments when converting to
+//! GeoJSON and all polylines converted from GeoJSON have zero bulge values.
+use std::fmt;
+
+use ::geojson::{Geometry, Value};
+
+use crate::{Polyline, Real};
+
+/// Error returned when converting a GeoJSON geometry to polylines fails.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GeoJsonError {
+    /// Geometry type which is not supported (only LineString, Polygon, MultiLineString,
+    /// MultiPolygon and GeometryCollection are supported).
+    UnsupportedGeometry,
+    /// A position with less than 2 coordinates or a coordinate that cannot be represented.
+    InvalidPosition,
+}
+
+impl fmt::Display for GeoJsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GeoJsonError::UnsupportedGeometry => write!(f, "unsupported GeoJSON geometry type"),
+            GeoJsonError::InvalidPosition => write!(f, "invalid GeoJSON position"),
+        }
+    }
+}
+
+impl std::error::Error for GeoJsonError {}
+
+fn polyline_from_positions<T>(
+    positions: &[Vec<f64>],
+    is_ring: bool,
+) -> Result<Polyline<T>, GeoJsonError>
+where
+    T: Real,
+{
+    let mut pline = if is_ring {
+        Polyline::new_closed()
+    } else {
+        Polyline::new()
+    };
+
+    for position in positions {
+        if position.len() < 2 {
+            return Err(GeoJsonError::InvalidPosition);
+        }
+        let x = T::from(position[0]).ok_or(GeoJsonError::InvalidPosition)?;
+        let y = T::from(position[1]).ok_or(GeoJsonError::InvalidPosition)?;
+        pline.add(x, y, T::zero());
+    }
+
+    pline.remove_repeated_ring_end();
+    Ok(pline)
+}
+
+fn polygon_from_rings<T>(
+    rings: &[Vec<Vec<f64>>],
+    result: &mut Vec<Polyline<T>>,
+) -> Result<(), GeoJsonError>
+where
+    T: Real,
+{
+    for (i, ring) in rings.iter().enumerate() {
+        let mut pline = polyline_from_positions(ring, true)?;
+        if pline.len() < 2 {
+            continue;
+        }
+        // first ring is the exterior (counter clockwise), all others are holes (clockwise)
+        let is_hole = i != 0;
+        if (pline.area() < T::zero()) != is_hole {
+            pline.invert_direction();
+        }
+        result.push(pline);
+    }
+
+    Ok(())
+}
+
+fn from_geojson_impl<T>(value: &Value, result: &mut Vec<Polyline<T>>) -> Result<(), GeoJsonError>
+where
+    T: Real,
+{
+    match value {
+        Value::LineString(positions) => {
+            let pline = polyline_from_positions(positions, false)?;
+            if pline.len() > 1 {
+                result.push(pline);
+            }
+        }
+        Value::MultiLineString(lines) => {
+            for positions in lines {
+                let pline = polyline_from_positions(positions, false)?;
+                if pline.len() > 1 {
+                    result.push(pline);
+                }
+            }
+        }
+        Value::Polygon(rings) => polygon_from_rings(rings, result)?,
+        Value::MultiPolygon(polygons) => {
+            for rings in polygons {
+                polygon_from_rings(rings, result)?;
+            }
+        }
+        Value::GeometryCollection(geometries) => {
+            for geometry in geometries {
+                from_geojson_impl(&geometry.value, result)?;
+            }
+        }
+        _ => return Err(GeoJsonError::UnsupportedGeometry),
+    }
+
+    Ok(())
+}
+
+/// Convert the GeoJSON geometry `g` into polylines.
+///
+/// LineString geometries produce open polylines, Polygon rings produce closed polylines (without
+/// repeating the start vertex) with the exterior ring oriented counter clockwise and hole rings
+/// oriented clockwise. Multi geometries and geometry collections produce the polylines of all
+/// their geometries. All bulge values are zero, any positions beyond x and y are ignored.
+///
+/// # Examples
+///
+/// ```
+/// # use cavalier_contours::*;
+/// # use cavalier_contours::geojson::*;
+/// let rings = vec![vec![
+///     vec![0.0, 0.0],
+///     vec![0.0, 3.0],
+///     vec![4.0, 0.0],
+///     vec![0.0, 0.0],
+/// ]];
+/// let geometry = ::geojson::Geometry::new(::geojson::Value::Polygon(rings));
+/// let plines: Vec<Polyline> = from_geojson(&geometry).unwrap();
+/// assert_eq!(plines.len(), 1);
+/// assert!(plines[0].is_closed());
+/// // exterior ring is oriented counter clockwise
+/// assert!(plines[0].area().fuzzy_eq(6.0));
+/// ```
+pub fn from_geojson<T>(g: &Geometry) -> Result<Vec<Polyline<T>>, GeoJsonError>
+where
+    T: Real,
+{
+    let mut result = Vec::new();
+    from_geojson_impl(&g.value, &mut result)?;
+    Ok(result)
+}
+
+/// Positions of the polyline path with arcs approximated by lines, closed polylines repeat the
+/// start point at the end (matching the start vertex of the polyline).
+fn positions<T>(pline: &Polyline<T>, error_distance: T) -> Vec<Vec<f64>>
+where
+    T: Real,
+{
+    let approx = match pline.approx_lines_ring_path(error_distance) {
+        Some(approx) => approx,
+        None => return Vec::new(),
+    };
+
+    approx
+        .iter()
+        .map(|v| vec![v.x.to_f64().unwrap(), v.y.to_f64().unwrap()])
+        .collect()
+}
+
+/// Positions of the closed polyline `ring` oriented counter clockwise if `ccw` is true or
+/// clockwise if `ccw` is false.
+fn ring_positions<T>(ring: &Polyline<T>, error_distance: T, ccw: bool) -> Vec<Vec<f64>>
+where
+    T: Real,
+{
+    let mut ring_positions = positions(ring, error_distance);
+    if (ring.area() < T::zero()) == ccw {
+        ring_positions.reverse();
+    }
+
+    ring_positions
+}
+
+/// Create a GeoJSON Polygon geometry from the closed polyline `exterior` and closed polylines
+/// `holes`.
+///
+/// Arc segments are approximated by line segments with `error_distance` (see
+/// [Polyline::arcs_to_approx_lines]). Following the GeoJSON specification the exterior ring is
+/// written counter clockwise and hole rings are written clockwise regardless of the polyline
+/// directions.
+pub fn to_geojson_polygon<T>(
+    exterior: &Polyline<T>,
+    holes: &[Polyline<T>],
+    error_distance: T,
+) -> Geometry
+where
+    T: Real,
+{
+    let rings = core::iter::once(ring_positions(exterior, error_distance, true))
+        .chain(
+            holes
+                .iter()
+                .map(|hole| ring_positions(hole, error_distance, false)),
+        )
+        .collect();
+
+    Geometry::new(Value::Polygon(rings))
+}
+
+impl<T> Polyline<T>
+where
+    T: Real,
+{
+    /// Convert the polyline to a GeoJSON geometry, Polygon (with no holes) if closed or
+    /// LineString if open.
+    ///
+    /// Arc segments are approximated by line segments with `error_distance` (see
+    /// [Polyline::arcs_to_approx_lines]). Closed polylines are written counter clockwise (see
+    /// [to_geojson_polygon]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(2.0, 1.0, 0.0);
+    /// let geometry = polyline.to_geojson(0.01);
+    /// assert_eq!(
+    ///     geometry.value,
+    ///     ::geojson::Value::LineString(vec![vec![0.0, 0.0], vec![2.0, 1.0]])
+    /// );
+    /// ```
+    pub fn to_geojson(&self, error_distance: T) -> Geometry {
+        if self.is_closed() {
+            to_geojson_polygon(self, &[], error_distance)
+        } else {
+            Geometry::new(Value::LineString(positions(self, error_distance)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FuzzyEq, PlineVertex};
+
+    #[test]
+    fn geojson_polygon_with_hole_round_trip() {
+        // exterior clockwise and hole counter clockwise, both get reoriented
+        let mut exterior = Polyline::new_closed();
+        exterior.add(0.0, 0.0, 0.0);
+        exterior.add(0.0, 10.0, 0.0);
+        exterior.add(10.0, 10.0, 0.0);
+        exterior.add(10.0, 0.0, 0.0);
+        let mut hole = Polyline::new_closed();
+        hole.add(2.0, 2.0, 0.0);
+        hole.add(4.0, 2.0, 0.0);
+        hole.add(4.0, 4.0, 0.0);
+        hole.add(2.0, 4.0, 0.0);
+
+        let geometry = to_geojson_polygon(&exterior, &[hole.clone()], 0.01);
+        match &geometry.value {
+            Value::Polygon(rings) => {
+                assert_eq!(rings.len(), 2);
+                assert_eq!(rings[0].len(), 5);
+                assert_eq!(rings[0].first(), rings[0].last());
+                assert_eq!(rings[1].len(), 5);
+            }
+            _ => panic!("expected polygon geometry"),
+        }
+
+        let plines: Vec<Polyline> = from_geojson(&geometry).unwrap();
+        assert_eq!(plines.len(), 2);
+        assert!(plines.iter().all(|p| p.is_closed() && p.len() == 4));
+        assert_fuzzy_eq!(plines[0].area(), 100.0);
+        assert_fuzzy_eq!(plines[1].area(), -4.0);
+        assert!(plines[0][0].fuzzy_eq(PlineVertex::new(0.0, 0.0, 0.0)));
+
+        let mut expected_exterior = exterior.clone();
+        expected_exterior.invert_direction();
+        assert!(plines[0]
+            .iter()
+            .all(|v| expected_exterior.iter().any(|u| u.fuzzy_eq(*v))));
+        assert!(plines[1]
+            .iter()
+            .all(|v| hole.iter().any(|u| u.fuzzy_eq(*v))));
+    }
+
+    #[test]
+    fn geojson_approximates_arcs() {
+        let mut circle = Polyline::new_closed();
+        circle.add(0.0, 0.0, 1.0);
+        circle.add(2.0, 0.0, 1.0);
+        let plines: Vec<Polyline> = from_geojson(&circle.to_geojson(0.001)).unwrap();
+        assert_eq!(plines.len(), 1);
+        assert!(plines[0].len() > 8);
+        assert!(plines[0].iter().all(|v| v.bulge_is_zero()));
+        assert!((plines[0].area() - circle.area()).abs() < 0.01);
+    }
+
+    #[test]
+    fn from_geojson_errors() {
+        let point = Geometry::new(Value::Point(vec![1.0, 1.0]));
+        let result: Result<Vec<Polyline>, _> = from_geojson(&point);
+        assert_eq!(result.unwrap_err(), GeoJsonError::UnsupportedGeometry);
+
+        let line = Geometry::new(Value::LineString(vec![vec![1.0, 1.0], vec![2.0]]));
+        let result: Result<Vec<Polyline>, _> = from_geojson(&line);
+        assert_eq!(result.unwrap_err(), GeoJsonError::InvalidPosition);
+    }
+}
@@ -1,5 +1,12 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 extern crate static_aabb2d_index;
 
+#[cfg(all(test, not(feature = "std")))]
+#[macro_use]
+extern crate std;
+
 #[macro_use]
 mod utils;
 mod base_math;
@@ -14,19 +21,33 @@ mod line_line_intersect;
 mod pline_seg_intersect;
 
 mod polyline;
+mod polyline_boolean;
+mod polyline_builder;
 mod polyline_intersects;
 mod polyline_offset;
 
+#[cfg(feature = "dxf")]
+pub mod dxf;
+#[cfg(feature = "geojson")]
+pub mod geojson;
+pub mod svg;
+pub mod wkt;
+
 pub mod core_math {
     pub use crate::base_math::*;
     pub use crate::pline_seg::*;
 }
 
+pub mod boolean {
+    pub use crate::polyline_boolean::*;
+}
+
 pub mod intersects {
     pub use crate::circle_circle_intersect::*;
     pub use crate::line_circle_intersect::*;
     pub use crate::line_line_intersect::*;
     pub use crate::pline_seg_intersect::*;
+    pub use crate::polyline_intersects::{find_intersects, IntersectPoint};
 }
 
 pub use static_aabb2d_index::AABB;
@@ -34,4 +55,26 @@ pub use static_aabb2d_index::AABB;
 pub use crate::base_traits::*;
 pub use crate::pline_vertex::*;
 pub use crate::polyline::*;
+pub use crate::polyline_builder::{BuildError, PolylineBuilder};
+#[cfg(feature = "rayon")]
+pub use crate::polyline_offset::parallel_offset_many;
+pub use crate::polyline_offset::{
+    offset_segment, Endcap, JoinStyle, OffsetError, OffsetOptions, OffsetWorkspace,
+};
 pub use crate::vector2::*;
+
+#[cfg(all(test, not(feature = "std")))]
+mod no_std_tests {
+    use crate::*;
+
+    #[test]
+    fn polyline_area() {
+        let mut polyline = Polyline::new_closed();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(2.0, 0.0, 1.0);
+        polyline.add(2.0, 2.0, 0.0);
+        polyline.add(0.0, 2.0, 0.0);
+        let expected = 4.0 + core::f64::consts::PI / 2.0;
+        assert!(polyline.area().fuzzy_eq(expected));
+    }
+}
@@ -131,7 +131,7 @@ where
     };
 
     if seg2_t0 > seg2_t1 {
-        std::mem::swap(&mut seg2_t0, &mut seg2_t1);
+        core::mem::swap(&mut seg2_t0, &mut seg2_t1);
     }
 
     // using threshold check here to make intersect "sticky" to prefer considering it an intersect
@@ -1,7 +1,7 @@
 use crate::{
     base_math::{
-        angle, bulge_from_angle, delta_angle, dist_squared, line_seg_closest_point, midpoint,
-        min_max, point_on_circle, point_within_arc_sweep,
+        angle, angle_from_bulge, bulge_from_angle, delta_angle, dist_squared,
+        line_seg_closest_point, midpoint, min_max, point_on_circle, point_within_arc_sweep,
     },
     core_math::is_left,
     PlineVertex, Real, Vector2, AABB,
@@ -435,7 +435,28 @@ where
 
 /// Computes the axis aligned bounding box of a polyline segment defined by `v1` to `v2`.
 ///
-/// This function is quite a bit slower than [seg_fast_approx_bounding_box] when given an arc.
+/// Unlike [seg_fast_approx_bounding_box] the box is tight, for arcs it includes the extreme points
+/// of the arc (where the arc crosses the horizontal or vertical axis through the arc center) rather
+/// than extending the chord by the sagitta. This function is quite a bit slower than
+/// [seg_fast_approx_bounding_box] when given an arc.
+///
+/// # Examples
+///
+/// ```
+/// # use cavalier_contours::*;
+/// # use cavalier_contours::core_math::*;
+/// // counter clockwise quarter arc on the unit circle going from -45 degrees to 45 degrees, the
+/// // arc crosses the x axis at (1, 0) which is beyond the chord end points
+/// let p = 0.5f64.sqrt();
+/// let bulge = (std::f64::consts::PI / 8.0).tan();
+/// let v1 = PlineVertex::new(p, -p, bulge);
+/// let v2 = PlineVertex::new(p, p, 0.0);
+/// let bb = seg_bounding_box(v1, v2);
+/// assert!(bb.min_x.fuzzy_eq(p));
+/// assert!(bb.max_x.fuzzy_eq(1.0));
+/// assert!(bb.min_y.fuzzy_eq(-p));
+/// assert!(bb.max_y.fuzzy_eq(p));
+/// ```
 pub fn seg_bounding_box<T>(v1: PlineVertex<T>, v2: PlineVertex<T>) -> AABB<T>
 where
     T: Real,
@@ -492,6 +513,57 @@ where
     arc_radius * delta_angle(start_angle, end_angle).abs()
 }
 
+/// Signed sweep angle of the polyline segment defined by `v1` to `v2`.
+///
+/// Positive for counter clockwise arcs, negative for clockwise arcs, and zero for line segments or
+/// segments with coincident end points.
+///
+/// # Examples
+///
+/// ```
+/// # use cavalier_contours::*;
+/// # use cavalier_contours::core_math::*;
+/// // counter clockwise half circle arc going from (2, 2) to (4, 2)
+/// let v1 = PlineVertex::new(2.0, 2.0, 1.0);
+/// let v2 = PlineVertex::new(4.0, 2.0, 0.0);
+/// assert!(seg_sweep_angle(v1, v2).fuzzy_eq(std::f64::consts::PI));
+/// // clockwise half circle arc
+/// let v1 = PlineVertex::new(2.0, 2.0, -1.0);
+/// assert!(seg_sweep_angle(v1, v2).fuzzy_eq(-std::f64::consts::PI));
+/// ```
+pub fn seg_sweep_angle<T>(v1: PlineVertex<T>, v2: PlineVertex<T>) -> T
+where
+    T: Real,
+{
+    if v1.bulge_is_zero() || v1.pos().fuzzy_eq(v2.pos()) {
+        return T::zero();
+    }
+
+    angle_from_bulge(v1.bulge)
+}
+
+/// Returns true if the polyline segment defined by `v1` to `v2` is a counter clockwise arc.
+///
+/// Returns false for clockwise arcs, line segments, and segments with coincident end points.
+///
+/// # Examples
+///
+/// ```
+/// # use cavalier_contours::*;
+/// # use cavalier_contours::core_math::*;
+/// let v1 = PlineVertex::new(2.0, 2.0, 1.0);
+/// let v2 = PlineVertex::new(4.0, 2.0, 0.0);
+/// assert!(seg_is_ccw(v1, v2));
+/// assert!(!seg_is_ccw(PlineVertex::new(2.0, 2.0, -1.0), v2));
+/// assert!(!seg_is_ccw(PlineVertex::new(2.0, 2.0, 0.0), v2));
+/// ```
+pub fn seg_is_ccw<T>(v1: PlineVertex<T>, v2: PlineVertex<T>) -> bool
+where
+    T: Real,
+{
+    seg_sweep_angle(v1, v2) > T::zero()
+}
+
 /// Find the midpoint for the polyline segment defined by `v1` to `v2`.
 ///
 /// # Examples
@@ -499,10 +571,13 @@ where
 /// ```
 /// # use cavalier_contours::*;
 /// # use cavalier_contours::core_math::*;
-/// // counter clockwise half circle arc going from (2, 2) to (2, 4)
+/// // counter clockwise half circle arc going from (2, 2) to (4, 2)
 /// let v1 = PlineVertex::new(2.0, 2.0, 1.0);
 /// let v2 = PlineVertex::new(4.0, 2.0, 0.0);
 /// assert!(seg_midpoint(v1, v2).fuzzy_eq(Vector2::new(3.0, 1.0)));
+/// // clockwise half circle arc going from (2, 2) to (4, 2), midpoint is the top of the arc
+/// let v1 = PlineVertex::new(2.0, 2.0, -1.0);
+/// assert!(seg_midpoint(v1, v2).fuzzy_eq(Vector2::new(3.0, 3.0)));
 /// ```
 ///
 /// Also works with line segments.
@@ -534,3 +609,201 @@ where
     };
     point_on_circle(arc_radius, arc_center, mid_angle)
 }
+
+/// Polyline segment geometry with arc parameters precomputed (see [PlineSegment::new]).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PlineSegment<T = f64>
+where
+    T: Real,
+{
+    /// Line segment.
+    Line {
+        /// Start point of the line.
+        start: Vector2<T>,
+        /// End point of the line.
+        end: Vector2<T>,
+    },
+    /// Circular arc segment.
+    Arc {
+        /// Start point of the arc.
+        start: Vector2<T>,
+        /// End point of the arc.
+        end: Vector2<T>,
+        /// Center of the arc circle.
+        center: Vector2<T>,
+        /// Radius of the arc circle.
+        radius: T,
+        /// Angle of `start` relative to `center` (in radians).
+        start_angle: T,
+        /// Angle of `end` relative to `center` (in radians).
+        end_angle: T,
+        /// True if the arc goes counter clockwise (positive bulge) from `start` to `end`.
+        ccw: bool,
+    },
+}
+
+impl<T> PlineSegment<T>
+where
+    T: Real,
+{
+    /// Create the segment geometry for the polyline segment defined by `v1` to `v2`.
+    ///
+    /// Segments with a zero bulge or with `v1` on top of `v2` are lines, otherwise the arc
+    /// radius, center and angles are computed (see [seg_arc_radius_and_center]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// # use cavalier_contours::core_math::*;
+    /// // arc half circle arc segment going from (0, 0) to (2, 0) counter clockwise
+    /// let v1 = PlineVertex::new(0.0, 0.0, 1.0);
+    /// let v2 = PlineVertex::new(2.0, 0.0, 0.0);
+    /// match PlineSegment::new(v1, v2) {
+    ///     PlineSegment::Arc { center, radius, ccw, .. } => {
+    ///         assert!(center.fuzzy_eq(Vector2::new(1.0, 0.0)));
+    ///         assert!(radius.fuzzy_eq(1.0));
+    ///         assert!(ccw);
+    ///     }
+    ///     PlineSegment::Line { .. } => unreachable!(),
+    /// }
+    /// ```
+    pub fn new(v1: PlineVertex<T>, v2: PlineVertex<T>) -> Self {
+        if v1.bulge_is_zero() || v1.pos().fuzzy_eq(v2.pos()) {
+            return PlineSegment::Line {
+                start: v1.pos(),
+                end: v2.pos(),
+            };
+        }
+
+        let (radius, center) = seg_arc_radius_and_center(v1, v2);
+        PlineSegment::Arc {
+            start: v1.pos(),
+            end: v2.pos(),
+            center,
+            radius,
+            start_angle: angle(center, v1.pos()),
+            end_angle: angle(center, v2.pos()),
+            ccw: v1.bulge_is_pos(),
+        }
+    }
+
+    /// Start point of the segment.
+    #[inline]
+    pub fn start(&self) -> Vector2<T> {
+        match *self {
+            PlineSegment::Line { start, .. } | PlineSegment::Arc { start, .. } => start,
+        }
+    }
+
+    /// End point of the segment.
+    #[inline]
+    pub fn end(&self) -> Vector2<T> {
+        match *self {
+            PlineSegment::Line { end, .. } | PlineSegment::Arc { end, .. } => end,
+        }
+    }
+}
+
+/// Line or arc geometry fully resolved from the bulge encoding (see
+/// [Polyline::to_primitives](crate::Polyline::to_primitives)).
+///
+/// Unlike [PlineSegment] arcs are defined only by their circle and angles (the start and end
+/// points are implied), as used by many exchange formats.
+///
+/// # Examples
+///
+/// ```
+/// # use cavalier_contours::*;
+/// # use cavalier_contours::core_math::*;
+/// // arc half circle arc segment going from (0, 0) to (2, 0) counter clockwise
+/// let v1 = PlineVertex::new(0.0, 0.0, 1.0);
+/// let v2 = PlineVertex::new(2.0, 0.0, 0.0);
+/// match Primitive::from(PlineSegment::new(v1, v2)) {
+///     Primitive::Arc { center, radius, start_angle, end_angle, ccw } => {
+///         assert!(center.fuzzy_eq(Vector2::new(1.0, 0.0)));
+///         assert!(radius.fuzzy_eq(1.0));
+///         assert!(start_angle.fuzzy_eq(std::f64::consts::PI));
+///         assert!(end_angle.fuzzy_eq(0.0));
+///         assert!(ccw);
+///     }
+///     Primitive::Line(..) => unreachable!(),
+/// }
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Primitive<T = f64>
+where
+    T: Real,
+{
+    /// Line going from the first point to the second point.
+    Line(Vector2<T>, Vector2<T>),
+    /// Circular arc.
+    Arc {
+        /// Center of the arc circle.
+        center: Vector2<T>,
+        /// Radius of the arc circle.
+        radius: T,
+        /// Angle of the arc start point relative to `center` (in radians).
+        start_angle: T,
+        /// Angle of the arc end point relative to `center` (in radians).
+        end_angle: T,
+        /// True if the arc goes counter clockwise from `start_angle` to `end_angle`.
+        ccw: bool,
+    },
+}
+
+impl<T> Primitive<T>
+where
+    T: Real,
+{
+    /// Start point of the primitive.
+    pub fn start(&self) -> Vector2<T> {
+        match *self {
+            Primitive::Line(start, _) => start,
+            Primitive::Arc {
+                center,
+                radius,
+                start_angle,
+                ..
+            } => point_on_circle(radius, center, start_angle),
+        }
+    }
+
+    /// End point of the primitive.
+    pub fn end(&self) -> Vector2<T> {
+        match *self {
+            Primitive::Line(_, end) => end,
+            Primitive::Arc {
+                center,
+                radius,
+                end_angle,
+                ..
+            } => point_on_circle(radius, center, end_angle),
+        }
+    }
+}
+
+impl<T> From<PlineSegment<T>> for Primitive<T>
+where
+    T: Real,
+{
+    fn from(seg: PlineSegment<T>) -> Self {
+        match seg {
+            PlineSegment::Line { start, end } => Primitive::Line(start, end),
+            PlineSegment::Arc {
+                center,
+                radius,
+                start_angle,
+                end_angle,
+                ccw,
+                ..
+            } => Primitive::Arc {
+                center,
+                radius,
+                start_angle,
+                end_angle,
+                ccw,
+            },
+        }
+    }
+}
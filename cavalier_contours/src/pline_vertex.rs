@@ -1,30 +1,55 @@
-use crate::{Real, Vector2};
+use crate::{base_traits::is_finite, Real, Vector2};
 
+/// Polyline vertex, `bulge` defines the arc segment going from this vertex to the next vertex.
+///
+/// `data` holds an optional user payload carried with the vertex (e.g. an id or color), it is
+/// `()` by default which adds no size to the vertex.
 #[derive(Debug, Copy, Clone)]
-pub struct PlineVertex<T = f64> {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "T: serde::Serialize, D: serde::Serialize",
+        deserialize = "T: serde::Deserialize<'de>, D: serde::Deserialize<'de> + Default"
+    ))
+)]
+pub struct PlineVertex<T = f64, D = ()> {
     pub x: T,
     pub y: T,
     pub bulge: T,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "is_zero_sized")
+    )]
+    pub data: D,
 }
 
-impl<T> PlineVertex<T>
+#[cfg(feature = "serde")]
+fn is_zero_sized<D>(_: &D) -> bool {
+    core::mem::size_of::<D>() == 0
+}
+
+impl<T, D> PlineVertex<T, D>
 where
     T: Real,
 {
-    pub fn new(x: T, y: T, bulge: T) -> Self {
-        PlineVertex { x, y, bulge }
-    }
-
-    pub fn from_slice(slice: &[T]) -> Option<Self> {
-        if let &[x, y, bulge] = slice {
-            Some(PlineVertex::new(x, y, bulge))
-        } else {
-            None
-        }
+    /// Create a new vertex with user `data` attached.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let v = PlineVertex::with_data(1.0, 2.0, 0.5, 7u32);
+    /// assert_eq!(v.data, 7);
+    /// assert!(v.without_data().fuzzy_eq(PlineVertex::new(1.0, 2.0, 0.5)));
+    /// ```
+    pub fn with_data(x: T, y: T, bulge: T, data: D) -> Self {
+        PlineVertex { x, y, bulge, data }
     }
 
-    pub fn from_vector2(vector2: Vector2<T>, bulge: T) -> Self {
-        PlineVertex::new(vector2.x, vector2.y, bulge)
+    /// Copy of the vertex with the user data removed.
+    pub fn without_data(&self) -> PlineVertex<T> {
+        PlineVertex::new(self.x, self.y, self.bulge)
     }
 
     pub fn pos(&self) -> Vector2<T> {
@@ -43,6 +68,37 @@ where
         self.bulge < T::zero()
     }
 
+    /// Returns true if the x, y, and bulge values are all finite (not NaN or infinite).
+    pub(crate) fn is_finite(&self) -> bool {
+        is_finite(self.x) && is_finite(self.y) && is_finite(self.bulge)
+    }
+}
+
+impl<T> PlineVertex<T>
+where
+    T: Real,
+{
+    pub fn new(x: T, y: T, bulge: T) -> Self {
+        PlineVertex {
+            x,
+            y,
+            bulge,
+            data: (),
+        }
+    }
+
+    pub fn from_slice(slice: &[T]) -> Option<Self> {
+        if let &[x, y, bulge] = slice {
+            Some(PlineVertex::new(x, y, bulge))
+        } else {
+            None
+        }
+    }
+
+    pub fn from_vector2(vector2: Vector2<T>, bulge: T) -> Self {
+        PlineVertex::new(vector2.x, vector2.y, bulge)
+    }
+
     /// Fuzzy equal comparison with another vertex using `fuzzy_epsilon` given.
     pub fn fuzzy_eq_eps(&self, other: Self, fuzzy_epsilon: T) -> bool {
         self.x.fuzzy_eq_eps(other.x, fuzzy_epsilon)
@@ -56,6 +112,66 @@ where
     }
 }
 
+/// Create a vertex from `[x, y, bulge]` array data.
+///
+/// # Examples
+///
+/// ```
+/// # use cavalier_contours::*;
+/// let v: PlineVertex = [1.0, 2.0, 0.5].into();
+/// assert!(v.fuzzy_eq(PlineVertex::new(1.0, 2.0, 0.5)));
+/// let data: [f64; 3] = v.into();
+/// assert_eq!(data, [1.0, 2.0, 0.5]);
+/// ```
+impl<T> From<[T; 3]> for PlineVertex<T>
+where
+    T: Real,
+{
+    fn from(data: [T; 3]) -> Self {
+        PlineVertex::new(data[0], data[1], data[2])
+    }
+}
+
+/// Convert a vertex to `[x, y, bulge]` array data.
+impl<T> From<PlineVertex<T>> for [T; 3]
+where
+    T: Real,
+{
+    fn from(v: PlineVertex<T>) -> Self {
+        [v.x, v.y, v.bulge]
+    }
+}
+
+/// Create a vertex from an `(x, y, bulge)` tuple.
+///
+/// # Examples
+///
+/// ```
+/// # use cavalier_contours::*;
+/// let v: PlineVertex = (1.0, 2.0, 0.5).into();
+/// assert!(v.fuzzy_eq(PlineVertex::new(1.0, 2.0, 0.5)));
+/// let data: (f64, f64, f64) = v.into();
+/// assert_eq!(data, (1.0, 2.0, 0.5));
+/// ```
+impl<T> From<(T, T, T)> for PlineVertex<T>
+where
+    T: Real,
+{
+    fn from((x, y, bulge): (T, T, T)) -> Self {
+        PlineVertex::new(x, y, bulge)
+    }
+}
+
+/// Convert a vertex to an `(x, y, bulge)` tuple.
+impl<T> From<PlineVertex<T>> for (T, T, T)
+where
+    T: Real,
+{
+    fn from(v: PlineVertex<T>) -> Self {
+        (v.x, v.y, v.bulge)
+    }
+}
+
 #[inline(always)]
 pub fn pline_vert<T>(x: T, y: T, bulge: T) -> PlineVertex<T>
 where
@@ -1,138 +1,96 @@
-use std::{
-    ops::{Index, IndexMut},
+use core::{
+    cmp::Ordering,
+    fmt,
+    iter::FromIterator,
+    ops::{ControlFlow, Index, IndexMut},
     slice::Windows,
 };
 
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+use alloc::collections::BinaryHeap;
 use static_aabb2d_index::{StaticAABB2DIndex, StaticAABB2DIndexBuilder, AABB};
 
 use crate::{
-    base_math::angle_from_bulge,
+    base_math::{angle_from_bulge, bulge_from_angle, normalize_radians},
+    base_traits::is_finite,
     core_math::{
         angle, arc_seg_bounding_box, delta_angle, dist_squared, is_left, is_left_or_equal,
-        point_on_circle, seg_arc_radius_and_center, seg_closest_point,
-        seg_fast_approx_bounding_box, seg_length,
+        line_seg_closest_point, midpoint, point_on_circle, seg_arc_radius_and_center,
+        seg_closest_point, seg_fast_approx_bounding_box, seg_length, seg_midpoint,
+        seg_split_at_point, seg_tangent_vector, PlineSegment, Primitive, SplitResult,
+    },
+    intersects::{pline_seg_intr, PlineSegIntr},
+    polyline_intersects::{
+        all_self_intersects, find_intersects, visit_global_self_intersects,
+        visit_local_self_intersects, IntersectPoint, PlineIntersect, PlineOverlappingIntersect,
     },
-    polyline_offset, PlineVertex, Real, Vector2,
+    polyline_offset::{self, OffsetError, OffsetOptions, OffsetWorkspace},
+    PlineVertex, Real, Vector2,
 };
 
+/// Polyline made up of line and arc segments (see [PlineVertex]).
+///
+/// The `D` type parameter is the type of user data carried by each vertex (see
+/// [PlineVertex::with_data]), it defaults to `()` (no data). Vertex user data is preserved by
+/// operations which modify the polyline in place without creating new vertexes (e.g.
+/// [Polyline::translate], [Polyline::scale], [Polyline::rotate], and
+/// [Polyline::invert_direction]). All other operations are only defined for polylines without
+/// user data (use [Polyline::without_data] to drop the data first).
+///
+/// Each polyline caches its segment spatial index (see [Polyline::spatial_index]) so repeated
+/// queries (e.g. offsetting at multiple distances) do not rebuild it, the cache is cleared when
+/// the polyline is modified. With the `std` feature the cache is a `std::sync::OnceLock` and the
+/// polyline is `Sync` (if `T` and `D` are), without `std` it is a `core::cell::OnceCell` and the
+/// polyline is not `Sync` (the `rayon` feature requires `std` for this reason).
 #[derive(Debug, Clone)]
-pub struct Polyline<T = f64> {
-    vertex_data: Vec<PlineVertex<T>>,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "T: serde::Serialize, D: serde::Serialize",
+        deserialize = "T: serde::Deserialize<'de>, D: serde::Deserialize<'de> + Default"
+    ))
+)]
+pub struct Polyline<T = f64, D = ()> {
+    vertex_data: Vec<PlineVertex<T, D>>,
     is_closed: bool,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pos_equal_eps: Option<T>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    spatial_index_cache: SpatialIndexCache<T>,
 }
 
-impl<T> Polyline<T>
+// Cost of the cache is the size of an empty index per polyline and a check that the cache is set
+// (single atomic load with std) on each modification, small compared to building an index which
+// every spatial query would otherwise do. Both OnceLock and OnceCell require Rust 1.70.
+#[cfg(feature = "std")]
+type SpatialIndexCache<T> = std::sync::OnceLock<Option<StaticAABB2DIndex<T>>>;
+#[cfg(not(feature = "std"))]
+type SpatialIndexCache<T> = core::cell::OnceCell<Option<StaticAABB2DIndex<T>>>;
+
+impl<T, D> Polyline<T, D>
 where
     T: Real,
 {
-    /// Create a new empty [Polyline] with `is_closed` set to false.
-    pub fn new() -> Self {
-        Polyline {
-            vertex_data: Vec::new(),
-            is_closed: false,
-        }
-    }
-
-    /// Create a new empty [Polyline] with `is_closed` set to true.
-    pub fn new_closed() -> Self {
-        Polyline {
-            vertex_data: Vec::new(),
-            is_closed: true,
-        }
-    }
-
-    /// Construct a new empty [Polyline] with `is_closed` set to false and some reserved capacity.
-    pub fn with_capacity(capacity: usize) -> Self {
-        Polyline {
-            vertex_data: Vec::with_capacity(capacity),
-            is_closed: false,
-        }
-    }
-
     /// Returns the number of vertexes currently in the polyline.
     pub fn len(&self) -> usize {
         self.vertex_data.len()
     }
 
-    /// Reserves capacity for at least `additional` more elements.
-    pub fn reserve(&mut self, additional: usize) {
-        self.vertex_data.reserve(additional);
-    }
-
-    /// Add a vertex to the polyline by giving the `x`, `y`, and `bulge` values of the vertex.
-    pub fn add(&mut self, x: T, y: T, bulge: T) {
-        self.vertex_data.push(PlineVertex::new(x, y, bulge));
-    }
-
-    /// Add vertex from array data (index 0 = x, 1 = y, 2 = bulge).
-    pub fn add_from_array(&mut self, data: [T; 3]) {
-        self.add(data[0], data[1], data[2]);
-    }
-
-    /// Add a vertex if it's position is not fuzzy equal to the last vertex in the polyline.
-    ///
-    /// If the vertex position is fuzzy equal then just update the bulge of the last vertex with
-    /// the bulge given.
-    pub(crate) fn add_or_replace(&mut self, x: T, y: T, bulge: T, pos_equal_eps: T) {
-        let ln = self.len();
-        if ln == 0 {
-            self.add(x, y, bulge);
-            return;
-        }
-
-        let last_vert = &mut self.vertex_data[ln - 1];
-        if last_vert.x.fuzzy_eq_eps(x, pos_equal_eps) && last_vert.y.fuzzy_eq_eps(y, pos_equal_eps)
-        {
-            last_vert.bulge = bulge;
-            return;
-        }
-
-        self.add(x, y, bulge);
-    }
-
-    /// Add a vertex if it's position is not fuzzy equal to the last vertex in the polyline.
-    ///
-    /// If the vertex position is fuzzy equal then just update the bulge of the last vertex with
-    /// the bulge given.
-    pub(crate) fn add_or_replace_vertex(&mut self, vertex: PlineVertex<T>, pos_equal_eps: T) {
-        self.add_or_replace(vertex.x, vertex.y, vertex.bulge, pos_equal_eps)
-    }
-
-    /// Returns the next wrapping vertex index for the polyline.
-    ///
-    /// If `i + 1 >= self.len()` then 0 is returned, otherwise `i + 1` is returned.
-    pub fn next_wrapping_index(&self, i: usize) -> usize {
-        let next = i + 1;
-        if next >= self.len() {
-            0
-        } else {
-            next
-        }
-    }
-
-    /// Returns the previous wrapping vertex index for the polyline.
-    ///
-    /// If `i == 0` then `self.len() - 1` is returned, otherwise `i - 1` is returned.
-    pub fn prev_wrapping_index(&self, i: usize) -> usize {
-        if i == 0 {
-            self.len() - 1
-        } else {
-            i - 1
-        }
-    }
-
     /// Add a vertex to the polyline by giving a [PlineVertex](crate::PlineVertex).
-    pub fn add_vertex(&mut self, vertex: PlineVertex<T>) {
+    pub fn add_vertex(&mut self, vertex: PlineVertex<T, D>) {
+        self.invalidate_spatial_index();
         self.vertex_data.push(vertex);
     }
 
-    /// Copy all vertexes from other to the end of this polyline.
-    pub fn extend_vertexes(&mut self, other: &Polyline<T>) {
-        self.vertex_data.extend(other.vertex_data.iter());
-    }
-
     /// Remove vertex at index.
     pub fn remove(&mut self, index: usize) {
+        self.invalidate_spatial_index();
         self.vertex_data.remove(index);
     }
 
@@ -143,9 +101,18 @@ where
 
     /// Clear all vertexes.
     pub fn clear(&mut self) {
+        self.invalidate_spatial_index();
         self.vertex_data.clear();
     }
 
+    /// Reset the polyline to the same state as a newly created open polyline while keeping the
+    /// allocated vertex buffer (used to reuse polylines as scratch buffers).
+    pub(crate) fn reset(&mut self) {
+        self.clear();
+        self.is_closed = false;
+        self.pos_equal_eps = None;
+    }
+
     /// Returns true if the polyline is closed, false if it is open.
     pub fn is_closed(&self) -> bool {
         self.is_closed
@@ -153,35 +120,28 @@ where
 
     /// Allows modifying whether the polyline is closed or not.
     pub fn set_is_closed(&mut self, is_closed: bool) {
+        self.invalidate_spatial_index();
         self.is_closed = is_closed;
     }
 
-    pub fn last(&self) -> Option<&PlineVertex<T>> {
+    pub fn last(&self) -> Option<&PlineVertex<T, D>> {
         self.vertex_data.last()
     }
 
-    pub fn last_mut(&mut self) -> Option<&mut PlineVertex<T>> {
+    pub fn last_mut(&mut self) -> Option<&mut PlineVertex<T, D>> {
+        self.invalidate_spatial_index();
         self.vertex_data.last_mut()
     }
 
-    /// Set the vertex data at a given index of the polyline.
-    pub fn set_vertex(&mut self, index: usize, x: T, y: T, bulge: T) {
-        self.vertex_data[index].x = x;
-        self.vertex_data[index].y = y;
-        self.vertex_data[index].bulge = bulge;
-    }
-
-    /// Fuzzy equal comparison with another polyline using `fuzzy_epsilon` given.
-    pub fn fuzzy_eq_eps(&self, other: &Self, fuzzy_epsilon: T) -> bool {
-        self.vertex_data
-            .iter()
-            .zip(&other.vertex_data)
-            .all(|(v1, v2)| v1.fuzzy_eq_eps(*v2, fuzzy_epsilon))
+    /// Iterate through all the vertexes in the polyline.
+    pub fn iter(&self) -> impl Iterator<Item = &PlineVertex<T, D>> {
+        self.vertex_data.iter()
     }
 
-    /// Fuzzy equal comparison with another vertex using T::fuzzy_epsilon().
-    pub fn fuzzy_eq(&self, other: &Self) -> bool {
-        self.fuzzy_eq_eps(other, T::fuzzy_epsilon())
+    /// Iterate through all the vertexes in the polyline as mutable references.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut PlineVertex<T, D>> {
+        self.invalidate_spatial_index();
+        self.vertex_data.iter_mut()
     }
 
     /// Invert/reverse the direction of the polyline in place.
@@ -190,13 +150,16 @@ where
     /// and then shifting (by 1 position) and inverting the sign of all the bulge values.
     /// E.g. after reversing the vertex the bulge at index 0 becomes negative bulge at index 1.
     /// The end result for a closed polyline is the direction will be changed
-    /// from clockwise to counter clockwise or vice versa.
+    /// from clockwise to counter clockwise or vice versa. For an open polyline the path is traced
+    /// in reverse (same geometry going from the last vertex to the first vertex), the bulge of the
+    /// last vertex after inverting is set to zero since it does not belong to any segment.
     pub fn invert_direction(&mut self) {
         let ln = self.len();
         if ln < 2 {
             return;
         }
 
+        self.invalidate_spatial_index();
         self.vertex_data.reverse();
 
         let first_bulge = self[0].bulge;
@@ -206,6 +169,8 @@ where
 
         if self.is_closed {
             self[ln - 1].bulge = -first_bulge;
+        } else {
+            self[ln - 1].bulge = T::zero();
         }
     }
 
@@ -231,6 +196,30 @@ where
         }
     }
 
+    /// Uniformly scale the polyline in the xy plane by `scale_factor` relative to an `origin` point.
+    ///
+    /// Bulge values are left unchanged since arcs are preserved under uniform scaling.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline = Polyline::new();
+    /// polyline.add(2.0, 2.0, 0.5);
+    /// polyline.add(4.0, 4.0, 1.0);
+    /// polyline.scale_about(2.0, Vector2::new(2.0, 2.0));
+    /// let mut expected = Polyline::new();
+    /// expected.add(2.0, 2.0, 0.5);
+    /// expected.add(6.0, 6.0, 1.0);
+    /// assert!(polyline.fuzzy_eq(&expected));
+    /// ```
+    pub fn scale_about(&mut self, scale_factor: T, origin: Vector2<T>) {
+        for v in self.iter_mut() {
+            v.x = origin.x + (v.x - origin.x) * scale_factor;
+            v.y = origin.y + (v.y - origin.y) * scale_factor;
+        }
+    }
+
     /// Translate the polyline by some `x_offset` and `y_offset`.
     ///
     /// # Examples
@@ -253,843 +242,8547 @@ where
         }
     }
 
-    /// Compute the XY extents of the polyline.
+    /// Rotate the polyline about an `origin` point by some `angle` in radians.
     ///
-    /// Returns `None` if polyline is empty. If polyline has only one vertex then
-    /// `min_x = max_x = polyline[0].x` and `min_y = max_y = polyline[0].y`.
+    /// Positive `angle` rotates counter clockwise. Bulge values are left unchanged since arcs are
+    /// preserved under rotation.
     ///
     /// # Examples
     ///
     /// ```
     /// # use cavalier_contours::*;
     /// let mut polyline = Polyline::new();
-    /// assert_eq!(polyline.extents(), None);
-    /// polyline.add(1.0, 1.0, 1.0);
-    /// let pt_extents = polyline.extents().unwrap();
-    /// assert!(pt_extents.min_x.fuzzy_eq(1.0));
-    /// assert!(pt_extents.min_y.fuzzy_eq(1.0));
-    /// assert!(pt_extents.max_x.fuzzy_eq(1.0));
-    /// assert!(pt_extents.max_y.fuzzy_eq(1.0));
+    /// polyline.add(2.0, 2.0, 0.5);
+    /// polyline.add(4.0, 2.0, 1.0);
+    /// polyline.rotate(std::f64::consts::FRAC_PI_2, Vector2::new(2.0, 2.0));
+    /// let mut expected = Polyline::new();
+    /// expected.add(2.0, 2.0, 0.5);
+    /// expected.add(2.0, 4.0, 1.0);
+    /// assert!(polyline.fuzzy_eq(&expected));
+    /// ```
+    pub fn rotate(&mut self, angle: T, origin: Vector2<T>) {
+        for v in self.iter_mut() {
+            let rotated = v.pos().rotate_about(origin, angle);
+            v.x = rotated.x;
+            v.y = rotated.y;
+        }
+    }
+
+    /// Snap the sweep angle of each arc segment to the nearest multiple of `increment` (in
+    /// radians) by recomputing its bulge.
     ///
-    /// polyline.add(3.0, 1.0, 1.0);
-    /// let extents = polyline.extents().unwrap();
-    /// assert!(extents.min_x.fuzzy_eq(1.0));
-    /// assert!(extents.min_y.fuzzy_eq(0.0));
-    /// assert!(extents.max_x.fuzzy_eq(3.0));
-    /// assert!(extents.max_y.fuzzy_eq(1.0));
+    /// Vertex positions are kept fixed so snapping an arc changes its radius and center, the curve
+    /// between the end points moves (further for larger changes in sweep angle), this may
+    /// introduce self intersects or change the tangent continuity at the arc end points. Arcs
+    /// whose sweep angle rounds to zero become line segments, arcs whose sweep angle rounds to a
+    /// full circle are snapped one `increment` less. Does nothing if `increment` is not positive.
+    ///
+    /// # Examples
     ///
-    /// polyline.set_is_closed(true);
-    /// let extents = polyline.extents().unwrap();
-    /// assert!(extents.min_x.fuzzy_eq(1.0));
-    /// assert!(extents.min_y.fuzzy_eq(0.0));
-    /// assert!(extents.max_x.fuzzy_eq(3.0));
-    /// assert!(extents.max_y.fuzzy_eq(2.0));
     /// ```
-    pub fn extents(&self) -> Option<AABB<T>> {
-        if self.len() == 0 {
-            return None;
+    /// # use cavalier_contours::*;
+    /// # use cavalier_contours::core_math::*;
+    /// let mut polyline = Polyline::new();
+    /// polyline.add(0.0, 0.0, bulge_from_angle(91f64.to_radians()));
+    /// polyline.add(2.0, 0.0, 0.0);
+    /// polyline.snap_arc_sweeps(15f64.to_radians());
+    /// assert!(angle_from_bulge(polyline[0].bulge).fuzzy_eq(90f64.to_radians()));
+    /// assert!(polyline[1].pos().fuzzy_eq(Vector2::new(2.0, 0.0)));
+    /// ```
+    pub fn snap_arc_sweeps(&mut self, increment: T) {
+        if increment <= T::zero() {
+            return;
         }
 
-        let mut result = AABB::new(self[0].x, self[0].y, self[0].x, self[0].y);
-
-        for (v1, v2) in self.iter_segments() {
-            if v1.bulge_is_zero() {
-                // line segment, just look at end of line point
-                if v2.x < result.min_x {
-                    result.min_x = v2.x;
-                } else if v2.x > result.max_x {
-                    result.max_x = v2.x;
-                }
-
-                if v2.y < result.min_y {
-                    result.min_y = v2.y;
-                } else if v2.y > result.max_y {
-                    result.max_y = v2.y;
-                }
+        let seg_count = match self.len() {
+            0 | 1 => 0,
+            len if self.is_closed => len,
+            len => len - 1,
+        };
 
+        for v in self.iter_mut().take(seg_count) {
+            if v.bulge_is_zero() {
                 continue;
             }
-            // else arc segment
-            let arc_extents = arc_seg_bounding_box(v1, v2);
 
-            result.min_x = num_traits::real::Real::min(result.min_x, arc_extents.min_x);
-            result.min_y = num_traits::real::Real::min(result.min_y, arc_extents.min_y);
-            result.max_x = num_traits::real::Real::max(result.max_x, arc_extents.max_x);
-            result.max_y = num_traits::real::Real::max(result.max_y, arc_extents.max_y);
+            let sweep = angle_from_bulge(v.bulge);
+            let mut snapped = (sweep.abs() / increment).round() * increment;
+            if snapped >= T::tau() {
+                snapped = snapped - increment;
+            }
+
+            v.bulge = if snapped > T::zero() {
+                bulge_from_angle(snapped) * v.bulge.signum()
+            } else {
+                T::zero()
+            };
         }
+    }
 
-        Some(result)
+    /// Clear the cached spatial index, must be called whenever the vertexes or closed state of the
+    /// polyline change.
+    fn invalidate_spatial_index(&mut self) {
+        self.spatial_index_cache.take();
     }
 
-    pub fn create_approx_spatial_index(&self) -> Option<StaticAABB2DIndex<T>> {
-        let ln = self.len();
-        if ln < 2 {
-            return None;
+    /// Copy of the polyline with the vertex user data removed.
+    ///
+    /// Most operations (e.g. offsetting, boolean operations, and arc approximation) are defined
+    /// for polylines without vertex user data since they create new vertexes which have no data to
+    /// carry, use this to run those operations on a polyline that has vertex user data.
+    pub fn without_data(&self) -> Polyline<T> {
+        Polyline {
+            vertex_data: self.vertex_data.iter().map(|v| v.without_data()).collect(),
+            is_closed: self.is_closed,
+            pos_equal_eps: self.pos_equal_eps,
+            spatial_index_cache: SpatialIndexCache::new(),
         }
+    }
+}
 
-        let seg_count = if self.is_closed { ln } else { ln - 1 };
-
-        let mut builder = StaticAABB2DIndexBuilder::new(seg_count);
-
-        for i in 0..ln - 1 {
-            let approx_aabb = seg_fast_approx_bounding_box(self[i], self[i + 1]);
-            builder.add(
-                approx_aabb.min_x,
-                approx_aabb.min_y,
-                approx_aabb.max_x,
-                approx_aabb.max_y,
-            );
+impl<T> Polyline<T>
+where
+    T: Real,
+{
+    /// Create a new empty [Polyline] with `is_closed` set to false.
+    pub fn new() -> Self {
+        Polyline {
+            vertex_data: Vec::new(),
+            is_closed: false,
+            pos_equal_eps: None,
+            spatial_index_cache: SpatialIndexCache::new(),
         }
+    }
 
-        if self.is_closed {
-            // add final segment from last to first
-            let approx_aabb = seg_fast_approx_bounding_box(*self.last().unwrap(), self[0]);
-            builder.add(
-                approx_aabb.min_x,
-                approx_aabb.min_y,
-                approx_aabb.max_x,
-                approx_aabb.max_y,
-            );
+    /// Create a new empty [Polyline] with `is_closed` set to true.
+    pub fn new_closed() -> Self {
+        Polyline {
+            vertex_data: Vec::new(),
+            is_closed: true,
+            pos_equal_eps: None,
+            spatial_index_cache: SpatialIndexCache::new(),
         }
-
-        builder.build().ok()
     }
 
-    /// Visit all the polyline segments (represented as polyline vertex pairs) with a function/closure.
-    ///
-    /// This is equivalent to [Polyline::iter_segments] but uses a visiting function rather than an iterator.
-    pub fn visit_segments<F>(&self, visitor: &mut F)
-    where
-        F: FnMut(PlineVertex<T>, PlineVertex<T>) -> bool,
-    {
-        let ln = self.vertex_data.len();
-        if ln < 2 {
-            return;
+    /// Construct a new empty [Polyline] with `is_closed` set to false and some reserved capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Polyline {
+            vertex_data: Vec::with_capacity(capacity),
+            is_closed: false,
+            pos_equal_eps: None,
+            spatial_index_cache: SpatialIndexCache::new(),
         }
+    }
 
-        if self.is_closed {
-            let v1 = self.vertex_data[ln - 1];
-            let v2 = self.vertex_data[0];
-            if !visitor(v1, v2) {
-                return;
-            }
+    /// Reserves capacity for at least `additional` more elements.
+    pub fn reserve(&mut self, additional: usize) {
+        self.vertex_data.reserve(additional);
+    }
+
+    /// Add a vertex to the polyline by giving the `x`, `y`, and `bulge` values of the vertex.
+    pub fn add(&mut self, x: T, y: T, bulge: T) {
+        self.invalidate_spatial_index();
+        self.vertex_data.push(PlineVertex::new(x, y, bulge));
+    }
+
+    /// Add a vertex to the polyline if its position is not fuzzy equal to the last vertex in the
+    /// polyline (using [Polyline::pos_equal_eps]).
+    ///
+    /// If the vertex position is fuzzy equal then just update the bulge of the last vertex with
+    /// the bulge given.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new();
+    /// polyline.set_pos_equal_eps(0.01);
+    /// polyline.add_or_merge(0.0, 0.0, 0.0);
+    /// polyline.add_or_merge(0.001, 0.0, 1.0);
+    /// assert_eq!(polyline.len(), 1);
+    /// assert!(polyline[0].fuzzy_eq(PlineVertex::new(0.0, 0.0, 1.0)));
+    /// ```
+    pub fn add_or_merge(&mut self, x: T, y: T, bulge: T) {
+        self.add_or_replace(x, y, bulge, self.pos_equal_eps());
+    }
+
+    /// Add vertex from array data (index 0 = x, 1 = y, 2 = bulge).
+    pub fn add_from_array(&mut self, data: [T; 3]) {
+        self.add(data[0], data[1], data[2]);
+    }
+
+    /// Add a vertex if it's position is not fuzzy equal to the last vertex in the polyline.
+    ///
+    /// If the vertex position is fuzzy equal then just update the bulge of the last vertex with
+    /// the bulge given.
+    pub(crate) fn add_or_replace(&mut self, x: T, y: T, bulge: T, pos_equal_eps: T) {
+        let ln = self.len();
+        if ln == 0 {
+            self.add(x, y, bulge);
+            return;
         }
 
-        let mut windows = self.vertex_data.windows(2);
-        while let Some(&[v1, v2]) = windows.next() {
-            if !visitor(v1, v2) {
-                break;
-            }
+        self.invalidate_spatial_index();
+        let last_vert = &mut self.vertex_data[ln - 1];
+        if last_vert.x.fuzzy_eq_eps(x, pos_equal_eps) && last_vert.y.fuzzy_eq_eps(y, pos_equal_eps)
+        {
+            last_vert.bulge = bulge;
+            return;
         }
+
+        self.add(x, y, bulge);
     }
 
-    /// Iterate through all the vertexes in the polyline.
-    pub fn iter(&self) -> impl Iterator<Item = &PlineVertex<T>> {
-        self.vertex_data.iter()
+    /// Add a vertex if it's position is not fuzzy equal to the last vertex in the polyline.
+    ///
+    /// If the vertex position is fuzzy equal then just update the bulge of the last vertex with
+    /// the bulge given.
+    pub(crate) fn add_or_replace_vertex(&mut self, vertex: PlineVertex<T>, pos_equal_eps: T) {
+        self.add_or_replace(vertex.x, vertex.y, vertex.bulge, pos_equal_eps)
     }
 
-    /// Iterate through all the vertexes in the polyline as mutable references.
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut PlineVertex<T>> {
-        self.vertex_data.iter_mut()
+    /// Returns the next wrapping vertex index for the polyline.
+    ///
+    /// If `i + 1 >= self.len()` then 0 is returned, otherwise `i + 1` is returned.
+    pub fn next_wrapping_index(&self, i: usize) -> usize {
+        let next = i + 1;
+        if next >= self.len() {
+            0
+        } else {
+            next
+        }
     }
 
-    /// Iterate through all the polyline segments (represented as polyline vertex pairs).
+    /// Returns the previous wrapping vertex index for the polyline.
     ///
-    /// This is equivalent to [Polyline::visit_segments] but returns an iterator rather than accepting a function.
-    pub fn iter_segments<'a>(
-        &'a self,
-    ) -> impl Iterator<Item = (PlineVertex<T>, PlineVertex<T>)> + 'a {
-        PlineSegIterator::new(&self)
+    /// If `i == 0` then `self.len() - 1` is returned, otherwise `i - 1` is returned.
+    pub fn prev_wrapping_index(&self, i: usize) -> usize {
+        if i == 0 {
+            self.len() - 1
+        } else {
+            i - 1
+        }
     }
 
-    /// Iterate through all the polyline segment vertex positional indexes.
+    /// Insert a vertex at `index`, shifting all vertexes after it.
     ///
-    /// Segments are represented by polyline vertex pairs, for each vertex there is
-    /// an associated positional index in the polyline, this method iterates through
-    /// those positional indexes as segment pairs.
-    pub fn iter_segment_indexes(&self) -> impl Iterator<Item = (usize, usize)> {
-        PlineSegIndexIterator::new(self.vertex_data.len(), self.is_closed)
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, x: T, y: T, bulge: T) {
+        self.insert_vertex(index, PlineVertex::new(x, y, bulge));
     }
 
-    pub fn parallel_offset(
-        &self,
-        offset: T,
-        spatial_index: Option<&StaticAABB2DIndex<T>>,
-    ) -> Vec<Polyline<T>> {
-        polyline_offset::parallel_offset(self, offset, spatial_index, None)
+    /// Insert a vertex at `index` by giving a [PlineVertex](crate::PlineVertex), shifting all
+    /// vertexes after it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn insert_vertex(&mut self, index: usize, vertex: PlineVertex<T>) {
+        self.invalidate_spatial_index();
+        self.vertex_data.insert(index, vertex);
     }
 
-    /// Compute the closed signed area of the polyline.
+    /// Copy all vertexes from other to the end of this polyline.
+    pub fn extend_vertexes(&mut self, other: &Polyline<T>) {
+        self.invalidate_spatial_index();
+        self.vertex_data.extend(other.vertex_data.iter());
+    }
+
+    /// Retain only the vertexes for which `f` returns true (same as [Vec::retain]), the closed
+    /// state of the polyline is unchanged.
     ///
-    /// If [Polyline::is_closed] is false (open polyline) then 0.0 is always returned.
-    /// The area is signed such that if the polyline direction is counter clockwise
-    /// then the area is positive, otherwise it is negative.
+    /// Removing a vertex changes the geometry of the segments adjacent to it, bulge values of the
+    /// remaining vertexes are left as is (it is up to the caller to update them if required).
     ///
     /// # Examples
     ///
     /// ```
     /// # use cavalier_contours::*;
     /// let mut polyline: Polyline = Polyline::new();
-    /// assert!(polyline.area().fuzzy_eq(0.0));
-    /// polyline.add(1.0, 1.0, 1.0);
-    /// assert!(polyline.area().fuzzy_eq(0.0));
-    ///
-    /// polyline.add(3.0, 1.0, 1.0);
-    /// // polyline is still open so area is 0
-    /// assert!(polyline.area().fuzzy_eq(0.0));
-    /// polyline.set_is_closed(true);
-    /// assert!(polyline.area().fuzzy_eq(std::f64::consts::PI));
-    /// polyline.invert_direction();
-    /// assert!(polyline.area().fuzzy_eq(-std::f64::consts::PI));
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(1.0, 0.0, 0.0);
+    /// polyline.add(2.0, 0.0, 0.0);
+    /// polyline.retain_vertexes(|v| v.x != 1.0);
+    /// assert_eq!(polyline.len(), 2);
+    /// assert!(polyline[1].fuzzy_eq(PlineVertex::new(2.0, 0.0, 0.0)));
     /// ```
-    pub fn area(&self) -> T {
-        if !self.is_closed {
-            return T::zero();
-        }
+    pub fn retain_vertexes<F>(&mut self, f: F)
+    where
+        F: FnMut(&PlineVertex<T>) -> bool,
+    {
+        self.invalidate_spatial_index();
+        self.vertex_data.retain(f);
+    }
 
-        // Implementation notes:
-        // Using the shoelace formula (https://en.wikipedia.org/wiki/Shoelace_formula) modified to support
-        // arcs defined by a bulge value. The shoelace formula returns a negative value for clockwise
-        // oriented polygons and positive value for counter clockwise oriented polygons. The area of each
-        // circular segment defined by arcs is then added if it is a counter clockwise arc or subtracted
-        // if it is a clockwise arc. The area of the circular segments are computed by finding the area of
-        // the arc sector minus the area of the triangle defined by the chord and center of circle.
-        // See https://en.wikipedia.org/wiki/Circular_segment
+    /// Epsilon value used to determine if two vertex positions are fuzzy equal, defaults to `1e-5`
+    /// if not set.
+    ///
+    /// This is the position equal epsilon used by all the methods which do not take one as a
+    /// parameter, e.g. [Polyline::add_or_merge], [Polyline::find_self_intersects],
+    /// [Polyline::split_at_arc_length], [Polyline::clip_to] and offsetting with default options
+    /// (e.g. by [Polyline::parallel_offset]).
+    pub fn pos_equal_eps(&self) -> T {
+        self.pos_equal_eps.unwrap_or_else(|| T::from(1e-5).unwrap())
+    }
 
-        let mut double_total_area = T::zero();
+    /// Set the epsilon value used to determine if two vertex positions are fuzzy equal (see
+    /// [Polyline::pos_equal_eps]).
+    pub fn set_pos_equal_eps(&mut self, pos_equal_eps: T) {
+        self.pos_equal_eps = Some(pos_equal_eps);
+    }
 
-        for (v1, v2) in self.iter_segments() {
-            double_total_area = double_total_area + v1.x * v2.y - v1.y * v2.x;
-            if !v1.bulge_is_zero() {
-                // add arc segment area
-                let b = v1.bulge.abs();
-                let sweep_angle = angle_from_bulge(b);
-                let triangle_base = (v2.pos() - v1.pos()).length();
-                let radius = triangle_base * ((b * b + T::one()) / (T::four() * b));
-                let sagitta = b * triangle_base / T::two();
-                let triangle_height = radius - sagitta;
-                let double_sector_area = sweep_angle * radius * radius;
-                let double_triangle_area = triangle_base * triangle_height;
-                let mut double_arc_area = double_sector_area - double_triangle_area;
-                if v1.bulge_is_neg() {
-                    double_arc_area = -double_arc_area;
-                }
+    /// Returns true if all vertex values (x, y, and bulge) of the polyline are finite (not NaN or
+    /// infinite).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// assert!(polyline.is_finite());
+    /// polyline.add(f64::INFINITY, 1.0, 0.0);
+    /// assert!(!polyline.is_finite());
+    /// ```
+    pub fn is_finite(&self) -> bool {
+        self.first_non_finite_index().is_none()
+    }
 
-                double_total_area = double_total_area + double_arc_area;
-            }
+    /// Validate the polyline vertex data, returns [PlineError::NonFiniteVertex] with the index of
+    /// the first vertex that has a non-finite value (NaN or infinite x, y, or bulge).
+    ///
+    /// Computations on a polyline with non-finite values produce meaningless results, this can be
+    /// used to check polylines from untrusted sources before processing them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// assert_eq!(polyline.validate(), Ok(()));
+    /// polyline.add(1.0, 1.0, f64::NAN);
+    /// assert_eq!(polyline.validate(), Err(PlineError::NonFiniteVertex { index: 1 }));
+    /// ```
+    pub fn validate(&self) -> Result<(), PlineError> {
+        match self.first_non_finite_index() {
+            Some(index) => Err(PlineError::NonFiniteVertex { index }),
+            None => Ok(()),
         }
+    }
 
-        double_total_area / T::two()
+    pub(crate) fn first_non_finite_index(&self) -> Option<usize> {
+        self.iter().position(|v| !v.is_finite())
     }
 
-    /// Find the closest segment point on a polyline to a `point` given.
+    /// Set the vertex data at a given index of the polyline.
+    pub fn set_vertex(&mut self, index: usize, x: T, y: T, bulge: T) {
+        self.invalidate_spatial_index();
+        self.vertex_data[index].x = x;
+        self.vertex_data[index].y = y;
+        self.vertex_data[index].bulge = bulge;
+    }
+
+    /// Fuzzy equal comparison with another polyline using `fuzzy_epsilon` given.
+    pub fn fuzzy_eq_eps(&self, other: &Self, fuzzy_epsilon: T) -> bool {
+        self.vertex_data
+            .iter()
+            .zip(&other.vertex_data)
+            .all(|(v1, v2)| v1.fuzzy_eq_eps(*v2, fuzzy_epsilon))
+    }
+
+    /// Fuzzy equal comparison with another vertex using T::fuzzy_epsilon().
+    pub fn fuzzy_eq(&self, other: &Self) -> bool {
+        self.fuzzy_eq_eps(other, T::fuzzy_epsilon())
+    }
+
+    /// Returns true if this polyline and `other` are both closed and represent the same loop,
+    /// comparing vertexes with `eps`.
     ///
-    /// If the polyline is empty then `None` is returned.
+    /// Unlike [Polyline::fuzzy_eq_eps] the polylines may start at different vertexes and may have
+    /// opposite directions (e.g. the same loop inverted with [Polyline::invert_direction]), the
+    /// vertex count must be the same. Returns false if either polyline is open.
     ///
     /// # Examples
     ///
     /// ```
     /// # use cavalier_contours::*;
-    /// let mut polyline: Polyline = Polyline::new();
-    /// assert!(matches!(polyline.closest_point(Vector2::zero()), None));
-    /// polyline.add(1.0, 1.0, 1.0);
-    /// let result = polyline.closest_point(Vector2::new(1.0, 0.0)).unwrap();
-    /// assert_eq!(result.seg_start_index, 0);
-    /// assert!(result.seg_point.fuzzy_eq(polyline[0].pos()));
-    /// assert!(result.distance.fuzzy_eq(1.0));
+    /// let mut square: Polyline = Polyline::new_closed();
+    /// square.add(0.0, 0.0, 0.0);
+    /// square.add(1.0, 0.0, 0.0);
+    /// square.add(1.0, 1.0, 0.0);
+    /// square.add(0.0, 1.0, 0.0);
+    /// let mut rotated = square.clone();
+    /// rotated.set_start_vertex(2);
+    /// assert!(!square.fuzzy_eq(&rotated));
+    /// assert!(square.same_loop(&rotated, 1e-5));
     /// ```
-    pub fn closest_point(&self, point: Vector2<T>) -> Option<ClosestPointResult<T>> {
-        if self.len() == 0 {
-            return None;
+    pub fn same_loop(&self, other: &Self, eps: T) -> bool {
+        if !self.is_closed || !other.is_closed || self.len() != other.len() {
+            return false;
         }
 
-        let mut result = ClosestPointResult {
-            seg_start_index: 0,
-            seg_point: self[0].pos(),
-            distance: Real::max_value(),
-        };
-
-        if self.len() == 1 {
-            result.distance = (result.seg_point - point).length();
-            return Some(result);
+        let n = self.len();
+        if n == 0 {
+            return true;
         }
 
-        let mut dist_squared = Real::max_value();
+        let matches_with_offset = |other: &Self| -> bool {
+            (0..n).any(|k| (0..n).all(|i| self[i].fuzzy_eq_eps(other[(i + k) % n], eps)))
+        };
 
-        for (i, j) in self.iter_segment_indexes() {
-            let v1 = self[i];
-            let v2 = self[j];
-            let cp = seg_closest_point(v1, v2, point);
-            let diff_v = point - cp;
-            let dist2 = diff_v.length_squared();
-            if dist2 < dist_squared {
-                result.seg_start_index = i;
-                result.seg_point = cp;
-                dist_squared = dist2;
-            }
+        if matches_with_offset(other) {
+            return true;
         }
 
-        result.distance = dist_squared.sqrt();
-
-        Some(result)
+        let mut inverted = other.clone();
+        inverted.invert_direction();
+        matches_with_offset(&inverted)
     }
 
-    /// Returns the total path length of the polyline.
+    /// Returns true if this polyline and `other` have the same geometry (path) within `eps`.
+    ///
+    /// Unlike [Polyline::fuzzy_eq_eps] the geometry is compared rather than the raw vertex values,
+    /// so differences in representation are tolerated, e.g. a line segment compared with an arc
+    /// segment with a tiny bulge, or a line segment compared with the same line split by an extra
+    /// vertex. The polylines must both be open or both be closed and have path lengths within
+    /// `eps`, then each vertex position and segment midpoint of each polyline must be within `eps`
+    /// of the other polyline path. Direction and start vertex are not compared.
     ///
     /// # Examples
     ///
     /// ```
     /// # use cavalier_contours::*;
-    /// let mut polyline: Polyline = Polyline::new();
-    /// // open polyline half circle
-    /// polyline.add(0.0, 0.0, 1.0);
-    /// polyline.add(2.0, 0.0, 1.0);
-    /// assert!(polyline.path_length().fuzzy_eq(std::f64::consts::PI));
-    /// // close into full circle
-    /// polyline.set_is_closed(true);
-    /// assert!(polyline.path_length().fuzzy_eq(2.0 * std::f64::consts::PI));
+    /// let mut line: Polyline = Polyline::new();
+    /// line.add(0.0, 0.0, 0.0);
+    /// line.add(2.0, 0.0, 0.0);
+    /// let mut split_line: Polyline = Polyline::new();
+    /// split_line.add(0.0, 0.0, 0.0);
+    /// split_line.add(1.0, 0.0, 0.0);
+    /// split_line.add(2.0, 0.0, 0.0);
+    /// assert!(!line.fuzzy_eq(&split_line));
+    /// assert!(line.geom_eq(&split_line, 1e-5));
     /// ```
-    pub fn path_length(&self) -> T {
-        self.iter_segments()
-            .fold(T::zero(), |acc, (v1, v2)| acc + seg_length(v1, v2))
-    }
+    pub fn geom_eq(&self, other: &Self, eps: T) -> bool {
+        if self.is_closed != other.is_closed {
+            return false;
+        }
 
-    /// Helper function for processing a line segment when computing the winding number.
-    fn process_line_winding(v1: PlineVertex<T>, v2: PlineVertex<T>, point: Vector2<T>) -> i32 {
-        let mut result = 0;
-        if v1.y <= point.y {
-            if v2.y > point.y && is_left(v1.pos(), v2.pos(), point) {
-                // left and upward crossing
-                result += 1;
-            }
-        } else if v2.y <= point.y && !is_left(v1.pos(), v2.pos(), point) {
-            // right an downward crossing
-            result -= 1;
+        match (self.len(), other.len()) {
+            (0, 0) => return true,
+            (0, _) | (_, 0) => return false,
+            _ => {}
         }
 
-        result
-    }
-
-    /// Helper function for processing an arc segment when computing the winding number.
-    fn process_arc_winding(v1: PlineVertex<T>, v2: PlineVertex<T>, point: Vector2<T>) -> i32 {
-        let is_ccw = v1.bulge_is_pos();
-        let point_is_left = if is_ccw {
-            is_left(v1.pos(), v2.pos(), point)
-        } else {
-            is_left_or_equal(v1.pos(), v2.pos(), point)
-        };
+        if !self.path_length().fuzzy_eq_eps(other.path_length(), eps) {
+            return false;
+        }
 
-        let dist_to_arc_center_less_than_radius = || {
-            let (arc_radius, arc_center) = seg_arc_radius_and_center(v1, v2);
-            let dist2 = dist_squared(arc_center, point);
-            dist2 < arc_radius * arc_radius
+        let points_on_path = |from: &Self, to: &Self| -> bool {
+            let on_path =
+                |point: Vector2<T>| matches!(to.closest_point(point), Some(c) if c.distance <= eps);
+            from.iter().all(|v| on_path(v.pos()))
+                && from
+                    .iter_segments()
+                    .all(|(v1, v2)| on_path(seg_midpoint(v1, v2)))
         };
 
-        let mut result = 0;
-
-        if v1.y <= point.y {
-            if v2.y > point.y {
-                // upward crossing of arc chord
-                if is_ccw {
-                    if point_is_left {
-                        // counter clockwise arc left of chord
-                        result += 1;
-                    } else {
-                        // counter clockwise arc right of chord
-                        if dist_to_arc_center_less_than_radius() {
-                            result += 1;
-                        }
-                    }
-                } else {
-                    if point_is_left {
-                        // clockwise arc left of chord
-                        if !dist_to_arc_center_less_than_radius() {
-                            result += 1;
-                        }
-                        // else clockwise arc right of chord, no crossing
-                    }
-                }
-            } else {
-                // not crossing arc chord and chord is below, check if point is inside arc sector
-                if is_ccw && !point_is_left {
-                    if v2.x < point.x && point.x < v1.x && dist_to_arc_center_less_than_radius() {
-                        result += 1;
-                    }
-                } else if !is_ccw && point_is_left {
-                    if v1.x < point.x && point.x < v2.x && dist_to_arc_center_less_than_radius() {
-                        result -= 1;
-                    }
-                }
-            }
-        } else if v2.y <= point.y {
-            // downward crossing of arc chord
-            if is_ccw {
-                if !point_is_left {
-                    // counter clockwise arc right of chord
-                    if dist_to_arc_center_less_than_radius() {
-                        result -= 1;
-                    }
-                }
-            // else counter clockwise arc left of chord, no crossing
-            } else {
-                if point_is_left {
-                    // clockwise arc left of chord
-                    if dist_to_arc_center_less_than_radius() {
-                        result -= 1;
-                    }
-                } else {
-                    // clockwise arc right of chord
-                    result -= 1;
-                }
-            }
-        } else {
-            // not crossing arc chord and chord is above, check if point is inside arc sector
-            if is_ccw && !point_is_left {
-                if v1.x < point.x && point.x < v2.x && dist_to_arc_center_less_than_radius() {
-                    result += 1;
-                }
-            } else {
-                if v2.x < point.x && point.x < v1.x && dist_to_arc_center_less_than_radius() {
-                    result -= 1;
-                }
-            }
-        }
-
-        result
+        points_on_path(self, other) && points_on_path(other, self)
     }
 
-    /// Calculate the winding number for a `point` relative to the polyline.
-    ///
-    /// The winding number calculates the number of turns/windings around a point
-    /// that the polyline path makes. For a closed polyline without self intersects
-    /// there are only three possibilities:
+    /// Change the start vertex of a closed polyline so the vertex at `index` becomes index 0.
     ///
-    /// * -1 (winds around point clockwise)
-    /// * 0 (point is outside the polyline)
-    /// * 1 (winds around the point counter clockwise).
+    /// The vertexes are rotated in place so the geometry is unchanged (each bulge stays with the
+    /// vertex that starts its segment).
     ///
-    /// This function always returns 0 if polyline [Polyline::is_closed] is false.
+    /// # Panics
     ///
-    /// If the point lies directly on top of one of the polyline segments the result
-    /// is not defined.
+    /// Panics if the polyline is open or `index` is out of bounds.
     ///
     /// # Examples
     ///
-    /// ### Polyline without self intersects
-    ///
     /// ```
     /// # use cavalier_contours::*;
     /// let mut polyline: Polyline = Polyline::new_closed();
-    /// polyline.add(0.0, 0.0, 1.0);
-    /// polyline.add(2.0, 0.0, 1.0);
-    /// assert_eq!(polyline.winding_number(Vector2::new(1.0, 0.0)), 1);
-    /// assert_eq!(polyline.winding_number(Vector2::new(0.0, 2.0)), 0);
-    /// polyline.invert_direction();
-    /// assert_eq!(polyline.winding_number(Vector2::new(1.0, 0.0)), -1);
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(1.0, 0.0, 1.0);
+    /// polyline.add(1.0, 1.0, 0.0);
+    /// polyline.set_start_vertex(1);
+    /// assert!(polyline[0].fuzzy_eq(PlineVertex::new(1.0, 0.0, 1.0)));
+    /// assert!(polyline[2].fuzzy_eq(PlineVertex::new(0.0, 0.0, 0.0)));
     /// ```
+    pub fn set_start_vertex(&mut self, index: usize) {
+        assert!(
+            self.is_closed,
+            "start vertex can only be changed for closed polylines"
+        );
+        assert!(
+            index < self.len(),
+            "index {} out of bounds for polyline with {} vertexes",
+            index,
+            self.len()
+        );
+        self.invalidate_spatial_index();
+        self.vertex_data.rotate_left(index);
+    }
+
+    /// Put the closed polyline into a canonical form: oriented counter clockwise (see
+    /// [Polyline::orient]) and starting at the lexicographically smallest vertex position
+    /// (smallest x, then smallest y).
     ///
-    /// ### Multiple windings with self intersecting polyline
+    /// Polylines which are equivalent by [Polyline::same_loop] become [Polyline::fuzzy_eq] after
+    /// being canonicalized. Vertex positions are compared exactly to pick the start vertex, so due
+    /// to floating point error loops with near equal minimum vertexes may still start at different
+    /// vertexes, callers should still use an epsilon comparison (e.g. [Polyline::same_loop]) when
+    /// deduplicating. This is a no-op for open polylines.
+    ///
+    /// # Examples
     ///
     /// ```
     /// # use cavalier_contours::*;
     /// let mut polyline: Polyline = Polyline::new_closed();
-    /// polyline.add(0.0, 0.0, 1.0);
-    /// polyline.add(2.0, 0.0, 1.0);
-    /// polyline.add(0.0, 0.0, 1.0);
-    /// polyline.add(4.0, 0.0, 1.0);
-    /// assert_eq!(polyline.winding_number(Vector2::new(1.0, 0.0)), 2);
-    /// assert_eq!(polyline.winding_number(Vector2::new(-1.0, 0.0)), 0);
-    /// polyline.invert_direction();
-    /// assert_eq!(polyline.winding_number(Vector2::new(1.0, 0.0)), -2);
+    /// polyline.add(1.0, 1.0, 0.0);
+    /// polyline.add(1.0, 0.0, 0.0);
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.canonicalize();
+    /// assert!(polyline[0].fuzzy_eq(PlineVertex::new(0.0, 0.0, 0.0)));
+    /// assert!(polyline[1].fuzzy_eq(PlineVertex::new(1.0, 0.0, 0.0)));
+    /// assert!(polyline[2].fuzzy_eq(PlineVertex::new(1.0, 1.0, 0.0)));
     /// ```
-    pub fn winding_number(&self, point: Vector2<T>) -> i32 {
+    pub fn canonicalize(&mut self) {
         if !self.is_closed || self.len() < 2 {
-            return 0;
+            return;
         }
 
-        let mut winding = 0;
+        self.orient(true);
 
-        for (v1, v2) in self.iter_segments() {
-            if v1.bulge_is_zero() {
-                winding += Self::process_line_winding(v1, v2, point);
-            } else {
-                winding += Self::process_arc_winding(v1, v2, point);
+        let mut min_index = 0;
+        for (i, v) in self.iter().enumerate().skip(1) {
+            let min = self[min_index];
+            if v.x < min.x || (v.x == min.x && v.y < min.y) {
+                min_index = i;
             }
         }
 
-        winding
+        self.set_start_vertex(min_index);
     }
 
-    /// Returns a new polyline with all arc segments converted to line segments with some `error_distance` or None
-    /// if T fails to cast to or from usize.
+    /// Remove redundant vertexes which lie between two collinear line segments, returning the
+    /// number of vertexes removed.
     ///
-    /// `error_distance` is the maximum distance from any line segment to the arc it is approximating.
-    /// Line segments are circumscribed by the arc (all line end points lie on the arc path).
-    pub fn arcs_to_approx_lines(&self, error_distance: T) -> Option<Self> {
-        let mut result = Polyline::new();
-        result.set_is_closed(self.is_closed);
-
-        // catch case where length is 0 since we may index into the last vertex later
-        if self.len() == 0 {
-            return Some(result);
+    /// A vertex is redundant if both the segment before it and the segment starting at it are line
+    /// segments and the vertex lies between its neighbors within `pos_equal_eps` of the line joining
+    /// them. Vertexes at the start or end of arc segments are never removed and the start and end
+    /// vertex of an open polyline are always kept. For closed polylines the segment wrapping around
+    /// from the last vertex to the first is included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(1.0, 0.0, 0.0);
+    /// polyline.add(2.0, 0.0, 0.0);
+    /// polyline.add(2.0, 1.0, 0.0);
+    /// assert_eq!(polyline.remove_redundant(1e-5), 1);
+    /// assert_eq!(polyline.len(), 3);
+    /// assert!(polyline[1].fuzzy_eq(PlineVertex::new(2.0, 0.0, 0.0)));
+    /// ```
+    pub fn remove_redundant(&mut self, pos_equal_eps: T) -> usize {
+        let ln = self.len();
+        if ln < 3 {
+            return 0;
         }
 
-        let abs_error = error_distance.abs();
-
-        for (v1, v2) in self.iter_segments() {
-            if v1.bulge_is_zero() {
-                result.add_vertex(v1);
-                continue;
-            }
+        let is_redundant =
+            |prev: PlineVertex<T>, v: PlineVertex<T>, next: PlineVertex<T>| -> bool {
+                if !prev.bulge_is_zero() || !v.bulge_is_zero() {
+                    return false;
+                }
 
-            let (arc_radius, arc_center) = seg_arc_radius_and_center(v1, v2);
-            if arc_radius.fuzzy_lt(error_distance) {
-                result.add(v1.x, v1.y, T::zero());
-                continue;
-            }
+                let chord = next.pos() - prev.pos();
+                let chord_length = chord.length();
+                if chord_length < pos_equal_eps {
+                    return false;
+                }
 
-            let start_angle = angle(arc_center, v1.pos());
-            let end_angle = angle(arc_center, v2.pos());
-            let angle_diff = delta_angle(start_angle, end_angle).abs();
+                let prev_to_v = v.pos() - prev.pos();
+                let dist_from_chord = chord.perp_dot(prev_to_v).abs() / chord_length;
+                dist_from_chord < pos_equal_eps && prev_to_v.dot(next.pos() - v.pos()) > T::zero()
+            };
 
-            let seg_sub_angle = T::two() * (T::one() - abs_error / arc_radius).acos().abs();
-            let seg_count = (angle_diff / seg_sub_angle).ceil();
-            // create angle offset such that all lines have an equal part of the arc
-            let seg_angle_offset = if v1.bulge_is_neg() {
-                -angle_diff / seg_count
+        let mut result: Vec<PlineVertex<T>> = Vec::with_capacity(ln);
+        result.push(self[0]);
+        for i in 1..ln {
+            let next = if i + 1 < ln {
+                self[i + 1]
+            } else if self.is_closed {
+                result[0]
             } else {
-                angle_diff / seg_count
+                // always keep end vertex of open polyline
+                result.push(self[i]);
+                break;
             };
 
-            // add start vertex
-            result.add(v1.x, v1.y, T::zero());
-            let usize_count = seg_count.to_usize()?;
-            // add all vertex points along arc
-            for i in 1..usize_count {
-                let angle_pos = T::from(i)?;
-                let angle = angle_pos * seg_angle_offset + start_angle;
-                let pos = point_on_circle(arc_radius, arc_center, angle);
-                result.add(pos.x, pos.y, T::zero());
+            if !is_redundant(*result.last().unwrap(), self[i], next) {
+                result.push(self[i]);
             }
         }
 
-        if !self.is_closed {
-            // add the final missing vertex in the case that the polyline is not closed
-            result.add_vertex(self[self.len() - 1]);
+        if self.is_closed {
+            // check the first vertex using the wrap around segment
+            while result.len() > 2 && is_redundant(result[result.len() - 1], result[0], result[1]) {
+                result.remove(0);
+            }
         }
 
-        Some(result)
+        let removed_count = ln - result.len();
+        self.invalidate_spatial_index();
+        self.vertex_data = result;
+        removed_count
     }
-}
-
-/// Result from calling [Polyline::closest_point].
-#[derive(Debug, Copy, Clone)]
-pub struct ClosestPointResult<T>
-where
-    T: Real,
-{
-    /// The start vertex index of the closest segment.
-    pub seg_start_index: usize,
-    /// The closest point on the closest segment.
-    pub seg_point: Vector2<T>,
-    /// The distance between the points.
-    pub distance: T,
-}
 
-impl<T> Index<usize> for Polyline<T>
-where
-    T: Real,
-{
-    type Output = PlineVertex<T>;
+    /// Merge consecutive arc segments which lie on the same circle into a single arc segment.
+    ///
+    /// Two consecutive arc segments are merged if they have the same direction and their centers
+    /// and radii are equal within `eps`, the vertex between them is removed and the bulge of the
+    /// merged arc is recomputed from the combined sweep angle. Runs of more than two arcs are
+    /// merged into one arc as long as the combined sweep is less than a full circle and the merged
+    /// arc end points are not coincident (so a closed circle always keeps at least 2 arcs). The
+    /// start and end vertex of an open polyline are always kept. For closed polylines the segment
+    /// wrapping around from the last vertex to the first is included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// # use cavalier_contours::core_math::*;
+    /// let quarter_bulge = bulge_from_angle(std::f64::consts::FRAC_PI_2);
+    /// let mut polyline: Polyline = Polyline::new();
+    /// polyline.add(2.0, 0.0, quarter_bulge);
+    /// polyline.add(1.0, 1.0, quarter_bulge);
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.merge_collinear_arcs(1e-5);
+    /// assert_eq!(polyline.len(), 2);
+    /// assert!(polyline[0].fuzzy_eq(PlineVertex::new(2.0, 0.0, 1.0)));
+    /// ```
+    pub fn merge_collinear_arcs(&mut self, eps: T) {
+        let ln = self.len();
+        if ln < 3 {
+            return;
+        }
 
-    #[inline]
-    fn index(&self, index: usize) -> &Self::Output {
-        &self.vertex_data[index]
-    }
-}
+        // returns the bulge of the arc merged from the arcs prev->v and v->next if they can be
+        // merged
+        let merged_bulge =
+            |prev: PlineVertex<T>, v: PlineVertex<T>, next: PlineVertex<T>| -> Option<T> {
+                if prev.bulge_is_zero()
+                    || v.bulge_is_zero()
+                    || prev.bulge_is_neg() != v.bulge_is_neg()
+                    || prev.pos().fuzzy_eq_eps(v.pos(), eps)
+                    || v.pos().fuzzy_eq_eps(next.pos(), eps)
+                    || prev.pos().fuzzy_eq_eps(next.pos(), eps)
+                {
+                    return None;
+                }
 
-impl<T> IndexMut<usize> for Polyline<T>
-where
-    T: Real,
-{
-    #[inline]
-    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        &mut self.vertex_data[index]
-    }
-}
+                let (radius1, center1) = seg_arc_radius_and_center(prev, v);
+                let (radius2, center2) = seg_arc_radius_and_center(v, next);
+                if !radius1.fuzzy_eq_eps(radius2, eps) || !center1.fuzzy_eq_eps(center2, eps) {
+                    return None;
+                }
 
-struct PlineSegIterator<'a, T>
-where
-    T: Real,
-{
-    polyline: &'a Polyline<T>,
-    vertex_windows: Windows<'a, PlineVertex<T>>,
-    is_closed_first_pass: bool,
-}
+                let sweep = angle_from_bulge(prev.bulge) + angle_from_bulge(v.bulge);
+                if sweep.abs() >= T::tau() {
+                    return None;
+                }
 
-impl<'a, T> PlineSegIterator<'a, T>
-where
-    T: Real,
-{
-    fn new(polyline: &'a Polyline<T>) -> PlineSegIterator<'a, T> {
-        let vertex_windows = polyline.vertex_data.windows(2);
-        let is_closed_first_pass = if polyline.vertex_data.len() < 2 {
-            false
-        } else {
-            polyline.is_closed
-        };
-        PlineSegIterator {
-            polyline,
-            vertex_windows,
-            is_closed_first_pass,
-        }
-    }
-}
+                Some(bulge_from_angle(sweep))
+            };
 
-impl<'a, T> Iterator for PlineSegIterator<'a, T>
-where
-    T: Real,
-{
-    type Item = (PlineVertex<T>, PlineVertex<T>);
+        let mut result: Vec<PlineVertex<T>> = Vec::with_capacity(ln);
+        result.push(self[0]);
+        for i in 1..ln {
+            let next = if i + 1 < ln {
+                self[i + 1]
+            } else if self.is_closed {
+                result[0]
+            } else {
+                // always keep end vertex of open polyline
+                result.push(self[i]);
+                break;
+            };
 
-    #[inline]
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.is_closed_first_pass {
-            self.is_closed_first_pass = false;
-            let ln = self.polyline.vertex_data.len();
-            return Some((self.polyline[ln - 1], self.polyline[0]));
+            let last = result.last_mut().unwrap();
+            match merged_bulge(*last, self[i], next) {
+                Some(bulge) => last.bulge = bulge,
+                None => result.push(self[i]),
+            }
         }
 
-        if let Some(&[v1, v2]) = self.vertex_windows.next() {
-            Some((v1, v2))
-        } else {
-            None
+        if self.is_closed {
+            // check the first vertex using the wrap around segment
+            while result.len() > 2 {
+                let last_index = result.len() - 1;
+                match merged_bulge(result[last_index], result[0], result[1]) {
+                    Some(bulge) => {
+                        result[last_index].bulge = bulge;
+                        result.remove(0);
+                    }
+                    None => break,
+                }
+            }
         }
+
+        self.invalidate_spatial_index();
+        self.vertex_data = result;
     }
 
-    #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        if self.is_closed_first_pass {
-            let ln = self.polyline.vertex_data.len();
-            (ln, Some(ln))
+    /// Remove degenerate (zero length) segments whose end points are fuzzy equal (using
+    /// `pos_equal_eps`), returning the number of vertexes removed.
+    ///
+    /// The end vertex of each degenerate segment is removed and its bulge is kept on the start
+    /// vertex (so the segment which followed the removed vertex is unchanged). For closed polylines
+    /// the segment wrapping around from the last vertex to the first is included, in which case
+    /// the last vertex is removed. At least one vertex is always kept.
+    ///
+    /// Degenerate segments lead to divide by zero when computing arc radius and center so they
+    /// should be removed before operations such as offsetting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(1.0, 0.0, 0.5);
+    /// polyline.add(1.0, 0.0, 1.0);
+    /// polyline.add(2.0, 0.0, 0.0);
+    /// assert_eq!(polyline.remove_degenerate_segments(1e-5), 1);
+    /// assert_eq!(polyline.len(), 3);
+    /// assert!(polyline[1].fuzzy_eq(PlineVertex::new(1.0, 0.0, 1.0)));
+    /// ```
+    pub fn remove_degenerate_segments(&mut self, pos_equal_eps: T) -> usize {
+        let ln = self.len();
+        if ln < 2 {
+            return 0;
+        }
+
+        self.invalidate_spatial_index();
+        let vertexes = core::mem::take(&mut self.vertex_data);
+        for v in vertexes {
+            self.add_or_replace_vertex(v, pos_equal_eps);
+        }
+
+        if self.is_closed
+            && self.len() > 1
+            && self[0]
+                .pos()
+                .fuzzy_eq_eps(self.last().unwrap().pos(), pos_equal_eps)
+        {
+            self.remove_last();
+        }
+
+        ln - self.len()
+    }
+
+    /// Returns a simplified copy of the polyline using a Douglas-Peucker variant which keeps arcs.
+    ///
+    /// Runs of line segments are decimated by removing vertexes which deviate less than `tolerance`
+    /// from the simplified path. Arc segments are atomic: vertexes at the start or end of an arc
+    /// segment are always kept (with their bulge values), as are the start and end vertexes of an
+    /// open polyline. Closed polylines keep at least 3 vertexes (if they had at least 3).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(1.0, 0.01, 0.0);
+    /// polyline.add(2.0, -0.01, 0.0);
+    /// polyline.add(3.0, 0.0, 0.0);
+    /// let simplified = polyline.simplify(0.1);
+    /// assert_eq!(simplified.len(), 2);
+    /// assert!(simplified[1].fuzzy_eq(PlineVertex::new(3.0, 0.0, 0.0)));
+    /// ```
+    pub fn simplify(&self, tolerance: T) -> Polyline<T> {
+        let ln = self.len();
+        if ln < 3 {
+            return self.clone();
+        }
+
+        let seg_count = if self.is_closed { ln } else { ln - 1 };
+        let mut keep = vec![false; ln];
+        for i in 0..seg_count {
+            if !self[i].bulge_is_zero() {
+                keep[i] = true;
+                keep[self.next_wrapping_index(i)] = true;
+            }
+        }
+
+        if !self.is_closed {
+            keep[0] = true;
+            keep[ln - 1] = true;
+        } else if !keep.iter().any(|&k| k) {
+            // all line segments, split closed polyline at first vertex and the vertex farthest from
+            // it
+            let start = self[0].pos();
+            let farthest = (1..ln)
+                .max_by(|&a, &b| {
+                    dist_squared(start, self[a].pos())
+                        .partial_cmp(&dist_squared(start, self[b].pos()))
+                        .unwrap_or(core::cmp::Ordering::Equal)
+                })
+                .unwrap();
+            keep[0] = true;
+            keep[farthest] = true;
+        }
+
+        let anchors: Vec<usize> = (0..ln).filter(|&i| keep[i]).collect();
+        let tolerance_squared = tolerance * tolerance;
+        // find the vertex farthest from the line between `start` and `end` (indexes may wrap),
+        // returns None if there are no vertexes between them
+        let farthest_between = |start: usize, end: usize| -> Option<(usize, T)> {
+            let mut result: Option<(usize, T)> = None;
+            let mut max_dist = -T::one();
+            let mut i = self.next_wrapping_index(start);
+            while i != end {
+                let p = self[i].pos();
+                let closest = line_seg_closest_point(self[start].pos(), self[end].pos(), p);
+                let dist = dist_squared(closest, p);
+                if dist > max_dist {
+                    max_dist = dist;
+                    result = Some((i, dist));
+                }
+                i = self.next_wrapping_index(i);
+            }
+            result
+        };
+
+        let pair_count = if self.is_closed {
+            anchors.len()
         } else {
-            self.vertex_windows.size_hint()
+            anchors.len() - 1
+        };
+        let mut stack = Vec::new();
+        for k in 0..pair_count {
+            let start = anchors[k];
+            if !self[start].bulge_is_zero() {
+                // arc segment
+                continue;
+            }
+
+            stack.push((start, anchors[(k + 1) % anchors.len()]));
+            while let Some((start, end)) = stack.pop() {
+                if let Some((i, dist)) = farthest_between(start, end) {
+                    if dist > tolerance_squared {
+                        keep[i] = true;
+                        stack.push((start, i));
+                        stack.push((i, end));
+                    }
+                }
+            }
+        }
+
+        if self.is_closed && keep.iter().filter(|&&k| k).count() < 3 {
+            // keep a valid closed polyline by keeping the vertex farthest from the 2 kept
+            let start = anchors[0];
+            let end = anchors[anchors.len() - 1];
+            let candidate = match (farthest_between(start, end), farthest_between(end, start)) {
+                (Some(a), Some(b)) => Some(if a.1 >= b.1 { a.0 } else { b.0 }),
+                (Some(a), None) => Some(a.0),
+                (None, Some(b)) => Some(b.0),
+                (None, None) => None,
+            };
+            if let Some(i) = candidate {
+                keep[i] = true;
+            }
+        }
+
+        let mut result = Polyline::with_capacity(anchors.len());
+        result.set_is_closed(self.is_closed);
+        for (i, &k) in keep.iter().enumerate() {
+            if k {
+                result.add_vertex(self[i]);
+            }
         }
+
+        result
     }
-}
 
-struct PlineSegIndexIterator {
-    pos: usize,
-    remaining: usize,
-    is_closed_first_pass: bool,
-}
+    /// Reverse the vertexes from index `start` to index `end` (inclusive) in place.
+    ///
+    /// The segments between the vertexes in the range are traced in reverse (same geometry going
+    /// the opposite direction) using the same shift and negate of bulge values as
+    /// [Polyline::invert_direction]. Segments outside the range are unchanged, the segment going
+    /// into the range now ends at the vertex that was at `end` and the segment going out of the
+    /// range now starts at the vertex that was at `start` (both keep their original bulge values).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start > end` or `end >= self.len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(1.0, 0.0, 1.0);
+    /// polyline.add(3.0, 0.0, 0.0);
+    /// polyline.add(4.0, 0.0, 0.0);
+    /// polyline.reverse_range(1, 2);
+    /// assert!(polyline[1].fuzzy_eq(PlineVertex::new(3.0, 0.0, -1.0)));
+    /// assert!(polyline[2].fuzzy_eq(PlineVertex::new(1.0, 0.0, 0.0)));
+    /// ```
+    pub fn reverse_range(&mut self, start: usize, end: usize) {
+        assert!(
+            start <= end && end < self.len(),
+            "reverse range {}..={} is invalid for polyline with length {}",
+            start,
+            end,
+            self.len()
+        );
+
+        if start == end {
+            return;
+        }
 
-impl PlineSegIndexIterator {
-    fn new(vertex_count: usize, is_closed: bool) -> PlineSegIndexIterator {
-        let remaining = if vertex_count < 2 {
-            0
-        } else if is_closed {
-            vertex_count
+        self.invalidate_spatial_index();
+        let out_bulge = self[end].bulge;
+        self.vertex_data[start..=end].reverse();
+        for i in start..end {
+            self[i].bulge = -self[i + 1].bulge;
+        }
+        self[end].bulge = out_bulge;
+    }
+
+    /// Orient the closed polyline to be counter clockwise (`ccw` is true) or clockwise (`ccw` is
+    /// false) by inverting its direction if required (see [Polyline::invert_direction]).
+    ///
+    /// Returns true if the direction was inverted. Open polylines have no orientation so this is a
+    /// no-op for open polylines (always returns false).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new_closed();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(0.0, 1.0, 0.0);
+    /// polyline.add(1.0, 1.0, 0.0);
+    /// assert!(polyline.area() < 0.0);
+    /// assert!(polyline.orient(true));
+    /// assert!(polyline.area() > 0.0);
+    /// ```
+    pub fn orient(&mut self, ccw: bool) -> bool {
+        if !self.is_closed {
+            return false;
+        }
+
+        let area = self.area();
+        let needs_invert = if ccw {
+            area < T::zero()
         } else {
-            vertex_count - 1
+            area > T::zero()
         };
-        PlineSegIndexIterator {
-            pos: 1,
-            remaining,
-            is_closed_first_pass: is_closed,
+        if needs_invert {
+            self.invert_direction();
+        }
+
+        needs_invert
+    }
+
+    /// Scale the polyline in the xy plane by `scale_x` in the x direction and `scale_y` in the
+    /// y direction.
+    ///
+    /// If `scale_x` is fuzzy equal to `scale_y` this is the same as calling [Polyline::scale].
+    /// Arcs do not remain circular under non-uniform scaling so if the scale factors differ and the
+    /// polyline has any arc segments then [PlineError::NonUniformScaleWithArcs] is returned and the
+    /// polyline is left unchanged. Use [Polyline::arcs_to_approx_lines] to convert arcs to lines
+    /// before scaling in that case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline = Polyline::new();
+    /// polyline.add(2.0, 2.0, 0.0);
+    /// polyline.add(4.0, 4.0, 0.0);
+    /// polyline.scale_xy(2.0, 0.5).unwrap();
+    /// let mut expected = Polyline::new();
+    /// expected.add(4.0, 1.0, 0.0);
+    /// expected.add(8.0, 2.0, 0.0);
+    /// assert!(polyline.fuzzy_eq(&expected));
+    ///
+    /// polyline.add(0.0, 0.0, 1.0);
+    /// polyline.add(1.0, 0.0, 0.0);
+    /// assert_eq!(polyline.scale_xy(2.0, 0.5), Err(PlineError::NonUniformScaleWithArcs));
+    /// ```
+    pub fn scale_xy(&mut self, scale_x: T, scale_y: T) -> Result<(), PlineError> {
+        if scale_x.fuzzy_eq(scale_y) {
+            self.scale(scale_x);
+            return Ok(());
+        }
+
+        if self.iter().any(|v| !v.bulge_is_zero()) {
+            return Err(PlineError::NonUniformScaleWithArcs);
+        }
+
+        for v in self.iter_mut() {
+            v.x = v.x * scale_x;
+            v.y = v.y * scale_y;
+        }
+
+        Ok(())
+    }
+
+    /// Snap all vertex positions to the nearest multiple of `grid_size` relative to `origin`.
+    ///
+    /// Bulge values are left unchanged (arcs are recomputed from the snapped end points). If
+    /// `remove_coincident` is true then vertexes which become coincident with the previous vertex
+    /// after snapping are removed (the removed vertex bulge replaces the previous vertex bulge),
+    /// for closed polylines the last vertex is also removed if it becomes coincident with the first
+    /// vertex.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `grid_size` is not greater than zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new();
+    /// polyline.add(0.1, 0.2, 0.0);
+    /// polyline.add(0.9, 1.7, 0.5);
+    /// polyline.add(1.05, 2.1, 0.0);
+    /// polyline.snap_to_grid(0.5, Vector2::new(0.0, 0.0), true);
+    /// assert_eq!(polyline.len(), 3);
+    /// assert!(polyline[1].fuzzy_eq(PlineVertex::new(1.0, 1.5, 0.5)));
+    /// assert!(polyline[2].fuzzy_eq(PlineVertex::new(1.0, 2.0, 0.0)));
+    /// ```
+    pub fn snap_to_grid(&mut self, grid_size: T, origin: Vector2<T>, remove_coincident: bool) {
+        assert!(grid_size > T::zero(), "grid_size must be greater than zero");
+        let snap = |value: T, origin_value: T| -> T {
+            ((value - origin_value) / grid_size).round() * grid_size + origin_value
+        };
+
+        for v in self.iter_mut() {
+            v.x = snap(v.x, origin.x);
+            v.y = snap(v.y, origin.y);
+        }
+
+        if !remove_coincident {
+            return;
+        }
+
+        self.remove_degenerate_segments(grid_size / T::from(1000).unwrap());
+    }
+
+    /// Mirror the polyline across the line defined by the points `p1` and `p2`.
+    ///
+    /// Reflection flips arc direction so all bulge values are negated, vertex order is left
+    /// unchanged so a closed counter clockwise polyline becomes clockwise (and vice versa).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p1` and `p2` are fuzzy equal (they do not define a line).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline = Polyline::new();
+    /// polyline.add(2.0, 2.0, 0.5);
+    /// polyline.add(4.0, 4.0, 1.0);
+    /// // mirror across the y axis
+    /// polyline.mirror(Vector2::new(0.0, 0.0), Vector2::new(0.0, 1.0));
+    /// let mut expected = Polyline::new();
+    /// expected.add(-2.0, 2.0, -0.5);
+    /// expected.add(-4.0, 4.0, -1.0);
+    /// assert!(polyline.fuzzy_eq(&expected));
+    /// ```
+    pub fn mirror(&mut self, p1: Vector2<T>, p2: Vector2<T>) {
+        assert!(
+            !p1.fuzzy_eq(p2),
+            "mirror line points must be distinct to define a line"
+        );
+        let dir = (p2 - p1).normalize();
+        for v in self.iter_mut() {
+            // reflect by projecting onto the line and moving the same distance past it
+            let w = v.pos() - p1;
+            let projected = p1 + dir.scale(w.dot(dir));
+            let reflected = projected.scale(T::two()) - v.pos();
+            v.x = reflected.x;
+            v.y = reflected.y;
+            v.bulge = -v.bulge;
+        }
+    }
+
+    /// Apply an affine transform to the polyline given as the matrix `m = [a, b, c, d, e, f]`.
+    ///
+    /// Each vertex position is mapped `(x, y) -> (a * x + c * y + e, b * x + d * y + f)`. If the
+    /// transform reverses orientation (negative determinant) then all bulge values are negated,
+    /// otherwise bulge values are left unchanged.
+    ///
+    /// Arcs are only exactly preserved under similarity transforms (rotation, uniform scale,
+    /// reflection, and translation). Returns true if the transform is a similarity transform,
+    /// otherwise returns false to indicate that the arc segments of the result do not match the
+    /// transformed arcs (non-uniform scale or shear distorts circular arcs into elliptical arcs which
+    /// cannot be represented by a bulge). Use [Polyline::arcs_to_approx_lines] before transforming
+    /// if arcs must be transformed accurately under non-similarity transforms.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline = Polyline::new();
+    /// polyline.add(2.0, 2.0, 0.5);
+    /// polyline.add(4.0, 4.0, 1.0);
+    /// // uniform scale by 2 then translate by (1, -1)
+    /// assert!(polyline.transform([2.0, 0.0, 0.0, 2.0, 1.0, -1.0]));
+    /// let mut expected = Polyline::new();
+    /// expected.add(5.0, 3.0, 0.5);
+    /// expected.add(9.0, 7.0, 1.0);
+    /// assert!(polyline.fuzzy_eq(&expected));
+    /// ```
+    pub fn transform(&mut self, m: [T; 6]) -> bool {
+        let [a, b, c, d, e, f] = m;
+        let det = a * d - b * c;
+        let reverses_orientation = det < T::zero();
+        let is_similarity = !det.fuzzy_eq_zero()
+            && if reverses_orientation {
+                a.fuzzy_eq(-d) && b.fuzzy_eq(c)
+            } else {
+                a.fuzzy_eq(d) && b.fuzzy_eq(-c)
+            };
+
+        for v in self.iter_mut() {
+            let x = v.x;
+            let y = v.y;
+            v.x = a * x + c * y + e;
+            v.y = b * x + d * y + f;
+            if reverses_orientation {
+                v.bulge = -v.bulge;
+            }
+        }
+
+        is_similarity
+    }
+
+    /// Compute the XY extents of the polyline.
+    ///
+    /// Returns `None` if polyline is empty or is not finite (see [Polyline::is_finite]). If polyline
+    /// has only one vertex then `min_x = max_x = polyline[0].x` and `min_y = max_y = polyline[0].y`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline = Polyline::new();
+    /// assert_eq!(polyline.extents(), None);
+    /// polyline.add(1.0, 1.0, 1.0);
+    /// let pt_extents = polyline.extents().unwrap();
+    /// assert!(pt_extents.min_x.fuzzy_eq(1.0));
+    /// assert!(pt_extents.min_y.fuzzy_eq(1.0));
+    /// assert!(pt_extents.max_x.fuzzy_eq(1.0));
+    /// assert!(pt_extents.max_y.fuzzy_eq(1.0));
+    ///
+    /// polyline.add(3.0, 1.0, 1.0);
+    /// let extents = polyline.extents().unwrap();
+    /// assert!(extents.min_x.fuzzy_eq(1.0));
+    /// assert!(extents.min_y.fuzzy_eq(0.0));
+    /// assert!(extents.max_x.fuzzy_eq(3.0));
+    /// assert!(extents.max_y.fuzzy_eq(1.0));
+    ///
+    /// polyline.set_is_closed(true);
+    /// let extents = polyline.extents().unwrap();
+    /// assert!(extents.min_x.fuzzy_eq(1.0));
+    /// assert!(extents.min_y.fuzzy_eq(0.0));
+    /// assert!(extents.max_x.fuzzy_eq(3.0));
+    /// assert!(extents.max_y.fuzzy_eq(2.0));
+    /// ```
+    pub fn extents(&self) -> Option<AABB<T>> {
+        if self.len() == 0 || !self.is_finite() {
+            return None;
         }
+
+        let mut result = AABB::new(self[0].x, self[0].y, self[0].x, self[0].y);
+
+        for (v1, v2) in self.iter_segments() {
+            if v1.bulge_is_zero() {
+                // line segment, just look at end of line point
+                if v2.x < result.min_x {
+                    result.min_x = v2.x;
+                } else if v2.x > result.max_x {
+                    result.max_x = v2.x;
+                }
+
+                if v2.y < result.min_y {
+                    result.min_y = v2.y;
+                } else if v2.y > result.max_y {
+                    result.max_y = v2.y;
+                }
+
+                continue;
+            }
+            // else arc segment
+            let arc_extents = arc_seg_bounding_box(v1, v2);
+
+            result.min_x = num_traits::real::Real::min(result.min_x, arc_extents.min_x);
+            result.min_y = num_traits::real::Real::min(result.min_y, arc_extents.min_y);
+            result.max_x = num_traits::real::Real::max(result.max_x, arc_extents.max_x);
+            result.max_y = num_traits::real::Real::max(result.max_y, arc_extents.max_y);
+        }
+
+        Some(result)
+    }
+
+    /// Compute the minimum area (rotated) bounding rectangle of the polyline.
+    ///
+    /// Returns the rectangle as a closed counter clockwise 4 vertex polyline along with its
+    /// rotation angle in radians (in the range `[0, PI / 2)`) relative to the X axis. The orientation
+    /// is found by rotating calipers over the convex hull of the polyline (arc segments are
+    /// tessellated for the hull) in linear time in the number of hull points, the extents for the
+    /// best orientation are then computed exactly (including arc segments). Returns `None` if
+    /// polyline is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// // square rotated 45 degrees
+    /// let mut polyline: Polyline = Polyline::new_closed();
+    /// polyline.add(1.0, 0.0, 0.0);
+    /// polyline.add(2.0, 1.0, 0.0);
+    /// polyline.add(1.0, 2.0, 0.0);
+    /// polyline.add(0.0, 1.0, 0.0);
+    /// let (rect, angle) = polyline.min_area_bounding_rect().unwrap();
+    /// assert!(rect.area().fuzzy_eq(2.0));
+    /// assert!(angle.fuzzy_eq(std::f64::consts::FRAC_PI_4));
+    /// ```
+    pub fn min_area_bounding_rect(&self) -> Option<(Polyline<T>, T)> {
+        let extents = self.extents()?;
+        let size = num_traits::real::Real::max(
+            extents.max_x - extents.min_x,
+            extents.max_y - extents.min_y,
+        );
+
+        let hull_points = if self.iter().any(|v| !v.bulge_is_zero()) {
+            let error_distance =
+                num_traits::real::Real::max(size * T::from(1e-3)?, T::fuzzy_epsilon());
+            let approx = self.arcs_to_approx_lines(error_distance)?;
+            convex_hull(approx.iter().map(|v| v.pos()).collect())
+        } else {
+            convex_hull(self.iter().map(|v| v.pos()).collect())
+        };
+
+        let mut best_angle = T::zero();
+        let mut best_extents = extents;
+        if let Some(edge_angle) = min_area_rect_edge_angle(&hull_points) {
+            // rectangle is the same for any edge angle modulo PI / 2
+            let half_pi = T::pi() / T::two();
+            let edge_angle = edge_angle - (edge_angle / half_pi).floor() * half_pi;
+            let mut rotated = self.clone();
+            rotated.rotate(-edge_angle, Vector2::zero());
+            let rotated_extents = rotated.extents()?;
+            let area = |e: AABB<T>| (e.max_x - e.min_x) * (e.max_y - e.min_y);
+            if area(rotated_extents) < area(extents) {
+                best_angle = edge_angle;
+                best_extents = rotated_extents;
+            }
+        }
+
+        let mut rect = Polyline::new_closed();
+        rect.add(best_extents.min_x, best_extents.min_y, T::zero());
+        rect.add(best_extents.max_x, best_extents.min_y, T::zero());
+        rect.add(best_extents.max_x, best_extents.max_y, T::zero());
+        rect.add(best_extents.min_x, best_extents.max_y, T::zero());
+        rect.rotate(best_angle, Vector2::zero());
+
+        Some((rect, best_angle))
+    }
+
+    /// Compute the minimum enclosing (bounding) circle of the polyline.
+    ///
+    /// Returns the circle center and radius. Arc segments are tessellated (with an error distance
+    /// relative to the size of the polyline) so the outermost points of arcs are included, the
+    /// circle is then found over the convex hull of the points using Welzl's algorithm. Returns
+    /// `None` if the polyline is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new_closed();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(4.0, 0.0, 0.0);
+    /// polyline.add(4.0, 4.0, 0.0);
+    /// polyline.add(0.0, 4.0, 0.0);
+    /// let (center, radius) = polyline.bounding_circle().unwrap();
+    /// assert!(center.fuzzy_eq(Vector2::new(2.0, 2.0)));
+    /// assert!(radius.fuzzy_eq(8.0f64.sqrt()));
+    /// ```
+    pub fn bounding_circle(&self) -> Option<(Vector2<T>, T)> {
+        let extents = self.extents()?;
+        let size = num_traits::real::Real::max(
+            extents.max_x - extents.min_x,
+            extents.max_y - extents.min_y,
+        );
+
+        let hull_points = if self.iter().any(|v| !v.bulge_is_zero()) {
+            let error_distance =
+                num_traits::real::Real::max(size * T::from(1e-5)?, T::fuzzy_epsilon());
+            let approx = self.arcs_to_approx_lines(error_distance)?;
+            convex_hull(approx.iter().map(|v| v.pos()).collect())
+        } else {
+            convex_hull(self.iter().map(|v| v.pos()).collect())
+        };
+
+        if hull_points.is_empty() {
+            // all points coincident
+            return Some((self[0].pos(), T::zero()));
+        }
+
+        Some(min_enclosing_circle(&hull_points))
+    }
+
+    /// Approximate the largest circle that fits inside the closed polyline (the pole of
+    /// inaccessibility).
+    ///
+    /// Returns the circle center and radius. The center is found by covering the polyline extents
+    /// with square cells and repeatedly subdividing the cells which may contain a point further
+    /// from the boundary than the best point found so far, this is an approximation: the radius
+    /// returned is within `tolerance` of the true maximum radius. Smaller `tolerance` values give
+    /// a more accurate result but take longer to compute. Points inside are determined by a non-zero
+    /// [Polyline::winding_number]. Returns `None` if the polyline is open, has less than 2
+    /// vertexes, or has no interior.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new_closed();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(10.0, 0.0, 0.0);
+    /// polyline.add(10.0, 4.0, 0.0);
+    /// polyline.add(0.0, 4.0, 0.0);
+    /// let (center, radius) = polyline.max_inscribed_circle(1e-3).unwrap();
+    /// assert!((radius - 2.0).abs() < 1e-3);
+    /// assert!((center.y - 2.0).abs() < 1e-2);
+    /// ```
+    pub fn max_inscribed_circle(&self, tolerance: T) -> Option<(Vector2<T>, T)> {
+        if !self.is_closed || self.len() < 2 {
+            return None;
+        }
+
+        let extents = self.extents()?;
+        let width = extents.max_x - extents.min_x;
+        let height = extents.max_y - extents.min_y;
+        let cell_size = num_traits::real::Real::min(width, height);
+        if cell_size.fuzzy_eq_zero() {
+            return None;
+        }
+
+        let tolerance = num_traits::real::Real::max(tolerance, T::fuzzy_epsilon());
+        let two = T::two();
+        let sqrt_2 = two.sqrt();
+
+        let new_cell = |center: Vector2<T>, half_size: T| {
+            let distance = self.signed_distance(center);
+            InscribedCell {
+                center,
+                half_size,
+                distance,
+                potential: distance + half_size * sqrt_2,
+            }
+        };
+
+        // max heap ordered by cell potential
+        let mut cells = BinaryHeap::new();
+        let half_size = cell_size / two;
+        let mut x = extents.min_x;
+        while x < extents.max_x {
+            let mut y = extents.min_y;
+            while y < extents.max_y {
+                cells.push(new_cell(
+                    Vector2::new(x + half_size, y + half_size),
+                    half_size,
+                ));
+                y = y + cell_size;
+            }
+            x = x + cell_size;
+        }
+
+        let extents_center =
+            Vector2::new(extents.min_x + width / two, extents.min_y + height / two);
+        let mut best = new_cell(extents_center, T::zero());
+        for cell in cells.iter() {
+            if cell.distance > best.distance {
+                best = *cell;
+            }
+        }
+
+        // take the cell with the greatest potential
+        while let Some(cell) = cells.pop() {
+            if cell.potential - best.distance <= tolerance {
+                // no remaining cell can improve on the best by more than tolerance
+                break;
+            }
+
+            let half_size = cell.half_size / two;
+            for &(dx, dy) in &[(-1, -1), (1, -1), (1, 1), (-1, 1)] {
+                let offset = Vector2::new(T::from(dx)?, T::from(dy)?).scale(half_size);
+                let child = new_cell(cell.center + offset, half_size);
+                if child.distance > best.distance {
+                    best = child;
+                }
+
+                if child.potential - best.distance > tolerance {
+                    cells.push(child);
+                }
+            }
+        }
+
+        if best.distance <= T::zero() {
+            return None;
+        }
+
+        Some((best.center, best.distance))
+    }
+
+    /// Distance from `point` to the closed polyline path, positive if `point` is inside (non-zero
+    /// winding number) and negative if outside.
+    fn signed_distance(&self, point: Vector2<T>) -> T {
+        let distance = match self.closest_point(point) {
+            Some(closest_point) => closest_point.distance,
+            None => return T::zero(),
+        };
+
+        if self.winding_number(point) != 0 {
+            distance
+        } else {
+            -distance
+        }
+    }
+
+    /// Spatial index of the polyline segments (same as [Polyline::create_approx_spatial_index]),
+    /// built on first use and cached until the polyline is modified.
+    ///
+    /// Returns `None` if the polyline has less than 2 vertexes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new_closed();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(10.0, 0.0, 0.0);
+    /// polyline.add(10.0, 10.0, 0.0);
+    /// // index is built once and shared by both offsets
+    /// let inward = polyline.parallel_offset(1.0, polyline.spatial_index());
+    /// let outward = polyline.parallel_offset(-1.0, polyline.spatial_index());
+    /// assert_eq!(inward.len(), 1);
+    /// assert_eq!(outward.len(), 1);
+    /// ```
+    pub fn spatial_index(&self) -> Option<&StaticAABB2DIndex<T>> {
+        self.spatial_index_cache
+            .get_or_init(|| self.create_approx_spatial_index())
+            .as_ref()
+    }
+
+    pub fn create_approx_spatial_index(&self) -> Option<StaticAABB2DIndex<T>> {
+        let ln = self.len();
+        if ln < 2 {
+            return None;
+        }
+
+        let seg_count = if self.is_closed { ln } else { ln - 1 };
+
+        let mut builder = StaticAABB2DIndexBuilder::new(seg_count);
+
+        for i in 0..ln - 1 {
+            let approx_aabb = seg_fast_approx_bounding_box(self[i], self[i + 1]);
+            builder.add(
+                approx_aabb.min_x,
+                approx_aabb.min_y,
+                approx_aabb.max_x,
+                approx_aabb.max_y,
+            );
+        }
+
+        if self.is_closed {
+            // add final segment from last to first
+            let approx_aabb = seg_fast_approx_bounding_box(*self.last().unwrap(), self[0]);
+            builder.add(
+                approx_aabb.min_x,
+                approx_aabb.min_y,
+                approx_aabb.max_x,
+                approx_aabb.max_y,
+            );
+        }
+
+        builder.build().ok()
+    }
+
+    /// Visit all the polyline segments (represented as polyline vertex pairs) with a function/closure.
+    ///
+    /// This is equivalent to [Polyline::iter_segments] but uses a visiting function rather than an iterator.
+    pub fn visit_segments<F>(&self, visitor: &mut F)
+    where
+        F: FnMut(PlineVertex<T>, PlineVertex<T>) -> bool,
+    {
+        let ln = self.vertex_data.len();
+        if ln < 2 {
+            return;
+        }
+
+        if self.is_closed {
+            let v1 = self.vertex_data[ln - 1];
+            let v2 = self.vertex_data[0];
+            if !visitor(v1, v2) {
+                return;
+            }
+        }
+
+        let mut windows = self.vertex_data.windows(2);
+        while let Some(&[v1, v2]) = windows.next() {
+            if !visitor(v1, v2) {
+                break;
+            }
+        }
+    }
+
+    /// Visit all the polyline segments (represented as polyline vertex pairs) with a function that
+    /// can stop early with a result value.
+    ///
+    /// Segments are visited in the same order as [Polyline::visit_segments]. Visiting stops at the
+    /// first `ControlFlow::Break(value)` returned and `Some(value)` is returned, `None` is returned
+    /// if all segments are visited.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// use core::ops::ControlFlow;
+    /// let mut polyline: Polyline = Polyline::new();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(2.0, 0.0, 1.0);
+    /// polyline.add(4.0, 0.0, 0.0);
+    /// let first_arc_end = polyline.try_visit_segments(|v1, v2| {
+    ///     if v1.bulge_is_zero() {
+    ///         ControlFlow::Continue(())
+    ///     } else {
+    ///         ControlFlow::Break(v2.pos())
+    ///     }
+    /// });
+    /// assert!(first_arc_end.unwrap().fuzzy_eq(Vector2::new(4.0, 0.0)));
+    /// ```
+    pub fn try_visit_segments<B, F>(&self, mut f: F) -> Option<B>
+    where
+        F: FnMut(PlineVertex<T>, PlineVertex<T>) -> ControlFlow<B>,
+    {
+        for (v1, v2) in self.iter_segments() {
+            if let ControlFlow::Break(value) = f(v1, v2) {
+                return Some(value);
+            }
+        }
+
+        None
+    }
+
+    /// Iterate through all the vertexes in the polyline together with their neighbors as
+    /// `(prev, current, next)` triples.
+    ///
+    /// For closed polylines every vertex is visited and the neighbors wrap around (the first vertex
+    /// is visited with the last vertex as its previous vertex). For open polylines the first and
+    /// last vertexes have no neighbor on one side so they are skipped. Nothing is yielded for
+    /// closed polylines with less than 2 vertexes or open polylines with less than 3 vertexes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(1.0, 0.0, 0.0);
+    /// polyline.add(1.0, 1.0, 0.0);
+    /// let mut iter = polyline.iter_vertex_neighbors();
+    /// let (prev, current, next) = iter.next().unwrap();
+    /// assert!(prev.fuzzy_eq(polyline[0]));
+    /// assert!(current.fuzzy_eq(polyline[1]));
+    /// assert!(next.fuzzy_eq(polyline[2]));
+    /// assert!(iter.next().is_none());
+    /// ```
+    pub fn iter_vertex_neighbors<'a>(
+        &'a self,
+    ) -> impl Iterator<Item = (PlineVertex<T>, PlineVertex<T>, PlineVertex<T>)> + 'a {
+        let n = self.len();
+        let range = if self.is_closed {
+            if n < 2 {
+                0..0
+            } else {
+                0..n
+            }
+        } else if n < 3 {
+            0..0
+        } else {
+            1..n - 1
+        };
+
+        range.map(move |i| {
+            (
+                self[self.prev_wrapping_index(i)],
+                self[i],
+                self[self.next_wrapping_index(i)],
+            )
+        })
+    }
+
+    /// Iterate through all the polyline segments (represented as polyline vertex pairs).
+    ///
+    /// This is equivalent to [Polyline::visit_segments] but returns an iterator rather than accepting a function.
+    ///
+    /// The iterator is double ended so segments may also be iterated in reverse (see
+    /// [Polyline::iter_segments_rev]).
+    pub fn iter_segments<'a>(
+        &'a self,
+    ) -> impl DoubleEndedIterator<Item = (PlineVertex<T>, PlineVertex<T>)> + ExactSizeIterator + 'a
+    {
+        PlineSegIterator::new(&self)
+    }
+
+    /// Iterate through all the polyline segments (represented as polyline vertex pairs) in reverse
+    /// order.
+    ///
+    /// Segments are yielded in the reverse order of [Polyline::iter_segments] (so for closed
+    /// polylines the closing segment is yielded last) but each vertex pair still reads
+    /// (start, end) in the direction of the polyline.
+    pub fn iter_segments_rev<'a>(
+        &'a self,
+    ) -> impl Iterator<Item = (PlineVertex<T>, PlineVertex<T>)> + 'a {
+        self.iter_segments().rev()
+    }
+
+    /// Iterate through all the polyline segments as [PlineSegment](crate::core_math::PlineSegment)
+    /// geometry with arc parameters precomputed.
+    ///
+    /// Segments are yielded in the same order as [Polyline::iter_segments].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// # use cavalier_contours::core_math::*;
+    /// let mut polyline: Polyline = Polyline::new();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(2.0, 0.0, 1.0);
+    /// polyline.add(4.0, 0.0, 0.0);
+    /// let segments: Vec<_> = polyline.iter_typed_segments().collect();
+    /// assert!(matches!(segments[0], PlineSegment::Line { .. }));
+    /// assert!(matches!(segments[1], PlineSegment::Arc { .. }));
+    /// ```
+    pub fn iter_typed_segments<'a>(&'a self) -> impl Iterator<Item = PlineSegment<T>> + 'a {
+        self.iter_segments()
+            .map(|(v1, v2)| PlineSegment::new(v1, v2))
+    }
+
+    /// Convert the polyline segments to a list of line and arc
+    /// [Primitive](crate::core_math::Primitive)s, in segment order starting from the first vertex.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// # use cavalier_contours::core_math::*;
+    /// let mut polyline: Polyline = Polyline::new();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(2.0, 0.0, 1.0);
+    /// polyline.add(4.0, 0.0, 0.0);
+    /// let primitives = polyline.to_primitives();
+    /// assert_eq!(primitives.len(), 2);
+    /// assert!(matches!(primitives[0], Primitive::Line(..)));
+    /// assert!(matches!(primitives[1], Primitive::Arc { ccw: true, .. }));
+    /// ```
+    pub fn to_primitives(&self) -> Vec<Primitive<T>> {
+        let mut result: Vec<Primitive<T>> =
+            self.iter_typed_segments().map(Primitive::from).collect();
+        if self.is_closed {
+            // iter_segments starts with the closing segment
+            result.rotate_left(1);
+        }
+
+        result
+    }
+
+    /// Build a polyline from a list of line and arc [Primitive](crate::core_math::Primitive)s
+    /// (the inverse of [Polyline::to_primitives]).
+    ///
+    /// Each primitive must start where the previous primitive ends (compared using
+    /// `pos_equal_eps`), otherwise [PlineError::DisconnectedPrimitive] is returned with the index of
+    /// the primitive. If the last primitive ends where the first primitive starts then the
+    /// polyline returned is closed. Arcs with the same start and end angle are full circles and are
+    /// split into two half circle segments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// # use cavalier_contours::core_math::*;
+    /// let primitives = [
+    ///     Primitive::Line(Vector2::new(0.0, 0.0), Vector2::new(2.0, 0.0)),
+    ///     Primitive::Arc {
+    ///         center: Vector2::new(2.0, 1.0),
+    ///         radius: 1.0,
+    ///         start_angle: -std::f64::consts::FRAC_PI_2,
+    ///         end_angle: std::f64::consts::FRAC_PI_2,
+    ///         ccw: true,
+    ///     },
+    ///     Primitive::Line(Vector2::new(2.0, 2.0), Vector2::new(0.0, 0.0)),
+    /// ];
+    /// let polyline = Polyline::from_primitives(&primitives, 1e-5).unwrap();
+    /// assert!(polyline.is_closed());
+    /// assert_eq!(polyline.len(), 3);
+    /// assert!(polyline[1].bulge.fuzzy_eq(1.0));
+    ///
+    /// let gap = [
+    ///     Primitive::Line(Vector2::new(0.0, 0.0), Vector2::new(2.0, 0.0)),
+    ///     Primitive::Line(Vector2::new(3.0, 0.0), Vector2::new(4.0, 0.0)),
+    /// ];
+    /// let result = Polyline::from_primitives(&gap, 1e-5);
+    /// assert_eq!(result.unwrap_err(), PlineError::DisconnectedPrimitive { index: 1 });
+    /// ```
+    pub fn from_primitives(
+        primitives: &[Primitive<T>],
+        pos_equal_eps: T,
+    ) -> Result<Polyline<T>, PlineError> {
+        let mut result = Polyline::with_capacity(primitives.len() + 1);
+        for (i, prim) in primitives.iter().enumerate() {
+            let start = prim.start();
+            if let Some(last) = result.last() {
+                if !last.pos().fuzzy_eq_eps(start, pos_equal_eps) {
+                    return Err(PlineError::DisconnectedPrimitive { index: i });
+                }
+            }
+
+            match *prim {
+                Primitive::Line(..) => {
+                    result.add_or_replace(start.x, start.y, T::zero(), pos_equal_eps);
+                }
+                Primitive::Arc {
+                    center,
+                    radius,
+                    start_angle,
+                    end_angle,
+                    ccw,
+                } => {
+                    let sweep = if ccw {
+                        normalize_radians(end_angle - start_angle)
+                    } else {
+                        normalize_radians(start_angle - end_angle)
+                    };
+
+                    let sign = if ccw { T::one() } else { -T::one() };
+                    if sweep.fuzzy_eq_zero() || sweep.fuzzy_eq(T::tau()) {
+                        // full circle, split into two half circles
+                        let opposite = point_on_circle(radius, center, start_angle + T::pi());
+                        result.add_or_replace(start.x, start.y, sign, pos_equal_eps);
+                        result.add(opposite.x, opposite.y, sign);
+                    } else {
+                        let bulge = sign * bulge_from_angle(sweep);
+                        result.add_or_replace(start.x, start.y, bulge, pos_equal_eps);
+                    }
+                }
+            }
+
+            let end = prim.end();
+            result.add(end.x, end.y, T::zero());
+        }
+
+        if result.len() > 2
+            && result[0]
+                .pos()
+                .fuzzy_eq_eps(result.last().unwrap().pos(), pos_equal_eps)
+        {
+            result.remove_last();
+            result.set_is_closed(true);
+        }
+
+        Ok(result)
+    }
+
+    /// Iterate through all the polyline segment vertex positional indexes.
+    ///
+    /// Segments are represented by polyline vertex pairs, for each vertex there is
+    /// an associated positional index in the polyline, this method iterates through
+    /// those positional indexes as segment pairs.
+    pub fn iter_segment_indexes(&self) -> impl Iterator<Item = (usize, usize)> {
+        PlineSegIndexIterator::new(self.vertex_data.len(), self.is_closed)
+    }
+
+    pub fn parallel_offset(
+        &self,
+        offset: T,
+        spatial_index: Option<&StaticAABB2DIndex<T>>,
+    ) -> Vec<Polyline<T>> {
+        polyline_offset::parallel_offset(self, offset, spatial_index, None)
+    }
+
+    /// Parallel offset the polyline (same as [Polyline::parallel_offset]), returning an error if
+    /// the polyline is not valid input for offsetting.
+    ///
+    /// [Polyline::parallel_offset] returns an empty vector both when the offset consumes the whole
+    /// shape and when it fails due to invalid input, this distinguishes the two: `Ok` with an
+    /// empty vector means the offset legitimately consumed the shape. Returns an error (see
+    /// [OffsetError](crate::OffsetError)) if the polyline has less than 2 vertexes, has non-finite
+    /// values, has a zero length segment, is closed and self intersects, or if `offset` is not
+    /// finite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new_closed();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(2.0, 0.0, 0.0);
+    /// polyline.add(2.0, 2.0, 0.0);
+    /// polyline.add(0.0, 2.0, 0.0);
+    /// assert_eq!(polyline.try_parallel_offset(0.5).unwrap().len(), 1);
+    /// polyline.set_vertex(2, 2.0, 0.0, 0.0);
+    /// assert!(matches!(
+    ///     polyline.try_parallel_offset(0.5),
+    ///     Err(OffsetError::ZeroLengthSegment { index: 1 })
+    /// ));
+    /// ```
+    pub fn try_parallel_offset(&self, offset: T) -> Result<Vec<Polyline<T>>, OffsetError> {
+        polyline_offset::try_parallel_offset(self, offset)
+    }
+
+    /// Parallel offset the polyline, writing the results to `out` and reusing the intermediate
+    /// buffers held by `workspace` (see [OffsetWorkspace](crate::OffsetWorkspace)).
+    ///
+    /// `out` is cleared before the results are added (the polylines previously held by `out` are
+    /// kept by `workspace` to be reused) so the same vector and workspace can be reused across
+    /// calls. Results are the same as [Polyline::parallel_offset] with no spatial index given.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new_closed();
+    /// polyline.add(0.0, 0.0, 1.0);
+    /// polyline.add(2.0, 0.0, 1.0);
+    /// let mut workspace = OffsetWorkspace::new();
+    /// let mut result = Vec::new();
+    /// polyline.parallel_offset_into(0.5, &mut workspace, &mut result);
+    /// assert_eq!(result.len(), 1);
+    /// assert!(result[0].area().fuzzy_eq(std::f64::consts::PI * 0.25));
+    /// ```
+    pub fn parallel_offset_into(
+        &self,
+        offset: T,
+        workspace: &mut OffsetWorkspace<T>,
+        out: &mut Vec<Polyline<T>>,
+    ) {
+        polyline_offset::parallel_offset_into(self, offset, None, None, workspace, out)
+    }
+
+    /// Parallel offset the polyline (same as [Polyline::parallel_offset]) and map each resulting
+    /// segment back to the segment of this polyline it was offset from.
+    ///
+    /// Each result is paired with a vector holding one source segment index per result vertex:
+    /// entry `i` is the start vertex index (in this polyline) of the source segment for the result
+    /// segment starting at vertex `i`. For open results the last entry repeats the source of the
+    /// final segment. Arcs added to join segments around an outward corner are mapped to the
+    /// segment ending at the corner.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(10.0, 0.0, 0.0);
+    /// polyline.add(10.0, 10.0, 0.0);
+    /// let result = polyline.parallel_offset_with_source(1.0, None);
+    /// assert_eq!(result.len(), 1);
+    /// let (offset_pline, source) = &result[0];
+    /// assert_eq!(offset_pline.len(), 3);
+    /// assert_eq!(source, &vec![0, 1, 1]);
+    /// ```
+    pub fn parallel_offset_with_source(
+        &self,
+        offset: T,
+        spatial_index: Option<&StaticAABB2DIndex<T>>,
+    ) -> Vec<(Polyline<T>, Vec<usize>)> {
+        polyline_offset::parallel_offset_with_source(self, offset, spatial_index)
+    }
+
+    /// Parallel offset the polyline using the `options` given.
+    ///
+    /// Closed polylines are offset the same as [Polyline::parallel_offset]. Open polylines are
+    /// offset by `offset.abs()` on both sides and the sides are joined at the ends by the end cap
+    /// style set in `options` (see [Endcap](crate::Endcap)), forming closed counter clockwise polylines.
+    /// Outward corners between line segments are joined using the join style set in `options` (see
+    /// [JoinStyle](crate::JoinStyle)).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(10.0, 0.0, 0.0);
+    /// let mut options = OffsetOptions::default();
+    /// options.endcap = Endcap::Round;
+    /// let result = polyline.parallel_offset_opt(1.0, &options);
+    /// assert_eq!(result.len(), 1);
+    /// assert!(result[0].is_closed());
+    /// assert!(result[0].area().fuzzy_eq(20.0 + std::f64::consts::PI));
+    /// ```
+    pub fn parallel_offset_opt(&self, offset: T, options: &OffsetOptions<T>) -> Vec<Polyline<T>> {
+        polyline_offset::parallel_offset_opt(self, offset, options)
+    }
+
+    /// Parallel offset the polyline by both `offset` and `-offset`, returning the
+    /// `(offset, -offset)` results.
+    ///
+    /// The spatial index of the polyline is built once and shared by both offsets, the results are
+    /// the same as calling [Polyline::parallel_offset] twice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new_closed();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(4.0, 0.0, 0.0);
+    /// polyline.add(4.0, 4.0, 0.0);
+    /// polyline.add(0.0, 4.0, 0.0);
+    /// let (inside, outside) = polyline.parallel_offset_both(1.0);
+    /// assert!(inside[0].area().fuzzy_eq(4.0));
+    /// assert!(outside[0].area().fuzzy_eq(36.0 - 4.0 + std::f64::consts::PI));
+    /// ```
+    pub fn parallel_offset_both(&self, offset: T) -> (Vec<Polyline<T>>, Vec<Polyline<T>>) {
+        let spatial_index = self.create_approx_spatial_index();
+        let spatial_index = spatial_index.as_ref();
+        (
+            self.parallel_offset(offset, spatial_index),
+            self.parallel_offset(-offset, spatial_index),
+        )
+    }
+
+    /// Repeatedly parallel offset the polyline inward by `step` until the result is empty,
+    /// returning each generation of offset polylines.
+    ///
+    /// Each generation is created by offsetting all the polylines of the previous generation (the
+    /// first generation is created by offsetting this polyline), each polyline builds its own
+    /// spatial index when offset. If `max_count` is given then at most `max_count` generations are
+    /// returned. Returns an empty vector if the polyline is not closed or `step` is not greater than
+    /// zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut square: Polyline = Polyline::new_closed();
+    /// square.add(0.0, 0.0, 0.0);
+    /// square.add(10.0, 0.0, 0.0);
+    /// square.add(10.0, 10.0, 0.0);
+    /// square.add(0.0, 10.0, 0.0);
+    /// let rings = square.offset_rings(2.0, None);
+    /// assert_eq!(rings.len(), 2);
+    /// assert!(rings[0][0].area().fuzzy_eq(36.0));
+    /// assert!(rings[1][0].area().fuzzy_eq(4.0));
+    /// ```
+    pub fn offset_rings(&self, step: T, max_count: Option<usize>) -> Vec<Vec<Polyline<T>>> {
+        let mut result: Vec<Vec<Polyline<T>>> = Vec::new();
+        if !self.is_closed || self.len() < 2 || step < T::zero() || step.fuzzy_eq_zero() {
+            return result;
+        }
+
+        let max_count = max_count.unwrap_or(usize::MAX);
+        // inward offset is positive for counter clockwise polylines and negative for clockwise
+        let offset_inward = |pline: &Polyline<T>| -> Vec<Polyline<T>> {
+            if pline.area() < T::zero() {
+                pline.parallel_offset(-step, None)
+            } else {
+                pline.parallel_offset(step, None)
+            }
+        };
+
+        let mut generation = offset_inward(self);
+        while !generation.is_empty() && result.len() < max_count {
+            let next_generation = generation.iter().flat_map(offset_inward).collect();
+            result.push(generation);
+            generation = next_generation;
+        }
+
+        result
+    }
+
+    /// Estimate the largest inward offset distance for which [Polyline::parallel_offset] of the
+    /// closed polyline still produces a non-empty result.
+    ///
+    /// This is found by binary searching on the offset distance (offsetting inward with the
+    /// direction of the polyline taken into account), the result is within a small fraction
+    /// (`1e-6`) of the polyline extents size of the true value. Returns `None` if the polyline is
+    /// open, has less than 2 vertexes, or has no area.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut square: Polyline = Polyline::new_closed();
+    /// square.add(0.0, 0.0, 0.0);
+    /// square.add(10.0, 0.0, 0.0);
+    /// square.add(10.0, 10.0, 0.0);
+    /// square.add(0.0, 10.0, 0.0);
+    /// let max_offset = square.max_inward_offset().unwrap();
+    /// assert!((max_offset - 5.0).abs() < 1e-3);
+    /// ```
+    pub fn max_inward_offset(&self) -> Option<T> {
+        if !self.is_closed || self.len() < 2 || self.area().fuzzy_eq_zero() {
+            return None;
+        }
+
+        let extents = self.extents()?;
+        let width = extents.max_x - extents.min_x;
+        let height = extents.max_y - extents.min_y;
+        let size = num_traits::real::Real::max(width, height);
+        let tolerance = size * T::from(1e-6).unwrap();
+
+        // inward offset is positive for counter clockwise polylines and negative for clockwise
+        let sign = if self.area() < T::zero() {
+            -T::one()
+        } else {
+            T::one()
+        };
+
+        let spatial_index = self.create_approx_spatial_index()?;
+        let has_offset = |offset: T| -> bool {
+            !self
+                .parallel_offset(sign * offset, Some(&spatial_index))
+                .is_empty()
+        };
+
+        // no inward offset can be larger than half the extents size (largest inscribed circle)
+        let mut low = T::zero();
+        let mut high = size / T::two();
+        if !has_offset(tolerance) {
+            return None;
+        }
+        if has_offset(high) {
+            return Some(high);
+        }
+
+        while high - low > tolerance {
+            let mid = (low + high) / T::two();
+            if has_offset(mid) {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+
+        Some(low)
+    }
+
+    /// Area of the region between the parallel offsets of the closed polyline at `inner_offset`
+    /// and `outer_offset` (e.g. the material removed between two tool paths).
+    ///
+    /// Offsets follow the same sign convention as [Polyline::parallel_offset] (positive offsets
+    /// are inward for counter clockwise polylines). The area enclosed at each offset is the sum of
+    /// the signed areas of all the closed polylines resulting from the offset (an offset may split
+    /// the polyline into multiple polylines or collapse it entirely), a zero offset uses the area
+    /// of the polyline itself. The absolute difference of the two enclosed areas is returned so the
+    /// order of the offsets does not matter. Returns zero if the polyline is open.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut square: Polyline = Polyline::new_closed();
+    /// square.add(0.0, 0.0, 0.0);
+    /// square.add(10.0, 0.0, 0.0);
+    /// square.add(10.0, 10.0, 0.0);
+    /// square.add(0.0, 10.0, 0.0);
+    /// // ring between the square and its inward offset by 1
+    /// assert!(square.offset_ring_area(1.0, 0.0).fuzzy_eq(100.0 - 64.0));
+    /// ```
+    pub fn offset_ring_area(&self, inner_offset: T, outer_offset: T) -> T {
+        if !self.is_closed {
+            return T::zero();
+        }
+
+        let enclosed_area = |offset: T| -> T {
+            if offset.fuzzy_eq_zero() {
+                return self.area();
+            }
+
+            self.parallel_offset(offset, self.spatial_index())
+                .iter()
+                .filter(|pline| pline.is_closed())
+                .fold(T::zero(), |acc, pline| acc + pline.area())
+        };
+
+        (enclosed_area(inner_offset) - enclosed_area(outer_offset)).abs()
+    }
+
+    /// Returns true if the polyline has any self intersects (including overlapping segments).
+    ///
+    /// `spatial_index` is used to find the segments which may intersect, if `None` is given then
+    /// the spatial index is created (see [Polyline::create_approx_spatial_index]). Intersects at the
+    /// vertex shared between two consecutive segments are not counted as self intersects.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new_closed();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(2.0, 2.0, 0.0);
+    /// polyline.add(2.0, 0.0, 0.0);
+    /// polyline.add(0.0, 2.0, 0.0);
+    /// assert!(polyline.self_intersects(None));
+    /// polyline.set_vertex(1, 2.0, 0.0, 0.0);
+    /// polyline.set_vertex(2, 2.0, 2.0, 0.0);
+    /// assert!(!polyline.self_intersects(None));
+    /// ```
+    pub fn self_intersects(&self, spatial_index: Option<&StaticAABB2DIndex<T>>) -> bool {
+        if self.len() < 2 {
+            return false;
+        }
+
+        let mut _constructed_index = None;
+        let index = if let Some(x) = spatial_index {
+            x
+        } else {
+            _constructed_index = self.create_approx_spatial_index();
+            _constructed_index.as_ref().unwrap()
+        };
+
+        let mut has_intersect = false;
+        let mut has_overlap = false;
+        let mut visitor = |_: PlineIntersect<T>| {
+            has_intersect = true;
+            false
+        };
+        let mut overlap_visitor = |_: PlineOverlappingIntersect<T>| {
+            has_overlap = true;
+            false
+        };
+
+        visit_local_self_intersects(
+            self,
+            &mut visitor,
+            &mut overlap_visitor,
+            self.pos_equal_eps(),
+        );
+
+        visit_global_self_intersects(self, index, &mut visitor, &mut overlap_visitor);
+
+        has_intersect || has_overlap
+    }
+
+    /// Find all the self intersects of the polyline.
+    ///
+    /// Each [SelfIntersect] holds the intersect point and the start vertex indexes of the two
+    /// segments involved. Overlapping segments are reported as two intersects (one at each end of
+    /// the overlap). Intersects at the vertex shared between two consecutive segments are not
+    /// included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new_closed();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(2.0, 2.0, 0.0);
+    /// polyline.add(2.0, 0.0, 0.0);
+    /// polyline.add(0.0, 2.0, 0.0);
+    /// let intersects = polyline.find_self_intersects();
+    /// assert_eq!(intersects.len(), 1);
+    /// assert!(intersects[0].point.fuzzy_eq(Vector2::new(1.0, 1.0)));
+    /// ```
+    pub fn find_self_intersects(&self) -> Vec<SelfIntersect<T>> {
+        let index = match self.create_approx_spatial_index() {
+            Some(x) => x,
+            None => return Vec::new(),
+        };
+
+        all_self_intersects(self, &index, self.pos_equal_eps())
+            .into_iter()
+            .map(|intr| SelfIntersect {
+                start_index1: intr.start_index1,
+                start_index2: intr.start_index2,
+                point: intr.position,
+            })
+            .collect()
+    }
+
+    /// Find all pairs of segments which overlap (coincident collinear lines or coincident arcs on
+    /// the same circle).
+    ///
+    /// Each pair holds the start vertex indexes of the two segments involved (smaller index first),
+    /// pairs are sorted. Segments which only touch at a point are not included, the overlap must be
+    /// longer than `eps` (segments with length less than `eps` are ignored). Overlapping segments
+    /// break operations such as [Polyline::parallel_offset] so this can be used to find them for
+    /// cleanup.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(4.0, 0.0, 0.0);
+    /// polyline.add(4.0, 2.0, 0.0);
+    /// polyline.add(2.0, 0.0, 0.0);
+    /// polyline.add(3.0, 0.0, 0.0);
+    /// assert_eq!(polyline.find_overlaps(1e-5), vec![(0, 3)]);
+    /// ```
+    pub fn find_overlaps(&self, eps: T) -> Vec<(usize, usize)> {
+        let index = match self.spatial_index() {
+            Some(x) => x,
+            None => return Vec::new(),
+        };
+
+        let mut result = Vec::new();
+        let mut query_stack = Vec::with_capacity(8);
+        for (i, j) in self.iter_segment_indexes() {
+            let v1 = self[i];
+            let v2 = self[j];
+            if v1.pos().fuzzy_eq_eps(v2.pos(), eps) {
+                continue;
+            }
+
+            let mut query_visitor = |hit_i: usize| -> bool {
+                // only test each pair of segments once
+                if hit_i <= i {
+                    return true;
+                }
+
+                let u1 = self[hit_i];
+                let u2 = self[self.next_wrapping_index(hit_i)];
+                if u1.pos().fuzzy_eq_eps(u2.pos(), eps) {
+                    return true;
+                }
+
+                match pline_seg_intr(v1, v2, u1, u2) {
+                    PlineSegIntr::OverlappingLines { point1, point2 }
+                    | PlineSegIntr::OverlappingArcs { point1, point2 }
+                        if !point1.fuzzy_eq_eps(point2, eps) =>
+                    {
+                        result.push((i, hit_i));
+                    }
+                    _ => {}
+                }
+
+                true
+            };
+
+            let aabb = seg_fast_approx_bounding_box(v1, v2);
+            index.visit_query_with_stack(
+                aabb.min_x - eps,
+                aabb.min_y - eps,
+                aabb.max_x + eps,
+                aabb.max_y + eps,
+                &mut query_visitor,
+                &mut query_stack,
+            );
+        }
+
+        result.sort_unstable();
+        result
+    }
+
+    /// Split a self intersecting closed polyline into simple closed loops at its self intersect
+    /// points (see [Polyline::find_self_intersects]).
+    ///
+    /// The self intersect points are inserted as vertexes (arc bulges are recomputed so the path
+    /// is unchanged) and then the path is walked, each time the path returns to an intersect point
+    /// it already passed through the loop formed is split off. Each loop keeps the direction of the
+    /// path it was taken from so the sum of the loop areas equals the polyline [Polyline::area].
+    /// Loops with zero area (e.g. from overlapping segments) are discarded.
+    ///
+    /// Open polylines and polylines without self intersects are returned unchanged (as the only
+    /// polyline in the result).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new_closed();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(2.0, 2.0, 0.0);
+    /// polyline.add(2.0, 0.0, 0.0);
+    /// polyline.add(0.0, 2.0, 0.0);
+    /// let loops = polyline.split_at_self_intersects();
+    /// assert_eq!(loops.len(), 2);
+    /// assert!(loops[0].area().fuzzy_eq(-1.0));
+    /// assert!(loops[1].area().fuzzy_eq(1.0));
+    /// ```
+    pub fn split_at_self_intersects(&self) -> Vec<Polyline<T>> {
+        if !self.is_closed || self.len() < 2 {
+            return vec![self.clone()];
+        }
+
+        let intersects = self.find_self_intersects();
+        if intersects.is_empty() {
+            return vec![self.clone()];
+        }
+
+        let pos_equal_eps = self.pos_equal_eps();
+
+        // intersect point id for each intersect, intersects at the same point (e.g. more than two
+        // segments crossing at one point) share the same id, found by sweeping the intersects
+        // sorted by x
+        let mut sorted_indexes: Vec<usize> = (0..intersects.len()).collect();
+        sorted_indexes.sort_unstable_by(|&a, &b| {
+            intersects[a]
+                .point
+                .x
+                .partial_cmp(&intersects[b].point.x)
+                .unwrap_or(core::cmp::Ordering::Equal)
+        });
+        let mut point_ids: Vec<Option<usize>> = vec![None; intersects.len()];
+        let mut point_id_count = 0;
+        for (k, &a) in sorted_indexes.iter().enumerate() {
+            if point_ids[a].is_some() {
+                continue;
+            }
+            point_ids[a] = Some(point_id_count);
+            let point = intersects[a].point;
+            for &b in &sorted_indexes[k + 1..] {
+                if intersects[b].point.x - point.x > pos_equal_eps {
+                    break;
+                }
+                if point_ids[b].is_none() && intersects[b].point.fuzzy_eq_eps(point, pos_equal_eps)
+                {
+                    point_ids[b] = Some(point_id_count);
+                }
+            }
+            point_id_count += 1;
+        }
+
+        // intersect points on each segment as (seg_t, point, point id)
+        let mut seg_points = vec![Vec::new(); self.len()];
+        for (intr, point_id) in intersects.iter().zip(point_ids.iter()) {
+            for &i in &[intr.start_index1, intr.start_index2] {
+                let j = self.next_wrapping_index(i);
+                let seg_t = Self::seg_t_at_point(self[i], self[j], intr.point);
+                seg_points[i].push((seg_t, intr.point, *point_id));
+            }
+        }
+
+        // polyline path with all the intersect points inserted as vertexes, along with the
+        // intersect point id of each vertex (None if the vertex is not at an intersect point)
+        let mut split = Polyline::with_capacity(self.len() + 2 * intersects.len());
+        let mut split_ids: Vec<Option<usize>> =
+            Vec::with_capacity(self.len() + 2 * intersects.len());
+        let mut add_vertex = |split: &mut Polyline<T>, v: PlineVertex<T>, id: Option<usize>| {
+            let len = split.len();
+            split.add_or_replace_vertex(v, pos_equal_eps);
+            if split.len() > len {
+                split_ids.push(id);
+            } else if id.is_some() {
+                // vertex replaced, keep the intersect point id
+                *split_ids.last_mut().unwrap() = id;
+            }
+        };
+        for (i, points) in seg_points.iter_mut().enumerate() {
+            let end = self[self.next_wrapping_index(i)];
+            points.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(core::cmp::Ordering::Equal));
+            let mut current = self[i];
+            let mut current_id = None;
+            for &(_, point, point_id) in points.iter() {
+                let SplitResult {
+                    updated_start,
+                    split_vertex,
+                } = seg_split_at_point(current, end, point, pos_equal_eps);
+                add_vertex(&mut split, updated_start, current_id);
+                current = split_vertex;
+                current_id = point_id;
+            }
+            add_vertex(&mut split, current, current_id);
+        }
+
+        if split.len() > 1
+            && split[0]
+                .pos()
+                .fuzzy_eq_eps(split.last().unwrap().pos(), pos_equal_eps)
+        {
+            split.remove_last();
+            let last_id = split_ids.pop().unwrap();
+            if split_ids[0].is_none() {
+                split_ids[0] = last_id;
+            }
+        }
+
+        // walk the path splitting off a loop each time an intersect point on the current path is
+        // revisited, path index of each intersect point on the current path is tracked by id
+        let mut result = Vec::new();
+        let mut path: Vec<(PlineVertex<T>, Option<usize>)> = Vec::with_capacity(split.len());
+        let mut path_indexes: Vec<Option<usize>> = vec![None; point_id_count];
+        let mut split_off_loop = |path: &mut Vec<(PlineVertex<T>, Option<usize>)>,
+                                  path_indexes: &mut Vec<Option<usize>>,
+                                  start: usize| {
+            let mut loop_pline = Polyline::new_closed();
+            for (lv, id) in path.drain(start..) {
+                if let Some(id) = id {
+                    path_indexes[id] = None;
+                }
+                loop_pline.add_vertex(lv);
+            }
+
+            if !loop_pline.area().fuzzy_eq_zero() {
+                result.push(loop_pline);
+            }
+        };
+        for (v, &id) in split.iter().zip(split_ids.iter()) {
+            if let Some(id) = id {
+                if let Some(k) = path_indexes[id] {
+                    split_off_loop(&mut path, &mut path_indexes, k);
+                }
+                path_indexes[id] = Some(path.len());
+            }
+            path.push((*v, id));
+        }
+
+        // remaining path closes back at the start
+        if !path.is_empty() {
+            split_off_loop(&mut path, &mut path_indexes, 0);
+        }
+
+        result
+    }
+
+    /// Compute the closed signed area of the polyline.
+    ///
+    /// If [Polyline::is_closed] is false (open polyline) then 0.0 is always returned.
+    /// If the polyline is closed and has non-finite values (see [Polyline::is_finite]) then the
+    /// result is not finite.
+    /// The area is signed such that if the polyline direction is counter clockwise
+    /// then the area is positive, otherwise it is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new();
+    /// assert!(polyline.area().fuzzy_eq(0.0));
+    /// polyline.add(1.0, 1.0, 1.0);
+    /// assert!(polyline.area().fuzzy_eq(0.0));
+    ///
+    /// polyline.add(3.0, 1.0, 1.0);
+    /// // polyline is still open so area is 0
+    /// assert!(polyline.area().fuzzy_eq(0.0));
+    /// polyline.set_is_closed(true);
+    /// assert!(polyline.area().fuzzy_eq(std::f64::consts::PI));
+    /// polyline.invert_direction();
+    /// assert!(polyline.area().fuzzy_eq(-std::f64::consts::PI));
+    /// ```
+    pub fn area(&self) -> T {
+        if !self.is_closed {
+            return T::zero();
+        }
+
+        Self::segments_area(self.iter_segments())
+    }
+
+    /// Compute the signed area of the polyline as if it were closed, without changing
+    /// [Polyline::is_closed].
+    ///
+    /// For closed polylines this is the same as [Polyline::area]. Open polylines are treated as
+    /// closed by a straight line segment from the last vertex to the first vertex (the bulge of the
+    /// last vertex is ignored).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(2.0, 0.0, 0.0);
+    /// polyline.add(2.0, 2.0, 0.0);
+    /// assert!(polyline.area().fuzzy_eq(0.0));
+    /// assert!(polyline.area_as_closed().fuzzy_eq(2.0));
+    /// ```
+    pub fn area_as_closed(&self) -> T {
+        if self.is_closed {
+            return self.area();
+        }
+
+        let ln = self.len();
+        if ln < 2 {
+            return T::zero();
+        }
+
+        let mut closing_start = self[ln - 1];
+        closing_start.bulge = T::zero();
+        Self::segments_area(
+            self.iter_segments()
+                .chain(core::iter::once((closing_start, self[0]))),
+        )
+    }
+
+    /// Signed area enclosed by the `segments` given (assumed to form a closed loop).
+    fn segments_area<I>(segments: I) -> T
+    where
+        I: Iterator<Item = (PlineVertex<T>, PlineVertex<T>)>,
+    {
+        // Implementation notes:
+        // Using the shoelace formula (https://en.wikipedia.org/wiki/Shoelace_formula) modified to support
+        // arcs defined by a bulge value. The shoelace formula returns a negative value for clockwise
+        // oriented polygons and positive value for counter clockwise oriented polygons. The area of each
+        // circular segment defined by arcs is then added if it is a counter clockwise arc or subtracted
+        // if it is a clockwise arc. The area of the circular segments are computed by finding the area of
+        // the arc sector minus the area of the triangle defined by the chord and center of circle.
+        // See https://en.wikipedia.org/wiki/Circular_segment
+
+        let mut double_total_area = T::zero();
+
+        for (v1, v2) in segments {
+            double_total_area = double_total_area + Self::seg_double_area(v1, v2);
+        }
+
+        double_total_area / T::two()
+    }
+
+    /// Signed contribution of the segment `v1` to `v2` to the double area sum (see
+    /// [Polyline::segments_area]).
+    fn seg_double_area(v1: PlineVertex<T>, v2: PlineVertex<T>) -> T {
+        let mut double_area = v1.x * v2.y - v1.y * v2.x;
+        if !v1.bulge_is_zero() {
+            // add arc segment area
+            let b = v1.bulge.abs();
+            let sweep_angle = angle_from_bulge(b);
+            let triangle_base = (v2.pos() - v1.pos()).length();
+            let radius = triangle_base * ((b * b + T::one()) / (T::four() * b));
+            let sagitta = b * triangle_base / T::two();
+            let triangle_height = radius - sagitta;
+            let double_sector_area = sweep_angle * radius * radius;
+            let double_triangle_area = triangle_base * triangle_height;
+            let mut double_arc_area = double_sector_area - double_triangle_area;
+            if v1.bulge_is_neg() {
+                double_arc_area = -double_arc_area;
+            }
+
+            double_area = double_area + double_arc_area;
+        }
+
+        double_area
+    }
+
+    /// Returns the signed contribution of each segment to the double area sum used by
+    /// [Polyline::area] (half the sum of the contributions is the area).
+    ///
+    /// The value at index `i` is the contribution of the segment starting at vertex `i`, each
+    /// contribution is the shoelace term of the segment chord (twice the signed area of the
+    /// triangle formed with the origin) plus twice the signed area of the circular segment
+    /// between the chord and the arc (for arc segments). Contributions depend on the position of
+    /// the origin, only their sum is meaningful as an area. Open polylines have no area so an empty
+    /// vector is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new_closed();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(2.0, 0.0, 0.0);
+    /// polyline.add(2.0, 2.0, 0.0);
+    /// let areas = polyline.segment_areas();
+    /// assert_eq!(areas.len(), 3);
+    /// assert!(areas[1].fuzzy_eq(4.0));
+    /// let sum: f64 = areas.iter().sum();
+    /// assert!((sum / 2.0).fuzzy_eq(polyline.area()));
+    /// ```
+    pub fn segment_areas(&self) -> Vec<T> {
+        if !self.is_closed {
+            return Vec::new();
+        }
+
+        let mut result = vec![T::zero(); self.len()];
+        for (i, j) in self.iter_segment_indexes() {
+            result[i] = Self::seg_double_area(self[i], self[j]);
+        }
+
+        result
+    }
+
+    /// Returns the total signed turning angle along the polyline path.
+    ///
+    /// This is the sum of the signed exterior (turning) angles at each vertex plus the signed
+    /// sweep angle of each arc segment (positive for counter clockwise turns). Open polylines only
+    /// include the turning at interior vertexes. Zero length segments are skipped. A simple closed
+    /// counter clockwise polyline returns `2 * PI` and a clockwise one returns `-2 * PI`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new_closed();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(1.0, 0.0, 0.0);
+    /// polyline.add(1.0, 1.0, 0.0);
+    /// assert!(polyline.total_turning_angle().fuzzy_eq(2.0 * std::f64::consts::PI));
+    /// ```
+    pub fn total_turning_angle(&self) -> T {
+        let seg_count = if self.is_closed {
+            self.len()
+        } else {
+            self.len().saturating_sub(1)
+        };
+
+        // end tangent angle of the last non-zero length segment
+        let mut prev_end_tangent: Option<T> = None;
+        let mut first_start_tangent = None;
+        let mut total = T::zero();
+        // note: not using iter_segments since it starts with the closing segment for closed
+        // polylines, segments must be visited in path order
+        for i in 0..seg_count {
+            let v1 = self[i];
+            let v2 = self[self.next_wrapping_index(i)];
+            if v1.pos().fuzzy_eq(v2.pos()) {
+                continue;
+            }
+
+            // arc tangents are the chord direction rotated by half the sweep angle
+            let chord_angle = angle(v1.pos(), v2.pos());
+            let sweep_angle = angle_from_bulge(v1.bulge);
+            let start_tangent = chord_angle - sweep_angle / T::two();
+            let end_tangent = chord_angle + sweep_angle / T::two();
+            total = total + sweep_angle;
+            if let Some(prev_end_tangent) = prev_end_tangent {
+                total = total + delta_angle(prev_end_tangent, start_tangent);
+            } else {
+                first_start_tangent = Some(start_tangent);
+            }
+            prev_end_tangent = Some(end_tangent);
+        }
+
+        if self.is_closed {
+            if let (Some(last_end_tangent), Some(first_start_tangent)) =
+                (prev_end_tangent, first_start_tangent)
+            {
+                total = total + delta_angle(last_end_tangent, first_start_tangent);
+            }
+        }
+
+        total
+    }
+
+    /// Returns true if the closed polyline is convex.
+    ///
+    /// A closed polyline is convex if it has non-zero area, turns in the same direction at every
+    /// vertex (collinear vertexes are allowed), every arc segment bulges outward (same direction as
+    /// the polyline orientation), and it winds around only once (see
+    /// [Polyline::total_turning_angle]). Zero length segments are skipped. Always returns false
+    /// for open polylines.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new_closed();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(2.0, 0.0, 0.0);
+    /// polyline.add(2.0, 2.0, 0.0);
+    /// polyline.add(0.0, 2.0, 0.0);
+    /// assert!(polyline.is_convex());
+    /// polyline.add(1.0, 1.0, 0.0);
+    /// assert!(!polyline.is_convex());
+    /// ```
+    pub fn is_convex(&self) -> bool {
+        if !self.is_closed || self.len() < 2 {
+            return false;
+        }
+
+        let area = self.area();
+        if area.fuzzy_eq_zero() {
+            return false;
+        }
+
+        let is_ccw = area > T::zero();
+        let pos_equal_eps = self.pos_equal_eps();
+        // turn angle (in radians) against the orientation which is still treated as collinear,
+        // independent of the position epsilon since the turn angle has no units
+        let collinear_angle_eps = T::from(1e-8).unwrap();
+        let mut prev_end_tangent: Option<Vector2<T>> = None;
+        let mut first_start_tangent: Option<Vector2<T>> = None;
+        // returns true if turning from tangent t1 to tangent t2 is consistent with the orientation
+        let turn_is_consistent = |t1: Vector2<T>, t2: Vector2<T>| -> bool {
+            let turn_angle = t1.perp_dot(t2).atan2(t1.dot(t2));
+            if is_ccw {
+                turn_angle > -collinear_angle_eps
+            } else {
+                turn_angle < collinear_angle_eps
+            }
+        };
+
+        for i in 0..self.len() {
+            let v1 = self[i];
+            let v2 = self[self.next_wrapping_index(i)];
+            if v1.pos().fuzzy_eq_eps(v2.pos(), pos_equal_eps) {
+                continue;
+            }
+
+            if !v1.bulge_is_zero() && v1.bulge_is_pos() != is_ccw {
+                return false;
+            }
+
+            let start_tangent = seg_tangent_vector(v1, v2, v1.pos());
+            if let Some(prev_tangent) = prev_end_tangent {
+                if !turn_is_consistent(prev_tangent, start_tangent) {
+                    return false;
+                }
+            } else {
+                first_start_tangent = Some(start_tangent);
+            }
+
+            prev_end_tangent = Some(seg_tangent_vector(v1, v2, v2.pos()));
+        }
+
+        // turn at the start vertex
+        if let (Some(t1), Some(t2)) = (prev_end_tangent, first_start_tangent) {
+            if !turn_is_consistent(t1, t2) {
+                return false;
+            }
+        }
+
+        // must wind around only once
+        self.total_turning_angle()
+            .abs()
+            .fuzzy_eq_eps(T::two() * T::pi(), T::from(1e-3).unwrap())
+    }
+
+    /// Compute the area centroid (center of mass) of the polyline.
+    ///
+    /// Returns `None` if [Polyline::is_closed] is false (open polyline), the polyline has less than
+    /// 2 vertexes, or the area of the polyline is zero. Arc segments are accounted for by adding
+    /// (or subtracting for clockwise arcs) the area and centroid of each circular segment formed
+    /// between the arc and its chord.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new_closed();
+    /// assert!(polyline.centroid().is_none());
+    /// polyline.add(0.0, 0.0, 1.0);
+    /// polyline.add(2.0, 0.0, 1.0);
+    /// assert!(polyline.centroid().unwrap().fuzzy_eq(Vector2::new(1.0, 0.0)));
+    /// ```
+    pub fn centroid(&self) -> Option<Vector2<T>> {
+        if !self.is_closed || self.len() < 2 {
+            return None;
+        }
+
+        // Implementation notes:
+        // Same approach as used in the area function, the polygon first moment of area is computed
+        // using the shoelace style formula (https://en.wikipedia.org/wiki/Centroid#Of_a_polygon) and
+        // then the first moment of area of each circular segment defined by arcs is added if it is a
+        // counter clockwise arc or subtracted if it is a clockwise arc. The first moment of a circular
+        // segment is its area times its centroid, the centroid lies along the line from the arc center
+        // to the arc midpoint at a distance of 4 * r * sin^3(a/2) / (3 * (a - sin(a))) from the arc
+        // center (a is the arc sweep angle), multiplying through by the segment area
+        // r^2 * (a - sin(a)) / 2 gives the distance term as chord_length^3 / 12 which avoids
+        // dividing by a value near zero for small arcs.
+        // See https://en.wikipedia.org/wiki/List_of_centroids
+
+        let mut double_total_area = T::zero();
+        let mut sixfold_moment = Vector2::zero();
+        let three = T::from(3).unwrap();
+
+        for (v1, v2) in self.iter_segments() {
+            let cross = v1.x * v2.y - v1.y * v2.x;
+            double_total_area = double_total_area + cross;
+            sixfold_moment = sixfold_moment + (v1.pos() + v2.pos()).scale(cross);
+            if !v1.bulge_is_zero() {
+                let (arc_radius, arc_center) = seg_arc_radius_and_center(v1, v2);
+                let sweep_angle = angle_from_bulge(v1.bulge.abs());
+                let chord_length = (v2.pos() - v1.pos()).length();
+                let mid_dir = (seg_midpoint(v1, v2) - arc_center).normalize();
+
+                let mut double_seg_area =
+                    arc_radius * arc_radius * (sweep_angle - sweep_angle.sin());
+                // 6 * (seg_area * arc_center + chord_length^3 / 12 * mid_dir)
+                let mut sixfold_seg_moment = arc_center.scale(three * double_seg_area)
+                    + mid_dir.scale(chord_length * chord_length * chord_length / T::two());
+
+                if v1.bulge_is_neg() {
+                    double_seg_area = -double_seg_area;
+                    sixfold_seg_moment = -sixfold_seg_moment;
+                }
+
+                double_total_area = double_total_area + double_seg_area;
+                sixfold_moment = sixfold_moment + sixfold_seg_moment;
+            }
+        }
+
+        if double_total_area.fuzzy_eq_zero() {
+            return None;
+        }
+
+        // centroid = moment / area = (sixfold_moment / 6) / (double_total_area / 2)
+        Some(sixfold_moment.scale(T::one() / (three * double_total_area)))
+    }
+
+    /// Find the closest segment point on a polyline to a `point` given.
+    ///
+    /// If the polyline is empty or is not finite (see [Polyline::is_finite]) then `None` is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new();
+    /// assert!(matches!(polyline.closest_point(Vector2::zero()), None));
+    /// polyline.add(1.0, 1.0, 1.0);
+    /// let result = polyline.closest_point(Vector2::new(1.0, 0.0)).unwrap();
+    /// assert_eq!(result.seg_start_index, 0);
+    /// assert!(result.seg_point.fuzzy_eq(polyline[0].pos()));
+    /// assert!(result.seg_t.fuzzy_eq(0.0));
+    /// assert!(result.distance.fuzzy_eq(1.0));
+    /// let mut polyline: Polyline = Polyline::new();
+    /// polyline.add(1.0, 1.0, 0.0);
+    /// polyline.add(3.0, 1.0, 0.0);
+    /// let result = polyline.closest_point(Vector2::new(1.5, 0.0)).unwrap();
+    /// assert_eq!(result.seg_start_index, 0);
+    /// assert!(result.seg_point.fuzzy_eq(Vector2::new(1.5, 1.0)));
+    /// assert!(result.seg_t.fuzzy_eq(0.25));
+    /// assert!(result.distance.fuzzy_eq(1.0));
+    /// ```
+    pub fn closest_point(&self, point: Vector2<T>) -> Option<ClosestPointResult<T>> {
+        if self.len() == 0 || !self.is_finite() {
+            return None;
+        }
+
+        if self.len() == 1 {
+            return Some(ClosestPointResult {
+                seg_start_index: 0,
+                seg_point: self[0].pos(),
+                seg_t: T::zero(),
+                distance: (self[0].pos() - point).length(),
+            });
+        }
+
+        Some(self.closest_point_of_segs(point, self.iter_segment_indexes().map(|(i, _)| i)))
+    }
+
+    /// Find the closest segment point on a polyline to a `point` given using a spatial `index` of
+    /// the polyline segments to prune the search.
+    ///
+    /// Segments are visited in order of their bounding box distance to `point` and the search stops
+    /// once the next bounding box is further away than the closest segment point found, this is
+    /// much faster than [Polyline::closest_point] for polylines with many segments. The result
+    /// is the same as [Polyline::closest_point] (up to ties where segments are equally close).
+    ///
+    /// `index` must be a spatial index of the polyline segments as created by
+    /// [Polyline::create_approx_spatial_index] (or returned by [Polyline::spatial_index]). If the
+    /// polyline is empty or a segment visited by the search has non-finite values (see
+    /// [Polyline::is_finite]) then `None` is returned, segments pruned by the search are not
+    /// checked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new();
+    /// polyline.add(1.0, 1.0, 0.0);
+    /// polyline.add(3.0, 1.0, 1.0);
+    /// polyline.add(5.0, 1.0, 0.0);
+    /// let index = polyline.create_approx_spatial_index().unwrap();
+    /// let point = Vector2::new(1.5, 0.0);
+    /// let result = polyline.closest_point_indexed(point, &index).unwrap();
+    /// let expected = polyline.closest_point(point).unwrap();
+    /// assert_eq!(result.seg_start_index, expected.seg_start_index);
+    /// assert!(result.seg_point.fuzzy_eq(expected.seg_point));
+    /// assert!(result.distance.fuzzy_eq(expected.distance));
+    /// ```
+    pub fn closest_point_indexed(
+        &self,
+        point: Vector2<T>,
+        index: &StaticAABB2DIndex<T>,
+    ) -> Option<ClosestPointResult<T>> {
+        if self.len() < 2 {
+            return self.closest_point(point);
+        }
+
+        let mut result = ClosestPointResult {
+            seg_start_index: 0,
+            seg_point: self[0].pos(),
+            seg_t: T::zero(),
+            distance: Real::max_value(),
+        };
+
+        let mut dist_squared = Real::max_value();
+        let mut is_finite = true;
+        let mut visitor = |i: usize, box_dist_squared: T| {
+            if box_dist_squared > dist_squared {
+                // all remaining segments are further away
+                return false;
+            }
+
+            let v1 = self[i];
+            let v2 = self[self.next_wrapping_index(i)];
+            if !v1.is_finite() || !v2.is_finite() {
+                is_finite = false;
+                return false;
+            }
+
+            let cp = seg_closest_point(v1, v2, point);
+            let dist2 = (point - cp).length_squared();
+            if dist2 < dist_squared {
+                result.seg_start_index = i;
+                result.seg_point = cp;
+                dist_squared = dist2;
+            }
+
+            true
+        };
+
+        index.visit_neighbors(point.x, point.y, &mut visitor);
+        if !is_finite {
+            return None;
+        }
+
+        result.distance = dist_squared.sqrt();
+        let i = result.seg_start_index;
+        result.seg_t =
+            Self::seg_t_at_point(self[i], self[self.next_wrapping_index(i)], result.seg_point);
+
+        Some(result)
+    }
+
+    /// Find the closest segment point on a polyline to a `point` given, only scanning the segments
+    /// with start vertex index in the range `start_seg` to `end_seg` (inclusive).
+    ///
+    /// The range wraps around for closed polylines, e.g. if `end_seg < start_seg` then the
+    /// segments from `start_seg` to the closing segment and from the first segment to `end_seg`
+    /// are scanned. This allows fast repeated queries when the region of interest is already
+    /// known (e.g. when dragging a point along the polyline).
+    ///
+    /// If the polyline has no segments, a segment in the range has non-finite values (see
+    /// [Polyline::is_finite], only the vertexes of the segments in the range are checked) or the
+    /// range is invalid (`start_seg` or `end_seg` is not a segment start index, or
+    /// `end_seg < start_seg` for an open polyline) then `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new_closed();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(2.0, 0.0, 0.0);
+    /// polyline.add(2.0, 2.0, 0.0);
+    /// polyline.add(0.0, 2.0, 0.0);
+    /// // wraps around to scan the closing segment (3) and the first segment (0)
+    /// let result = polyline
+    ///     .closest_point_in_range(Vector2::new(1.0, 1.5), 3, 0)
+    ///     .unwrap();
+    /// assert_eq!(result.seg_start_index, 3);
+    /// assert!(result.seg_point.fuzzy_eq(Vector2::new(0.0, 1.5)));
+    /// assert!(result.distance.fuzzy_eq(1.0));
+    /// assert!(polyline.closest_point_in_range(Vector2::zero(), 4, 0).is_none());
+    /// ```
+    pub fn closest_point_in_range(
+        &self,
+        point: Vector2<T>,
+        start_seg: usize,
+        end_seg: usize,
+    ) -> Option<ClosestPointResult<T>> {
+        let seg_count = match self.len() {
+            0 | 1 => 0,
+            len if self.is_closed => len,
+            len => len - 1,
+        };
+        if seg_count == 0 || start_seg >= seg_count || end_seg >= seg_count {
+            return None;
+        }
+
+        let range_count = if start_seg <= end_seg {
+            end_seg - start_seg + 1
+        } else if self.is_closed {
+            seg_count - start_seg + end_seg + 1
+        } else {
+            return None;
+        };
+
+        let seg_starts = (0..range_count).map(|k| (start_seg + k) % seg_count);
+        let range_is_finite = self[start_seg].is_finite()
+            && seg_starts
+                .clone()
+                .all(|i| self[self.next_wrapping_index(i)].is_finite());
+        if !range_is_finite {
+            return None;
+        }
+
+        Some(self.closest_point_of_segs(point, seg_starts))
+    }
+
+    /// Helper function to find the closest point to `point` on the segments starting at the vertex
+    /// indexes given by `seg_starts` (must yield at least one index).
+    fn closest_point_of_segs<I>(&self, point: Vector2<T>, seg_starts: I) -> ClosestPointResult<T>
+    where
+        I: Iterator<Item = usize>,
+    {
+        let mut result = ClosestPointResult {
+            seg_start_index: 0,
+            seg_point: self[0].pos(),
+            seg_t: T::zero(),
+            distance: Real::max_value(),
+        };
+
+        let mut dist_squared = Real::max_value();
+
+        for i in seg_starts {
+            let v1 = self[i];
+            let v2 = self[self.next_wrapping_index(i)];
+            let cp = seg_closest_point(v1, v2, point);
+            let diff_v = point - cp;
+            let dist2 = diff_v.length_squared();
+            if dist2 < dist_squared {
+                result.seg_start_index = i;
+                result.seg_point = cp;
+                dist_squared = dist2;
+            }
+        }
+
+        result.distance = dist_squared.sqrt();
+        let i = result.seg_start_index;
+        result.seg_t =
+            Self::seg_t_at_point(self[i], self[self.next_wrapping_index(i)], result.seg_point);
+
+        result
+    }
+
+    /// Insert a vertex on the polyline path at the closest point to `point` (see
+    /// [Polyline::closest_point]).
+    ///
+    /// If the closest segment is an arc then its bulge is split so the two resulting arcs
+    /// reproduce the original arc, the polyline path is unchanged. Returns the index of the new
+    /// vertex, if the closest point lies on an existing vertex then no vertex is inserted and the
+    /// index of the existing vertex is returned.
+    ///
+    /// Returns `None` (leaving the polyline unchanged) if the polyline has less than 2 vertexes,
+    /// `point` is not finite, or the polyline is not finite (see [Polyline::is_finite]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(4.0, 0.0, 0.0);
+    /// let index = polyline.insert_at_closest(Vector2::new(1.0, 2.0));
+    /// assert_eq!(index, Some(1));
+    /// assert_eq!(polyline.len(), 3);
+    /// assert!(polyline[1].fuzzy_eq(PlineVertex::new(1.0, 0.0, 0.0)));
+    /// ```
+    pub fn insert_at_closest(&mut self, point: Vector2<T>) -> Option<usize> {
+        if self.len() < 2 || !is_finite(point.x) || !is_finite(point.y) {
+            return None;
+        }
+
+        let pos_equal_eps = self.pos_equal_eps();
+        let closest = self.closest_point(point)?;
+        let i = closest.seg_start_index;
+        let j = self.next_wrapping_index(i);
+        if closest.seg_point.fuzzy_eq_eps(self[i].pos(), pos_equal_eps) {
+            return Some(i);
+        }
+
+        if closest.seg_point.fuzzy_eq_eps(self[j].pos(), pos_equal_eps) {
+            return Some(j);
+        }
+
+        let SplitResult {
+            updated_start,
+            split_vertex,
+        } = seg_split_at_point(self[i], self[j], closest.seg_point, pos_equal_eps);
+        self[i] = updated_start;
+        self.invalidate_spatial_index();
+        self.vertex_data.insert(i + 1, split_vertex);
+
+        Some(i + 1)
+    }
+
+    /// Returns the total path length of the polyline.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new();
+    /// // open polyline half circle
+    /// polyline.add(0.0, 0.0, 1.0);
+    /// polyline.add(2.0, 0.0, 1.0);
+    /// assert!(polyline.path_length().fuzzy_eq(std::f64::consts::PI));
+    /// // close into full circle
+    /// polyline.set_is_closed(true);
+    /// assert!(polyline.path_length().fuzzy_eq(2.0 * std::f64::consts::PI));
+    /// ```
+    pub fn path_length(&self) -> T {
+        self.iter_segments()
+            .fold(T::zero(), |acc, (v1, v2)| acc + seg_length(v1, v2))
+    }
+
+    /// Returns the cumulative path length at each vertex, element `i` is the path length from the
+    /// start of the polyline to vertex `i`.
+    ///
+    /// For closed polylines an extra element is appended for the end of the closing segment (equal
+    /// to [Polyline::path_length]), so the result has `len + 1` elements. For open polylines the
+    /// result has `len` elements. Returns an empty vector if the polyline is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new_closed();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(3.0, 0.0, 0.0);
+    /// polyline.add(3.0, 4.0, 0.0);
+    /// let lengths = polyline.cumulative_lengths();
+    /// assert_eq!(lengths.len(), 4);
+    /// assert!(lengths[1].fuzzy_eq(3.0));
+    /// assert!(lengths[2].fuzzy_eq(7.0));
+    /// assert!(lengths[3].fuzzy_eq(12.0));
+    /// ```
+    pub fn cumulative_lengths(&self) -> Vec<T> {
+        if self.len() == 0 {
+            return Vec::new();
+        }
+
+        let seg_count = if self.is_closed {
+            self.len()
+        } else {
+            self.len() - 1
+        };
+
+        let mut result = Vec::with_capacity(seg_count + 1);
+        let mut acc_length = T::zero();
+        result.push(acc_length);
+        for i in 0..seg_count {
+            acc_length = acc_length + seg_length(self[i], self[self.next_wrapping_index(i)]);
+            result.push(acc_length);
+        }
+
+        result
+    }
+
+    /// Find the segment at `distance` along the polyline path.
+    ///
+    /// Returns the segment start vertex index and the remaining distance along that segment.
+    /// Segments are treated as half open ranges (a distance which lands exactly on a vertex returns
+    /// the segment starting at that vertex), except the end of an open polyline which returns the
+    /// last segment. For closed polylines the distance wraps around, for open polylines it is
+    /// clamped to the path. Returns `None` if the polyline has less than 2 vertexes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(2.0, 0.0, 0.0);
+    /// polyline.add(2.0, 2.0, 0.0);
+    /// let (index, seg_distance) = polyline.vertex_at_arc_length(3.0).unwrap();
+    /// assert_eq!(index, 1);
+    /// assert!(seg_distance.fuzzy_eq(1.0));
+    /// ```
+    pub fn vertex_at_arc_length(&self, distance: T) -> Option<(usize, T)> {
+        if self.len() < 2 {
+            return None;
+        }
+
+        let total_length = self.path_length();
+        let distance = if total_length.fuzzy_eq_zero() {
+            T::zero()
+        } else if self.is_closed {
+            distance - (distance / total_length).floor() * total_length
+        } else {
+            num_traits::real::Real::min(
+                num_traits::real::Real::max(distance, T::zero()),
+                total_length,
+            )
+        };
+
+        let seg_count = if self.is_closed {
+            self.len()
+        } else {
+            self.len() - 1
+        };
+
+        // note: not using iter_segment_indexes since it starts with the closing segment for closed
+        // polylines, segments must be visited in path order
+        let mut last_seg = (0, T::zero());
+        let mut acc_length = T::zero();
+        for i in 0..seg_count {
+            let j = self.next_wrapping_index(i);
+            let seg_len = seg_length(self[i], self[j]);
+            if distance < acc_length + seg_len {
+                return Some((i, distance - acc_length));
+            }
+            acc_length = acc_length + seg_len;
+            last_seg = (i, seg_len);
+        }
+
+        // distance is at the very end of the path (or past it due to floating point error)
+        Some(last_seg)
+    }
+
+    /// Helper function to find the point at `length` along the segment `v1` to `v2`.
+    fn seg_point_at_length(v1: PlineVertex<T>, v2: PlineVertex<T>, length: T) -> Vector2<T> {
+        if v1.bulge_is_zero() || v1.pos().fuzzy_eq(v2.pos()) {
+            let seg_len = (v2.pos() - v1.pos()).length();
+            if seg_len.fuzzy_eq_zero() {
+                return v1.pos();
+            }
+            return v1.pos() + (v2.pos() - v1.pos()).scale(length / seg_len);
+        }
+
+        let (arc_radius, arc_center) = seg_arc_radius_and_center(v1, v2);
+        let start_angle = angle(arc_center, v1.pos());
+        let sweep = length / arc_radius;
+        let point_angle = if v1.bulge_is_neg() {
+            start_angle - sweep
+        } else {
+            start_angle + sweep
+        };
+
+        point_on_circle(arc_radius, arc_center, point_angle)
+    }
+
+    /// Helper function to find the fraction of the segment length at `point` for the segment `v1`
+    /// to `v2` (inverse of [Polyline::seg_point_at_length] normalized by segment length). Assumes
+    /// `point` lies on the segment, the result is clamped to be between 0 and 1.
+    fn seg_t_at_point(v1: PlineVertex<T>, v2: PlineVertex<T>, point: Vector2<T>) -> T {
+        if v1.pos().fuzzy_eq(v2.pos()) || point.fuzzy_eq(v1.pos()) {
+            return T::zero();
+        }
+
+        let t = if v1.bulge_is_zero() {
+            (point - v1.pos()).length() / (v2.pos() - v1.pos()).length()
+        } else {
+            let (_, arc_center) = seg_arc_radius_and_center(v1, v2);
+            let start_angle = angle(arc_center, v1.pos());
+            let point_angle = angle(arc_center, point);
+            let point_sweep = if v1.bulge_is_pos() {
+                normalize_radians(point_angle - start_angle)
+            } else {
+                normalize_radians(start_angle - point_angle)
+            };
+            point_sweep / angle_from_bulge(v1.bulge).abs()
+        };
+
+        num_traits::real::Real::min(num_traits::real::Real::max(t, T::zero()), T::one())
+    }
+
+    /// Returns the point at `distance` along the polyline path from the start of the polyline.
+    ///
+    /// For open polylines `distance` is clamped to be between 0 and [Polyline::path_length], for
+    /// closed polylines `distance` wraps around (e.g. `path_length + 1` is the same as `1`). Points
+    /// on arc segments are found by sweeping along the arc (not along the chord). Returns `None` if
+    /// the polyline is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new();
+    /// assert!(polyline.sample_at_arc_length(0.0).is_none());
+    /// // half circle going from (0, 0) to (2, 0) counter clockwise
+    /// polyline.add(0.0, 0.0, 1.0);
+    /// polyline.add(2.0, 0.0, 0.0);
+    /// let point = polyline.sample_at_arc_length(std::f64::consts::PI / 2.0).unwrap();
+    /// assert!(point.fuzzy_eq(Vector2::new(1.0, -1.0)));
+    /// // clamped to the end
+    /// assert!(polyline.sample_at_arc_length(10.0).unwrap().fuzzy_eq(Vector2::new(2.0, 0.0)));
+    /// ```
+    pub fn sample_at_arc_length(&self, distance: T) -> Option<Vector2<T>> {
+        if self.len() == 1 {
+            return Some(self[0].pos());
+        }
+
+        let (i, seg_distance) = self.vertex_at_arc_length(distance)?;
+        let j = self.next_wrapping_index(i);
+        Some(Self::seg_point_at_length(self[i], self[j], seg_distance))
+    }
+
+    /// Returns the unit tangent direction vector at `distance` along the polyline path.
+    ///
+    /// For line segments this is the segment direction, for arc segments it is perpendicular to
+    /// the arc radius (pointing in the direction of travel along the arc). `distance` is clamped or
+    /// wrapped the same as in [Polyline::sample_at_arc_length], if `distance` lands exactly on a
+    /// vertex then the tangent of the segment starting at that vertex is returned. Returns `None`
+    /// if the polyline has less than 2 vertexes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(2.0, 0.0, 0.0);
+    /// let tangent = polyline.tangent_at_arc_length(1.0).unwrap();
+    /// assert!(tangent.fuzzy_eq(Vector2::new(1.0, 0.0)));
+    /// ```
+    pub fn tangent_at_arc_length(&self, distance: T) -> Option<Vector2<T>> {
+        let (i, seg_distance) = self.vertex_at_arc_length(distance)?;
+        let v1 = self[i];
+        let v2 = self[self.next_wrapping_index(i)];
+        if v1.pos().fuzzy_eq(v2.pos()) {
+            return None;
+        }
+
+        let point = Self::seg_point_at_length(v1, v2, seg_distance);
+        Some(seg_tangent_vector(v1, v2, point).normalize())
+    }
+
+    /// Returns the signed curvature at `distance` along the polyline path.
+    ///
+    /// Curvature is `1 / radius` for arc segments, positive for counter clockwise arcs and negative
+    /// for clockwise arcs, and zero for line segments. Curvature is discontinuous at vertexes so
+    /// segments are treated as half open ranges: if `distance` lands exactly on a vertex then the
+    /// curvature of the segment starting at that vertex is returned (except at the end of an open
+    /// polyline where the last segment is used). Returns `None` if the polyline has less than 2
+    /// vertexes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// // counter clockwise circle with radius 2
+    /// let mut polyline: Polyline = Polyline::new_closed();
+    /// polyline.add(0.0, 0.0, 1.0);
+    /// polyline.add(4.0, 0.0, 1.0);
+    /// assert!(polyline.curvature_at_arc_length(1.0).unwrap().fuzzy_eq(0.5));
+    /// ```
+    pub fn curvature_at_arc_length(&self, distance: T) -> Option<T> {
+        let (i, _) = self.vertex_at_arc_length(distance)?;
+        let v1 = self[i];
+        let v2 = self[self.next_wrapping_index(i)];
+        Some(Self::seg_curvature(v1, v2))
+    }
+
+    /// Helper function to find the signed curvature of the segment `v1` to `v2` (see
+    /// [Polyline::curvature_at_arc_length]).
+    fn seg_curvature(v1: PlineVertex<T>, v2: PlineVertex<T>) -> T {
+        if v1.bulge_is_zero() || v1.pos().fuzzy_eq(v2.pos()) {
+            return T::zero();
+        }
+
+        let (arc_radius, _) = seg_arc_radius_and_center(v1, v2);
+        let curvature = T::one() / arc_radius;
+        if v1.bulge_is_neg() {
+            -curvature
+        } else {
+            curvature
+        }
+    }
+
+    /// Classify the continuity of the polyline path at the vertex at `index`.
+    ///
+    /// The unit tangent directions and signed curvatures (see [Polyline::curvature_at_arc_length])
+    /// of the segments ending and starting at the vertex are compared using `eps`. Returns
+    /// [Continuity::G0] if the tangents differ (a corner), [Continuity::G1] if the tangents match
+    /// but the curvatures differ, and [Continuity::G2] if both match. The first and last vertex of
+    /// an open polyline are not a junction between segments and always return [Continuity::G2],
+    /// a vertex next to a zero length segment returns [Continuity::G0].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(2.0, 0.0, 0.0);
+    /// polyline.add(4.0, 0.0, 0.0);
+    /// polyline.add(4.0, 2.0, 0.0);
+    /// assert_eq!(polyline.continuity_at(1, 1e-5), Continuity::G2);
+    /// assert_eq!(polyline.continuity_at(2, 1e-5), Continuity::G0);
+    /// ```
+    pub fn continuity_at(&self, index: usize, eps: T) -> Continuity {
+        assert!(index < self.len(), "index out of bounds");
+        if !self.is_closed && (index == 0 || index == self.len() - 1) {
+            return Continuity::G2;
+        }
+
+        let prev = self[self.prev_wrapping_index(index)];
+        let current = self[index];
+        let next = self[self.next_wrapping_index(index)];
+        if prev.pos().fuzzy_eq_eps(current.pos(), eps)
+            || current.pos().fuzzy_eq_eps(next.pos(), eps)
+        {
+            return Continuity::G0;
+        }
+
+        let incoming_tangent = seg_tangent_vector(prev, current, current.pos()).normalize();
+        let outgoing_tangent = seg_tangent_vector(current, next, current.pos()).normalize();
+        if !incoming_tangent.fuzzy_eq_eps(outgoing_tangent, eps) {
+            return Continuity::G0;
+        }
+
+        let incoming_curvature = Self::seg_curvature(prev, current);
+        let outgoing_curvature = Self::seg_curvature(current, next);
+        if !incoming_curvature.fuzzy_eq_eps(outgoing_curvature, eps) {
+            return Continuity::G1;
+        }
+
+        Continuity::G2
+    }
+
+    /// Walk the polyline path pushing the point at each of the increasing path `distances` (measured
+    /// from the start of the polyline) to `result`, stops at the first distance past the end of the
+    /// path.
+    fn push_points_at_path_distances<I>(&self, distances: I, result: &mut Vec<Vector2<T>>)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut distances = distances.into_iter();
+        let mut next_distance = match distances.next() {
+            Some(d) => d,
+            None => return,
+        };
+
+        let seg_count = if self.is_closed {
+            self.len()
+        } else {
+            self.len() - 1
+        };
+
+        // path distance at the start of the current segment
+        let mut seg_start_distance = T::zero();
+        for i in 0..seg_count {
+            let v1 = self[i];
+            let v2 = self[self.next_wrapping_index(i)];
+            let seg_len = seg_length(v1, v2);
+            loop {
+                let seg_distance = next_distance - seg_start_distance;
+                if !(seg_distance.fuzzy_lt(seg_len) || seg_distance.fuzzy_eq(seg_len)) {
+                    break;
+                }
+                result.push(Self::seg_point_at_length(v1, v2, seg_distance));
+                next_distance = match distances.next() {
+                    Some(d) => d,
+                    None => return,
+                };
+            }
+            seg_start_distance = seg_start_distance + seg_len;
+        }
+    }
+
+    /// Returns points evenly spaced by `spacing` along the polyline path.
+    ///
+    /// The first point is the start of the polyline and each following point is `spacing` further
+    /// along the path (the last point of an open polyline is only included if the path length is
+    /// a multiple of `spacing`). Returns an empty vector if the polyline is empty or `spacing` is
+    /// not greater than zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(2.0, 0.0, 0.0);
+    /// polyline.add(2.0, 1.0, 0.0);
+    /// let points = polyline.sample_uniform(1.0);
+    /// assert_eq!(points.len(), 4);
+    /// assert!(points[1].fuzzy_eq(Vector2::new(1.0, 0.0)));
+    /// assert!(points[3].fuzzy_eq(Vector2::new(2.0, 1.0)));
+    /// ```
+    pub fn sample_uniform(&self, spacing: T) -> Vec<Vector2<T>> {
+        let mut result = Vec::new();
+        if self.len() == 0 || spacing <= T::zero() {
+            return result;
+        }
+
+        result.push(self[0].pos());
+        self.push_points_at_path_distances(
+            (1..).map(|i| spacing * T::from(i).unwrap()),
+            &mut result,
+        );
+
+        if self.is_closed && result.len() > 1 && result[0].fuzzy_eq(*result.last().unwrap()) {
+            // last sample landed back on the start point
+            result.pop();
+        }
+
+        result
+    }
+
+    /// Returns `n` points evenly spaced by arc length along the polyline path.
+    ///
+    /// The first point is the start of the polyline and the last point is the end of the path (the
+    /// start point again for closed polylines), so the spacing between points is
+    /// `path_length / (n - 1)`. If `n` is 1 then only the start point is returned. Returns an empty
+    /// vector if the polyline is empty or `n` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(2.0, 0.0, 0.0);
+    /// polyline.add(2.0, 2.0, 0.0);
+    /// let points = polyline.resample_count(3);
+    /// assert_eq!(points.len(), 3);
+    /// assert!(points[1].fuzzy_eq(Vector2::new(2.0, 0.0)));
+    /// assert!(points[2].fuzzy_eq(Vector2::new(2.0, 2.0)));
+    /// ```
+    pub fn resample_count(&self, n: usize) -> Vec<Vector2<T>> {
+        let mut result = Vec::with_capacity(n);
+        if self.len() == 0 || n == 0 {
+            return result;
+        }
+
+        result.push(self[0].pos());
+        if n == 1 {
+            return result;
+        }
+
+        let step = self.path_length() / T::from(n - 1).unwrap();
+        self.push_points_at_path_distances(
+            (1..n - 1).map(|i| step * T::from(i).unwrap()),
+            &mut result,
+        );
+
+        // end point is added exactly (also fills any samples lost to floating point error)
+        let end_point = if self.is_closed {
+            self[0].pos()
+        } else {
+            self.last().unwrap().pos()
+        };
+        result.resize(n, end_point);
+
+        result
+    }
+
+    /// Approximate the Hausdorff distance between this polyline and `other` (the largest distance
+    /// from a point on either path to the closest point on the other path).
+    ///
+    /// The distance is approximated by sampling `samples` points evenly spaced by arc length along
+    /// each path (see [Polyline::resample_count]) and finding the closest
+    /// point on the other path for each sample (see [Polyline::closest_point]), the larger of the
+    /// two directed maximums is returned. Closest points are exact so only the sampled side is
+    /// approximated, the result never exceeds the true Hausdorff distance and converges to it as
+    /// `samples` increases. At least 2 samples are always taken. If either polyline is empty then
+    /// `T::max_value()` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(10.0, 0.0, 0.0);
+    /// let mut bent = polyline.clone();
+    /// bent.insert(1, 5.0, 1.0, 0.0);
+    /// assert!(polyline.hausdorff_distance(&bent, 11).fuzzy_eq(1.0));
+    /// ```
+    pub fn hausdorff_distance(&self, other: &Polyline<T>, samples: usize) -> T {
+        if self.len() == 0 || other.len() == 0 {
+            return Real::max_value();
+        }
+
+        let samples = samples.max(2);
+        let directed = |from: &Polyline<T>, to: &Polyline<T>| -> T {
+            from.resample_count(samples)
+                .into_iter()
+                .filter_map(|point| to.closest_point(point))
+                .fold(T::zero(), |max_dist, closest| {
+                    num_traits::real::Real::max(max_dist, closest.distance)
+                })
+        };
+
+        num_traits::real::Real::max(directed(self, other), directed(other, self))
+    }
+
+    /// Split the polyline into two polylines at `distance` along the polyline path.
+    ///
+    /// For open polylines the prefix (start to the cut point) and suffix (cut point to the end) are
+    /// returned. A vertex is inserted at the cut point and the bulge of a split arc segment is
+    /// recomputed so the two partial arcs reproduce the original arc. `distance` is clamped the same
+    /// as in [Polyline::sample_at_arc_length], cutting at the start or end returns a polyline with
+    /// a single vertex on that side.
+    ///
+    /// Closed polylines are opened at the cut point: the first polyline returned is open, starts
+    /// and ends at the cut point and follows the whole path, the second polyline returned is empty.
+    ///
+    /// If the polyline has less than 2 vertexes then an open copy of the polyline and an empty
+    /// polyline are returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(4.0, 0.0, 0.0);
+    /// let (prefix, suffix) = polyline.split_at_arc_length(1.0);
+    /// assert_eq!(prefix.len(), 2);
+    /// assert!(prefix[1].fuzzy_eq(PlineVertex::new(1.0, 0.0, 0.0)));
+    /// assert!(suffix.path_length().fuzzy_eq(3.0));
+    /// ```
+    pub fn split_at_arc_length(&self, distance: T) -> (Polyline<T>, Polyline<T>) {
+        let (i, seg_distance) = match self.vertex_at_arc_length(distance) {
+            Some(seg) => seg,
+            None => {
+                let mut prefix = self.clone();
+                prefix.set_is_closed(false);
+                return (prefix, Polyline::new());
+            }
+        };
+
+        let pos_equal_eps = self.pos_equal_eps();
+        let j = self.next_wrapping_index(i);
+        let point = Self::seg_point_at_length(self[i], self[j], seg_distance);
+        let SplitResult {
+            updated_start,
+            split_vertex,
+        } = seg_split_at_point(self[i], self[j], point, pos_equal_eps);
+        let cut_end = PlineVertex::new(split_vertex.x, split_vertex.y, T::zero());
+
+        if self.is_closed {
+            let mut opened = Polyline::with_capacity(self.len() + 2);
+            opened.add_vertex(split_vertex);
+            for k in 1..self.len() {
+                opened.add_or_replace_vertex(self[(i + k) % self.len()], pos_equal_eps);
+            }
+            opened.add_or_replace_vertex(updated_start, pos_equal_eps);
+            opened.add_or_replace_vertex(cut_end, pos_equal_eps);
+            return (opened, Polyline::new());
+        }
+
+        let mut prefix = Polyline::with_capacity(i + 2);
+        for v in &self.vertex_data[..i] {
+            prefix.add_vertex(*v);
+        }
+        prefix.add_or_replace_vertex(updated_start, pos_equal_eps);
+        prefix.add_or_replace_vertex(cut_end, pos_equal_eps);
+
+        let mut suffix = Polyline::with_capacity(self.len() - i);
+        suffix.add_vertex(split_vertex);
+        for v in &self.vertex_data[j..] {
+            suffix.add_or_replace_vertex(*v, pos_equal_eps);
+        }
+
+        (prefix, suffix)
+    }
+
+    /// Returns the open polyline following the path between `start_distance` and `end_distance`
+    /// along the polyline path.
+    ///
+    /// Vertexes are inserted at both cut points and partial arc bulges are recomputed (see
+    /// [Polyline::split_at_arc_length]), both cuts may land inside the same segment. For open
+    /// polylines the distances are clamped to the path and an empty polyline is returned if
+    /// `start_distance > end_distance`. For closed polylines `start_distance > end_distance` wraps
+    /// around past the start of the polyline, the length sliced is at most the whole path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(3.0, 0.0, 0.0);
+    /// let middle = polyline.slice(1.0, 2.0);
+    /// assert_eq!(middle.len(), 2);
+    /// assert!(middle[0].fuzzy_eq(PlineVertex::new(1.0, 0.0, 0.0)));
+    /// assert!(middle[1].fuzzy_eq(PlineVertex::new(2.0, 0.0, 0.0)));
+    /// ```
+    pub fn slice(&self, start_distance: T, end_distance: T) -> Polyline<T> {
+        if self.is_closed {
+            let total_length = self.path_length();
+            let mut length = end_distance - start_distance;
+            if length < T::zero() {
+                length = length + total_length;
+            }
+            let length = num_traits::real::Real::min(
+                num_traits::real::Real::max(length, T::zero()),
+                total_length,
+            );
+            let (opened, _) = self.split_at_arc_length(start_distance);
+            let (result, _) = opened.split_at_arc_length(length);
+            return result;
+        }
+
+        if start_distance > end_distance {
+            return Polyline::new();
+        }
+
+        let (prefix, _) = self.split_at_arc_length(end_distance);
+        let (_, result) = prefix.split_at_arc_length(start_distance);
+        result
+    }
+
+    /// Helper function for processing a line segment when computing the winding number.
+    fn process_line_winding(v1: PlineVertex<T>, v2: PlineVertex<T>, point: Vector2<T>) -> i32 {
+        let mut result = 0;
+        if v1.y <= point.y {
+            if v2.y > point.y && is_left(v1.pos(), v2.pos(), point) {
+                // left and upward crossing
+                result += 1;
+            }
+        } else if v2.y <= point.y && !is_left(v1.pos(), v2.pos(), point) {
+            // right an downward crossing
+            result -= 1;
+        }
+
+        result
+    }
+
+    /// Helper function for processing an arc segment when computing the winding number.
+    fn process_arc_winding(v1: PlineVertex<T>, v2: PlineVertex<T>, point: Vector2<T>) -> i32 {
+        let is_ccw = v1.bulge_is_pos();
+        let point_is_left = if is_ccw {
+            is_left(v1.pos(), v2.pos(), point)
+        } else {
+            is_left_or_equal(v1.pos(), v2.pos(), point)
+        };
+
+        let dist_to_arc_center_less_than_radius = || {
+            let (arc_radius, arc_center) = seg_arc_radius_and_center(v1, v2);
+            let dist2 = dist_squared(arc_center, point);
+            dist2 < arc_radius * arc_radius
+        };
+
+        let mut result = 0;
+
+        if v1.y <= point.y {
+            if v2.y > point.y {
+                // upward crossing of arc chord
+                if is_ccw {
+                    if point_is_left {
+                        // counter clockwise arc left of chord
+                        result += 1;
+                    } else {
+                        // counter clockwise arc right of chord
+                        if dist_to_arc_center_less_than_radius() {
+                            result += 1;
+                        }
+                    }
+                } else {
+                    if point_is_left {
+                        // clockwise arc left of chord
+                        if !dist_to_arc_center_less_than_radius() {
+                            result += 1;
+                        }
+                        // else clockwise arc right of chord, no crossing
+                    }
+                }
+            } else {
+                // not crossing arc chord and chord is below, check if point is inside arc sector
+                if is_ccw && !point_is_left {
+                    if v2.x < point.x && point.x < v1.x && dist_to_arc_center_less_than_radius() {
+                        result += 1;
+                    }
+                } else if !is_ccw
+                    && point_is_left
+                    && v1.x < point.x
+                    && point.x < v2.x
+                    && dist_to_arc_center_less_than_radius()
+                {
+                    result -= 1;
+                }
+            }
+        } else if v2.y <= point.y {
+            // downward crossing of arc chord
+            if is_ccw {
+                if !point_is_left {
+                    // counter clockwise arc right of chord
+                    if dist_to_arc_center_less_than_radius() {
+                        result -= 1;
+                    }
+                }
+            // else counter clockwise arc left of chord, no crossing
+            } else {
+                if point_is_left {
+                    // clockwise arc left of chord
+                    if dist_to_arc_center_less_than_radius() {
+                        result -= 1;
+                    }
+                } else {
+                    // clockwise arc right of chord
+                    result -= 1;
+                }
+            }
+        } else {
+            // not crossing arc chord and chord is above, check if point is inside arc sector
+            if is_ccw && !point_is_left {
+                if v1.x < point.x && point.x < v2.x && dist_to_arc_center_less_than_radius() {
+                    result += 1;
+                }
+            } else if !is_ccw
+                && point_is_left
+                && v2.x < point.x
+                && point.x < v1.x
+                && dist_to_arc_center_less_than_radius()
+            {
+                result -= 1;
+            }
+        }
+
+        result
+    }
+
+    /// Calculate the winding number for a `point` relative to the polyline.
+    ///
+    /// The winding number calculates the number of turns/windings around a point
+    /// that the polyline path makes. For a closed polyline without self intersects
+    /// there are only three possibilities:
+    ///
+    /// * -1 (winds around point clockwise)
+    /// * 0 (point is outside the polyline)
+    /// * 1 (winds around the point counter clockwise).
+    ///
+    /// This function always returns 0 if polyline [Polyline::is_closed] is false.
+    ///
+    /// If the point lies directly on top of one of the polyline segments the result
+    /// is not defined (see [Polyline::winding_number_checked]).
+    ///
+    /// # Examples
+    ///
+    /// ### Polyline without self intersects
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new_closed();
+    /// polyline.add(0.0, 0.0, 1.0);
+    /// polyline.add(2.0, 0.0, 1.0);
+    /// assert_eq!(polyline.winding_number(Vector2::new(1.0, 0.0)), 1);
+    /// assert_eq!(polyline.winding_number(Vector2::new(0.0, 2.0)), 0);
+    /// polyline.invert_direction();
+    /// assert_eq!(polyline.winding_number(Vector2::new(1.0, 0.0)), -1);
+    /// ```
+    ///
+    /// ### Multiple windings with self intersecting polyline
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new_closed();
+    /// polyline.add(0.0, 0.0, 1.0);
+    /// polyline.add(2.0, 0.0, 1.0);
+    /// polyline.add(0.0, 0.0, 1.0);
+    /// polyline.add(4.0, 0.0, 1.0);
+    /// assert_eq!(polyline.winding_number(Vector2::new(1.0, 0.0)), 2);
+    /// assert_eq!(polyline.winding_number(Vector2::new(-1.0, 0.0)), 0);
+    /// polyline.invert_direction();
+    /// assert_eq!(polyline.winding_number(Vector2::new(1.0, 0.0)), -2);
+    /// ```
+    pub fn winding_number(&self, point: Vector2<T>) -> i32 {
+        if !self.is_closed || self.len() < 2 {
+            return 0;
+        }
+
+        let mut winding = 0;
+
+        for (v1, v2) in self.iter_segments() {
+            if v1.bulge_is_zero() {
+                winding += Self::process_line_winding(v1, v2, point);
+            } else {
+                winding += Self::process_arc_winding(v1, v2, point);
+            }
+        }
+
+        winding
+    }
+
+    /// Same as [Polyline::winding_number] but returns [WindingResult::OnBoundary] if `point` is
+    /// within `eps` of the polyline path (rather than an undefined winding number).
+    ///
+    /// Always returns `WindingResult::Winding(0)` if polyline [Polyline::is_closed] is false.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new_closed();
+    /// polyline.add(0.0, 0.0, 1.0);
+    /// polyline.add(2.0, 0.0, 1.0);
+    /// let inside = polyline.winding_number_checked(Vector2::new(1.0, 0.0), 1e-5);
+    /// assert_eq!(inside, WindingResult::Winding(1));
+    /// let on_arc = polyline.winding_number_checked(Vector2::new(1.0, -1.0), 1e-5);
+    /// assert_eq!(on_arc, WindingResult::OnBoundary);
+    /// ```
+    pub fn winding_number_checked(&self, point: Vector2<T>, eps: T) -> WindingResult {
+        if !self.is_closed || self.len() < 2 {
+            return WindingResult::Winding(0);
+        }
+
+        match self.closest_point(point) {
+            Some(closest_point) if closest_point.distance < eps => WindingResult::OnBoundary,
+            _ => WindingResult::Winding(self.winding_number(point)),
+        }
+    }
+
+    /// Returns true if `point` is inside the closed polyline.
+    ///
+    /// Points within `pos_equal_eps` of the polyline path are treated as on the boundary and
+    /// handled according to `on_boundary` (see [OnBoundary]), otherwise the
+    /// [Polyline::winding_number] is used (any non-zero winding is inside). Always returns false
+    /// for open polylines. Use [Polyline::winding_number_checked] to detect points on the
+    /// boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new_closed();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(2.0, 0.0, 0.0);
+    /// polyline.add(2.0, 2.0, 0.0);
+    /// polyline.add(0.0, 2.0, 0.0);
+    /// assert!(polyline.contains_point(Vector2::new(1.0, 1.0), OnBoundary::Outside, 1e-5));
+    /// assert!(!polyline.contains_point(Vector2::new(3.0, 1.0), OnBoundary::Inside, 1e-5));
+    /// assert!(polyline.contains_point(Vector2::new(2.0, 1.0), OnBoundary::Inside, 1e-5));
+    /// assert!(!polyline.contains_point(Vector2::new(2.0, 1.0), OnBoundary::Outside, 1e-5));
+    /// ```
+    pub fn contains_point(
+        &self,
+        point: Vector2<T>,
+        on_boundary: OnBoundary,
+        pos_equal_eps: T,
+    ) -> bool {
+        if !self.is_closed || self.len() < 2 {
+            return false;
+        }
+
+        match self.winding_number_checked(point, pos_equal_eps) {
+            WindingResult::Winding(winding) => winding != 0,
+            WindingResult::OnBoundary => on_boundary == OnBoundary::Inside,
+        }
+    }
+
+    /// Clip the polyline against the closed polyline `boundary`, returning the pieces of the
+    /// polyline path which lie inside `boundary` if `keep_inside` is true or outside `boundary` if
+    /// `keep_inside` is false.
+    ///
+    /// The polyline is split at all the points where it intersects `boundary` (see
+    /// [intersects::find_intersects](crate::intersects::find_intersects)) and each piece is kept or
+    /// discarded based on the [Polyline::winding_number] of `boundary` at the midpoint of the
+    /// piece (any non-zero winding is inside). Consecutive pieces which are kept are joined, all
+    /// returned pieces are open except if the whole of a closed polyline is kept (in which case a
+    /// copy of the polyline is returned).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `boundary` is not closed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut square: Polyline = Polyline::new_closed();
+    /// square.add(0.0, 0.0, 0.0);
+    /// square.add(2.0, 0.0, 0.0);
+    /// square.add(2.0, 2.0, 0.0);
+    /// square.add(0.0, 2.0, 0.0);
+    /// let mut line: Polyline = Polyline::new();
+    /// line.add(-1.0, 1.0, 0.0);
+    /// line.add(3.0, 1.0, 0.0);
+    /// let inside = line.clip_to(&square, true);
+    /// assert_eq!(inside.len(), 1);
+    /// assert!(inside[0][0].fuzzy_eq(PlineVertex::new(0.0, 1.0, 0.0)));
+    /// assert!(inside[0][1].fuzzy_eq(PlineVertex::new(2.0, 1.0, 0.0)));
+    /// assert_eq!(line.clip_to(&square, false).len(), 2);
+    /// ```
+    pub fn clip_to(&self, boundary: &Polyline<T>, keep_inside: bool) -> Vec<Polyline<T>> {
+        assert!(boundary.is_closed, "boundary polyline must be closed");
+        if self.len() < 2 {
+            return Vec::new();
+        }
+
+        let pos_equal_eps = self.pos_equal_eps();
+        // path length at the start of each segment
+        let seg_start_lengths = self.cumulative_lengths();
+        let total_length = *seg_start_lengths.last().unwrap();
+
+        let cut_distance = |i: usize, point: Vector2<T>| -> T {
+            let v1 = self[i];
+            let v2 = self[self.next_wrapping_index(i)];
+            seg_start_lengths[i] + Self::seg_t_at_point(v1, v2, point) * seg_length(v1, v2)
+        };
+
+        let mut cuts = Vec::new();
+        for intr in find_intersects(self, boundary, None) {
+            match intr {
+                IntersectPoint::Point {
+                    start_index1,
+                    point,
+                    ..
+                } => cuts.push(cut_distance(start_index1, point)),
+                IntersectPoint::Overlap {
+                    start_index1,
+                    point1,
+                    point2,
+                    ..
+                } => {
+                    cuts.push(cut_distance(start_index1, point1));
+                    cuts.push(cut_distance(start_index1, point2));
+                }
+            }
+        }
+
+        if !self.is_closed {
+            cuts.push(T::zero());
+            cuts.push(total_length);
+        }
+
+        cuts.sort_by(|a, b| a.partial_cmp(b).unwrap_or(core::cmp::Ordering::Equal));
+        cuts.dedup_by(|a, b| (*a - *b).abs() < pos_equal_eps);
+
+        let is_kept = |start: T, end: T| -> bool {
+            let mid = self.sample_at_arc_length((start + end) / T::two()).unwrap();
+            (boundary.winding_number(mid) != 0) == keep_inside
+        };
+
+        if self.is_closed && cuts.len() < 2 {
+            // no cuts (or a single touching point), whole polyline is either kept or discarded
+            let start = cuts.first().copied().unwrap_or_else(T::zero);
+            if is_kept(start, start + total_length) {
+                return vec![self.clone()];
+            }
+            return Vec::new();
+        }
+
+        // intervals between cuts in path order, last interval wraps around for closed polylines
+        let mut intervals: Vec<(T, T)> = cuts.windows(2).map(|w| (w[0], w[1])).collect();
+        if self.is_closed {
+            intervals.push((*cuts.last().unwrap(), cuts[0] + total_length));
+        }
+        intervals.retain(|(start, end)| *end - *start >= pos_equal_eps);
+
+        // kept pieces as (start, end) distances, joining consecutive kept intervals
+        let mut pieces: Vec<(T, T)> = Vec::new();
+        let mut first_kept = false;
+        let mut prev_kept = false;
+        for (k, &(start, end)) in intervals.iter().enumerate() {
+            let kept = is_kept(start, end);
+            if kept {
+                if prev_kept {
+                    pieces.last_mut().unwrap().1 = end;
+                } else {
+                    pieces.push((start, end));
+                }
+            }
+            if k == 0 {
+                first_kept = kept;
+            }
+            prev_kept = kept;
+        }
+
+        if self.is_closed && first_kept && prev_kept && pieces.len() > 1 {
+            // join the piece wrapping around the end with the first piece
+            let first = pieces.remove(0);
+            pieces.last_mut().unwrap().1 = first.1 + total_length;
+        }
+
+        pieces
+            .into_iter()
+            .map(|(start, end)| {
+                if self.is_closed && end - start >= total_length - pos_equal_eps {
+                    return self.clone();
+                }
+                self.slice(start, end)
+            })
+            .collect()
+    }
+
+    /// Returns a new polyline with each corner between two line segments replaced by a tangent
+    /// arc with the given `radius`.
+    ///
+    /// The line segments adjacent to each filleted corner are trimmed back to the arc tangent
+    /// points. Corners which are not between two line segments, corners where the segments are
+    /// collinear, and corners too tight for `radius` (the tangent points would not fit on the
+    /// adjacent segments, taking into account the fillets at neighboring corners) are left as is.
+    /// The first and last vertex of an open polyline are never filleted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(4.0, 0.0, 0.0);
+    /// polyline.add(4.0, 4.0, 0.0);
+    /// let result = polyline.fillet(1.0);
+    /// assert_eq!(result.len(), 4);
+    /// assert!(result[1].fuzzy_eq(PlineVertex::new(3.0, 0.0, (std::f64::consts::PI / 8.0).tan())));
+    /// assert!(result[2].fuzzy_eq(PlineVertex::new(4.0, 1.0, 0.0)));
+    /// ```
+    pub fn fillet(&self, radius: T) -> Polyline<T> {
+        if radius <= T::zero() {
+            return self.clone();
+        }
+
+        self.replace_corners(|corner_angle, turns_left| {
+            let trim = radius / (corner_angle / T::two()).tan();
+            // arc sweep is the turning angle at the corner, positive for left (counter clockwise)
+            // turns
+            let sweep = T::pi() - corner_angle;
+            let sweep = if turns_left { sweep } else { -sweep };
+            (trim, bulge_from_angle(sweep))
+        })
+    }
+
+    /// Returns a new polyline with each corner between two line segments cut by a straight line
+    /// segment (chamfer) starting `distance` back from the corner along both segments.
+    ///
+    /// Corners are skipped the same as in [Polyline::fillet] (including corners where the segments
+    /// are too short for `distance`), the chamfered corners are all line segments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(4.0, 0.0, 0.0);
+    /// polyline.add(4.0, 4.0, 0.0);
+    /// let result = polyline.chamfer(1.0);
+    /// assert_eq!(result.len(), 4);
+    /// assert!(result[1].fuzzy_eq(PlineVertex::new(3.0, 0.0, 0.0)));
+    /// assert!(result[2].fuzzy_eq(PlineVertex::new(4.0, 1.0, 0.0)));
+    /// ```
+    pub fn chamfer(&self, distance: T) -> Polyline<T> {
+        if distance <= T::zero() {
+            return self.clone();
+        }
+
+        self.replace_corners(|_, _| (distance, T::zero()))
+    }
+
+    /// Helper function for [Polyline::fillet] and [Polyline::chamfer] which replaces each corner
+    /// between two line segments by a segment between two trim points.
+    ///
+    /// `corner` is called with the angle between the two segments at the corner (between 0 and
+    /// PI) and whether the path turns left (counter clockwise) at the corner, returning the
+    /// distance from the corner to the trim points and the bulge of the segment joining them.
+    fn replace_corners<F>(&self, corner: F) -> Polyline<T>
+    where
+        F: Fn(T, bool) -> (T, T),
+    {
+        let n = self.len();
+        let mut result = Polyline::with_capacity(2 * n);
+        result.set_is_closed(self.is_closed);
+        if n < 3 {
+            result.extend_vertexes(self);
+            return result;
+        }
+
+        // unit directions from the corner to the neighbor vertexes and the corner trim distance and
+        // bulge (None if the corner is not replaced)
+        let corner_data = |i: usize| -> Option<(Vector2<T>, Vector2<T>, T, T)> {
+            if !self.is_closed && (i == 0 || i == n - 1) {
+                return None;
+            }
+
+            let prev = self[self.prev_wrapping_index(i)];
+            let current = self[i];
+            let next = self[self.next_wrapping_index(i)];
+            if !prev.bulge_is_zero() || !current.bulge_is_zero() {
+                return None;
+            }
+
+            let d1 = prev.pos() - current.pos();
+            let d2 = next.pos() - current.pos();
+            if d1.length().fuzzy_eq_zero() || d2.length().fuzzy_eq_zero() {
+                return None;
+            }
+
+            let d1 = d1.normalize();
+            let d2 = d2.normalize();
+            let cos_angle = num_traits::real::Real::min(
+                num_traits::real::Real::max(d1.dot(d2), -T::one()),
+                T::one(),
+            );
+            let corner_angle = cos_angle.acos();
+            if corner_angle.fuzzy_eq_zero() || corner_angle.fuzzy_eq(T::pi()) {
+                return None;
+            }
+
+            let (trim, bulge) = corner(corner_angle, d1.perp_dot(d2) < T::zero());
+            Some((d1, d2, trim, bulge))
+        };
+
+        let corners: Vec<_> = (0..n).map(corner_data).collect();
+        let trim_at = |i: usize| corners[i].map_or(T::zero(), |c| c.2);
+        let fits = |i: usize| -> bool {
+            let prev_index = self.prev_wrapping_index(i);
+            let next_index = self.next_wrapping_index(i);
+            let prev_len = (self[i].pos() - self[prev_index].pos()).length();
+            let next_len = (self[next_index].pos() - self[i].pos()).length();
+            trim_at(i) + trim_at(prev_index) <= prev_len + T::fuzzy_epsilon()
+                && trim_at(i) + trim_at(next_index) <= next_len + T::fuzzy_epsilon()
+        };
+
+        let pos_equal_eps = self.pos_equal_eps();
+        for (i, c) in corners.iter().enumerate() {
+            let current = self[i];
+            let (d1, d2, trim, bulge) = match c {
+                Some(c) if !c.2.fuzzy_eq_zero() && fits(i) => *c,
+                _ => {
+                    result.add_or_replace_vertex(current, pos_equal_eps);
+                    continue;
+                }
+            };
+
+            let start = current.pos() + d1.scale(trim);
+            let end = current.pos() + d2.scale(trim);
+            result.add_or_replace(start.x, start.y, bulge, pos_equal_eps);
+            result.add_or_replace(end.x, end.y, T::zero(), pos_equal_eps);
+        }
+
+        if self.is_closed
+            && result.len() > 1
+            && result[0]
+                .pos()
+                .fuzzy_eq_eps(result.last().unwrap().pos(), pos_equal_eps)
+        {
+            result.remove_last();
+        }
+
+        result
+    }
+
+    /// Subdivide all segments longer than `max_spacing` into equal length parts no longer than
+    /// `max_spacing`, without changing the polyline path.
+    ///
+    /// Line segments are subdivided by inserting colinear vertexes, arc segments are subdivided
+    /// into multiple arc segments along the same arc (with the bulge values updated to match the
+    /// smaller sweep angles).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_spacing` is not greater than zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(3.0, 0.0, 0.0);
+    /// polyline.densify(1.0);
+    /// assert_eq!(polyline.len(), 4);
+    /// assert!(polyline[1].fuzzy_eq(PlineVertex::new(1.0, 0.0, 0.0)));
+    /// assert!(polyline[2].fuzzy_eq(PlineVertex::new(2.0, 0.0, 0.0)));
+    /// ```
+    pub fn densify(&mut self, max_spacing: T) {
+        assert!(
+            max_spacing > T::zero(),
+            "max_spacing must be greater than zero"
+        );
+        if self.len() < 2 {
+            return;
+        }
+
+        let seg_count = if self.is_closed {
+            self.len()
+        } else {
+            self.len() - 1
+        };
+
+        let mut result = Vec::with_capacity(self.len());
+        for i in 0..seg_count {
+            let v1 = self[i];
+            let v2 = self[self.next_wrapping_index(i)];
+            let sub_count = (seg_length(v1, v2) / max_spacing).ceil();
+            let usize_count = sub_count.to_usize().unwrap_or(1);
+            if usize_count < 2 {
+                result.push(v1);
+                continue;
+            }
+
+            if v1.bulge_is_zero() {
+                let step = (v2.pos() - v1.pos()).scale(T::one() / sub_count);
+                for k in 0..usize_count {
+                    let pos = v1.pos() + step.scale(T::from(k).unwrap());
+                    result.push(PlineVertex::new(pos.x, pos.y, T::zero()));
+                }
+                continue;
+            }
+
+            let (arc_radius, arc_center) = seg_arc_radius_and_center(v1, v2);
+            let start_angle = angle(arc_center, v1.pos());
+            let sub_sweep = angle_from_bulge(v1.bulge) / sub_count;
+            let sub_bulge = bulge_from_angle(sub_sweep);
+            result.push(PlineVertex::new(v1.x, v1.y, sub_bulge));
+            for k in 1..usize_count {
+                let pos = point_on_circle(
+                    arc_radius,
+                    arc_center,
+                    start_angle + sub_sweep * T::from(k).unwrap(),
+                );
+                result.push(PlineVertex::new(pos.x, pos.y, sub_bulge));
+            }
+        }
+
+        if !self.is_closed {
+            result.push(self[self.len() - 1]);
+        }
+
+        self.invalidate_spatial_index();
+        self.vertex_data = result;
+    }
+
+    /// Returns a new polyline with all arc segments converted to line segments with some `error_distance` or None
+    /// if T fails to cast to or from usize.
+    ///
+    /// `error_distance` is the maximum distance from any line segment to the arc it is approximating.
+    /// Line segments are circumscribed by the arc (all line end points lie on the arc path).
+    pub fn arcs_to_approx_lines(&self, error_distance: T) -> Option<Self> {
+        self.arcs_to_approx_lines_impl(error_distance, None)
+    }
+
+    /// Same as [Polyline::arcs_to_approx_lines] but the segments are approximated concurrently
+    /// (using rayon).
+    ///
+    /// Each segment is approximated into its own buffer and the buffers are then joined in segment
+    /// order, so the result is identical to [Polyline::arcs_to_approx_lines]. Only worthwhile for
+    /// polylines with many arc segments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new_closed();
+    /// polyline.add(0.0, 0.0, 1.0);
+    /// polyline.add(2.0, 0.0, 1.0);
+    /// let serial = polyline.arcs_to_approx_lines(0.01).unwrap();
+    /// let par = polyline.arcs_to_approx_lines_par(0.01).unwrap();
+    /// assert!(serial.fuzzy_eq(&par));
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn arcs_to_approx_lines_par(&self, error_distance: T) -> Option<Self>
+    where
+        T: Send + Sync,
+    {
+        use rayon::prelude::*;
+
+        if self.len() == 0 {
+            return Some(self.approx_lines_from_vertexes(Vec::new()));
+        }
+
+        let arc_seg_count = Self::error_distance_seg_count(error_distance, None);
+        let segments: Vec<_> = self.iter_segments().collect();
+        let buffers: Vec<Vec<PlineVertex<T>>> = segments
+            .par_iter()
+            .map(|&(v1, v2)| {
+                let mut buffer = Vec::new();
+                Self::seg_to_approx_lines(v1, v2, &arc_seg_count, &mut buffer)?;
+                Some(buffer)
+            })
+            .collect::<Option<_>>()?;
+
+        let mut vertexes = Vec::with_capacity(buffers.iter().map(|b| b.len()).sum::<usize>() + 1);
+        for buffer in buffers {
+            vertexes.extend(buffer);
+        }
+
+        Some(self.approx_lines_from_vertexes(vertexes))
+    }
+
+    /// Returns a new polyline with all arc segments converted to line segments with no line
+    /// segment longer than `max_chord`.
+    ///
+    /// Each arc is divided into the fewest equal parts such that the chord length of each part is
+    /// at most `max_chord`, line end points lie on the arc path. Line segments in the polyline are
+    /// left as is (even if longer than `max_chord`).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_chord` is not greater than zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new_closed();
+    /// polyline.add(0.0, 0.0, 1.0);
+    /// polyline.add(2.0, 0.0, 1.0);
+    /// let result = polyline.arcs_to_approx_lines_by_chord(0.5);
+    /// assert!(result.iter_segments().all(|(v1, v2)| v1.bulge_is_zero()
+    ///     && (v2.pos() - v1.pos()).length() <= 0.5));
+    /// ```
+    pub fn arcs_to_approx_lines_by_chord(&self, max_chord: T) -> Self {
+        assert!(max_chord > T::zero(), "max_chord must be greater than zero");
+        self.arcs_to_approx_lines_with(|arc_radius, angle_diff| {
+            // largest sweep angle for which the chord length does not exceed max_chord
+            let ratio = num_traits::real::Real::min(max_chord / (T::two() * arc_radius), T::one());
+            let seg_sub_angle = T::two() * ratio.asin();
+            Some((angle_diff / seg_sub_angle).ceil())
+        })
+        .expect("failed to cast segment count")
+    }
+
+    /// Returns the polyline path flattened into a strip of points with all arc segments
+    /// approximated by line segments with some `error_distance` (see
+    /// [Polyline::arcs_to_approx_lines]).
+    ///
+    /// For closed polylines the first point is appended at the end to close the strip. Returns an
+    /// empty vector if the polyline is empty. The points are collected from
+    /// [Polyline::flatten_iter] so no intermediate polyline is created.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new_closed();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(1.0, 0.0, 0.0);
+    /// polyline.add(1.0, 1.0, 0.0);
+    /// let points = polyline.flatten_to_points(0.01);
+    /// assert_eq!(points.len(), 4);
+    /// assert!(points[0].fuzzy_eq(points[3]));
+    /// ```
+    pub fn flatten_to_points(&self, error_distance: T) -> Vec<Vector2<T>> {
+        self.flatten_iter(error_distance).collect()
+    }
+
+    /// Returns a lazy iterator over the polyline path flattened into points (see
+    /// [Polyline::flatten_to_points], which collects this iterator).
+    ///
+    /// Points are computed segment by segment as the iterator advances (arcs are subdivided on the
+    /// fly) so no intermediate polyline or vector is allocated. The points are the vertex
+    /// positions of [Polyline::arcs_to_approx_lines] (with the first point repeated at the end for
+    /// closed polylines). Segments for which T fails to cast to or from usize yield no points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new_closed();
+    /// polyline.add(0.0, 0.0, 1.0);
+    /// polyline.add(2.0, 0.0, 1.0);
+    /// let count = polyline.flatten_iter(0.01).count();
+    /// assert_eq!(count, polyline.flatten_to_points(0.01).len());
+    /// ```
+    pub fn flatten_iter(&self, error_distance: T) -> impl Iterator<Item = Vector2<T>> + '_ {
+        let arc_seg_count = Self::error_distance_seg_count(error_distance, None);
+        let end_point = if self.is_closed {
+            // first point is repeated at the end to close the strip
+            self.iter_segments().next().map(|(v1, _)| v1.pos())
+        } else {
+            self.last().map(|v| v.pos())
+        };
+
+        self.iter_segments()
+            .flat_map(move |(v1, v2)| {
+                Self::seg_approx_points(v1, v2, &arc_seg_count)
+                    .into_iter()
+                    .flatten()
+            })
+            .chain(end_point)
+    }
+
+    /// Same as [Polyline::arcs_to_approx_lines] but each arc is approximated by at most
+    /// `max_segments_per_arc` line segments.
+    ///
+    /// Whichever of `error_distance` or `max_segments_per_arc` yields fewer segments is used, each
+    /// arc is always approximated by at least one line segment (even if `max_segments_per_arc` is
+    /// 0).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new_closed();
+    /// polyline.add(0.0, 0.0, 1.0);
+    /// polyline.add(2.0, 0.0, 1.0);
+    /// let result = polyline.arcs_to_approx_lines_capped(1e-6, 4).unwrap();
+    /// assert_eq!(result.len(), 8);
+    /// ```
+    pub fn arcs_to_approx_lines_capped(
+        &self,
+        error_distance: T,
+        max_segments_per_arc: usize,
+    ) -> Option<Self> {
+        self.arcs_to_approx_lines_impl(error_distance, Some(max_segments_per_arc))
+    }
+
+    fn arcs_to_approx_lines_impl(
+        &self,
+        error_distance: T,
+        max_segments_per_arc: Option<usize>,
+    ) -> Option<Self> {
+        self.arcs_to_approx_lines_with(Self::error_distance_seg_count(
+            error_distance,
+            max_segments_per_arc,
+        ))
+    }
+
+    /// Helper function which returns the arc segment count function used by
+    /// [Polyline::arcs_to_approx_lines_with] for approximating arcs with some `error_distance`.
+    fn error_distance_seg_count(
+        error_distance: T,
+        max_segments_per_arc: Option<usize>,
+    ) -> impl Fn(T, T) -> Option<T> {
+        let abs_error = error_distance.abs();
+        move |arc_radius, angle_diff| {
+            if arc_radius.fuzzy_lt(error_distance) {
+                return Some(T::one());
+            }
+
+            let seg_sub_angle = T::two() * (T::one() - abs_error / arc_radius).acos().abs();
+            let mut seg_count = (angle_diff / seg_sub_angle).ceil();
+            if let Some(max_count) = max_segments_per_arc {
+                let max_count = T::from(max_count.max(1))?;
+                if max_count < seg_count {
+                    seg_count = max_count;
+                }
+            }
+
+            Some(seg_count)
+        }
+    }
+
+    /// Helper function to convert all arc segments to line segments, `arc_seg_count` is called
+    /// with the arc radius and arc sweep angle (absolute value) to get the number of line segments
+    /// (as T) to approximate each arc with. Returns None if `arc_seg_count` returns None or T
+    /// fails to cast to or from usize.
+    fn arcs_to_approx_lines_with<F>(&self, arc_seg_count: F) -> Option<Self>
+    where
+        F: Fn(T, T) -> Option<T>,
+    {
+        let mut vertexes = Vec::new();
+        for (v1, v2) in self.iter_segments() {
+            Self::seg_to_approx_lines(v1, v2, &arc_seg_count, &mut vertexes)?;
+        }
+
+        Some(self.approx_lines_from_vertexes(vertexes))
+    }
+
+    /// Helper function to remove the last vertex of a closed polyline if it repeats the start
+    /// point (formats such as WKT, GeoJSON and SVG paths repeat the start point to close a ring).
+    pub(crate) fn remove_repeated_ring_end(&mut self) {
+        if self.is_closed() && self.len() > 1 && self[0].pos().fuzzy_eq(self.last().unwrap().pos())
+        {
+            self.remove_last();
+        }
+    }
+
+    /// Helper function to approximate arcs by lines for writing to formats without arc segments
+    /// (see [Polyline::arcs_to_approx_lines]), closed polylines keep the same start point and
+    /// repeat it at the end. Returns None if the polyline has less than 2 vertexes or the
+    /// approximation fails.
+    pub(crate) fn approx_lines_ring_path(&self, error_distance: T) -> Option<Self> {
+        let mut approx = match self.arcs_to_approx_lines(error_distance) {
+            Some(approx) if self.len() > 1 => approx,
+            _ => return None,
+        };
+
+        if self.is_closed() {
+            // keep the same start point as the polyline
+            let start_index = approx.iter().position(|v| v.pos().fuzzy_eq(self[0].pos()));
+            if let Some(start_index) = start_index {
+                approx.set_start_vertex(start_index);
+            }
+            approx.add_vertex(approx[0]);
+        }
+
+        Some(approx)
+    }
+
+    /// Helper function to add the vertexes approximating the segment `v1` to `v2` with line
+    /// segments to `out` (the end vertex is not added), see [Polyline::arcs_to_approx_lines_with].
+    fn seg_to_approx_lines<F>(
+        v1: PlineVertex<T>,
+        v2: PlineVertex<T>,
+        arc_seg_count: &F,
+        out: &mut Vec<PlineVertex<T>>,
+    ) -> Option<()>
+    where
+        F: Fn(T, T) -> Option<T>,
+    {
+        if v1.bulge_is_zero() {
+            out.push(v1);
+            return Some(());
+        }
+
+        let points = Self::seg_approx_points(v1, v2, arc_seg_count)?;
+        out.extend(points.map(|pos| PlineVertex::new(pos.x, pos.y, T::zero())));
+        Some(())
+    }
+
+    /// Helper function returning an iterator over the points approximating the segment `v1` to
+    /// `v2` with line segments (the end point is not included), see
+    /// [Polyline::arcs_to_approx_lines_with]. Returns None if `arc_seg_count` returns None or T
+    /// fails to cast to or from usize.
+    fn seg_approx_points<F>(
+        v1: PlineVertex<T>,
+        v2: PlineVertex<T>,
+        arc_seg_count: &F,
+    ) -> Option<impl Iterator<Item = Vector2<T>>>
+    where
+        F: Fn(T, T) -> Option<T>,
+    {
+        let mut arc = None;
+        let mut usize_count = 1;
+        if !v1.bulge_is_zero() {
+            let (arc_radius, arc_center) = seg_arc_radius_and_center(v1, v2);
+            let start_angle = angle(arc_center, v1.pos());
+            // note: using bulge rather than end angle so arcs sweeping more than a half circle work
+            let angle_diff = angle_from_bulge(v1.bulge).abs();
+
+            let seg_count = arc_seg_count(arc_radius, angle_diff)?;
+            if seg_count > T::one() {
+                // create angle offset such that all lines have an equal part of the arc
+                let seg_angle_offset = if v1.bulge_is_neg() {
+                    -angle_diff / seg_count
+                } else {
+                    angle_diff / seg_count
+                };
+                usize_count = seg_count.to_usize()?;
+                // check the last index casts so casting within the iterator cannot fail
+                T::from(usize_count - 1)?;
+                arc = Some((arc_radius, arc_center, start_angle, seg_angle_offset));
+            }
+        }
+
+        // start point followed by all the points along the arc
+        Some((0..usize_count).map(move |i| match arc {
+            Some((arc_radius, arc_center, start_angle, seg_angle_offset)) if i > 0 => {
+                let angle_pos = T::from(i).unwrap();
+                let angle = angle_pos * seg_angle_offset + start_angle;
+                point_on_circle(arc_radius, arc_center, angle)
+            }
+            _ => v1.pos(),
+        }))
+    }
+
+    /// Helper function to create the approximated polyline from the segment `vertexes` created by
+    /// [Polyline::seg_to_approx_lines].
+    fn approx_lines_from_vertexes(&self, vertexes: Vec<PlineVertex<T>>) -> Self {
+        let mut result: Polyline<T> = vertexes.into_iter().collect();
+        result.set_is_closed(self.is_closed);
+        if !self.is_closed && self.len() > 0 {
+            // add the final missing vertex in the case that the polyline is not closed
+            result.add_vertex(self[self.len() - 1]);
+        }
+
+        result
+    }
+}
+
+/// Join open polylines end to end where their end points are fuzzy equal (using `pos_equal_eps`).
+///
+/// Polylines are greedily connected in order: each polyline is extended at its end and then at
+/// its start by the first unused polyline with a matching end point, inverting the direction of
+/// the polyline being appended if required (see [Polyline::invert_direction]). Joined polylines
+/// whose final end point meets their start point are closed. Closed polylines and polylines which
+/// could not be joined to any other polyline are returned unchanged.
+///
+/// # Examples
+///
+/// ```
+/// # use cavalier_contours::*;
+/// let mut first: Polyline = Polyline::new();
+/// first.add(0.0, 0.0, 0.0);
+/// first.add(1.0, 0.0, 0.0);
+/// let mut second = Polyline::new();
+/// second.add(2.0, 0.0, 0.0);
+/// second.add(1.0, 0.0, 0.0);
+/// let joined = join_polylines(&[first, second], 1e-5);
+/// assert_eq!(joined.len(), 1);
+/// assert_eq!(joined[0].len(), 3);
+/// assert!(joined[0][2].pos().fuzzy_eq(Vector2::new(2.0, 0.0)));
+/// ```
+pub fn join_polylines<T>(plines: &[Polyline<T>], pos_equal_eps: T) -> Vec<Polyline<T>>
+where
+    T: Real,
+{
+    let mut result = Vec::new();
+    let mut used = vec![false; plines.len()];
+
+    // extend current at its end with the first matching unused open polyline, returns true if
+    // joined
+    let try_extend = |current: &mut Polyline<T>, used: &mut Vec<bool>| -> bool {
+        let end_point = current.last().unwrap().pos();
+        for (j, other) in plines.iter().enumerate() {
+            if used[j] || other.is_closed() || other.len() < 2 {
+                continue;
+            }
+
+            let mut to_append = if other[0].pos().fuzzy_eq_eps(end_point, pos_equal_eps) {
+                other.clone()
+            } else if other
+                .last()
+                .unwrap()
+                .pos()
+                .fuzzy_eq_eps(end_point, pos_equal_eps)
+            {
+                let mut inverted = other.clone();
+                inverted.invert_direction();
+                inverted
+            } else {
+                continue;
+            };
+
+            used[j] = true;
+            // end vertex of open polyline has no segment so its bulge is discarded
+            let ln = to_append.len();
+            to_append[ln - 1].bulge = T::zero();
+            for v in to_append.iter() {
+                current.add_or_replace_vertex(*v, pos_equal_eps);
+            }
+            return true;
+        }
+
+        false
+    };
+
+    for (i, pline) in plines.iter().enumerate() {
+        if used[i] {
+            continue;
+        }
+
+        used[i] = true;
+        if pline.is_closed() || pline.len() < 2 {
+            result.push(pline.clone());
+            continue;
+        }
+
+        let mut current = pline.clone();
+        let ln = current.len();
+        current[ln - 1].bulge = T::zero();
+        let mut joined = false;
+        while try_extend(&mut current, &mut used) {
+            joined = true;
+        }
+
+        // extend at the start by extending the inverted polyline at its end
+        current.invert_direction();
+        let ln = current.len();
+        current[ln - 1].bulge = T::zero();
+        while try_extend(&mut current, &mut used) {
+            joined = true;
+        }
+        current.invert_direction();
+        let ln = current.len();
+        current[ln - 1].bulge = T::zero();
+
+        if !joined {
+            result.push(pline.clone());
+            continue;
+        }
+
+        let ln = current.len();
+        if ln > 2
+            && current[0]
+                .pos()
+                .fuzzy_eq_eps(current[ln - 1].pos(), pos_equal_eps)
+        {
+            current.remove_last();
+            current.set_is_closed(true);
+        }
+
+        result.push(current);
+    }
+
+    result
+}
+
+/// Find the angle (relative to the X axis) of the convex hull edge which the minimum area bounding
+/// rectangle is aligned with, using rotating calipers over the counter clockwise `hull` points (as
+/// returned by [convex_hull]). Returns `None` if the hull has less than 2 points.
+fn min_area_rect_edge_angle<T>(hull: &[Vector2<T>]) -> Option<T>
+where
+    T: Real,
+{
+    let n = hull.len();
+    if n < 2 {
+        return None;
+    }
+
+    if n == 2 {
+        return Some(angle(hull[0], hull[1]));
+    }
+
+    let next = |i: usize| (i + 1) % n;
+    // caliper indexes: furthest point along the edge direction, furthest point from the edge, and
+    // furthest point against the edge direction, each only moves forward around the hull
+    let mut right = 1;
+    let mut top = 1;
+    let mut left = 1;
+    let mut best_area = Real::max_value();
+    let mut best_edge = 0;
+    for i in 0..n {
+        let dir = (hull[next(i)] - hull[i]).normalize();
+        let normal = dir.perp();
+        while (hull[next(right)] - hull[right]).dot(dir) > T::zero() {
+            right = next(right);
+        }
+
+        if i == 0 {
+            top = right;
+        }
+
+        while (hull[next(top)] - hull[top]).dot(normal) > T::zero() {
+            top = next(top);
+        }
+
+        if i == 0 {
+            left = top;
+        }
+
+        while (hull[next(left)] - hull[left]).dot(dir) < T::zero() {
+            left = next(left);
+        }
+
+        let width = (hull[right] - hull[left]).dot(dir);
+        let height = (hull[top] - hull[i]).dot(normal);
+        let area = width * height;
+        if area < best_area {
+            best_area = area;
+            best_edge = i;
+        }
+    }
+
+    Some(angle(hull[best_edge], hull[next(best_edge)]))
+}
+
+/// Compute the convex hull of `points` using the monotone chain algorithm.
+///
+/// Returns the hull points in counter clockwise order (collinear points are excluded).
+fn convex_hull<T>(mut points: Vec<Vector2<T>>) -> Vec<Vector2<T>>
+where
+    T: Real,
+{
+    points.sort_unstable_by(|a, b| {
+        a.x.partial_cmp(&b.x)
+            .unwrap_or(core::cmp::Ordering::Equal)
+            .then(a.y.partial_cmp(&b.y).unwrap_or(core::cmp::Ordering::Equal))
+    });
+    points.dedup_by(|a, b| a.fuzzy_eq(*b));
+
+    if points.len() < 3 {
+        return points;
+    }
+
+    let turns_left =
+        |o: Vector2<T>, a: Vector2<T>, b: Vector2<T>| (a - o).perp_dot(b - o) > T::zero();
+
+    let mut hull: Vec<Vector2<T>> = Vec::with_capacity(2 * points.len());
+    // lower hull
+    for &p in points.iter() {
+        while hull.len() >= 2 && !turns_left(hull[hull.len() - 2], hull[hull.len() - 1], p) {
+            hull.pop();
+        }
+        hull.push(p);
+    }
+
+    // upper hull
+    let lower_len = hull.len() + 1;
+    for &p in points.iter().rev().skip(1) {
+        while hull.len() >= lower_len && !turns_left(hull[hull.len() - 2], hull[hull.len() - 1], p)
+        {
+            hull.pop();
+        }
+        hull.push(p);
+    }
+
+    // last point is the same as the first
+    hull.pop();
+    hull
+}
+
+/// Compute the minimum enclosing circle of `points` (iterative form of Welzl's algorithm).
+///
+/// Returns the circle center and radius, `points` must not be empty.
+fn min_enclosing_circle<T>(points: &[Vector2<T>]) -> (Vector2<T>, T)
+where
+    T: Real,
+{
+    let circle_from_2 = |p1: Vector2<T>, p2: Vector2<T>| {
+        let center = midpoint(p1, p2);
+        (center, (p1 - center).length())
+    };
+
+    let circle_from_3 = |p1: Vector2<T>, p2: Vector2<T>, p3: Vector2<T>| {
+        let b = p2 - p1;
+        let c = p3 - p1;
+        let d = T::two() * b.perp_dot(c);
+        if d.fuzzy_eq_zero() {
+            // colinear, use the farthest pair
+            let candidates = [(p1, p2), (p1, p3), (p2, p3)];
+            let (q1, q2) = candidates
+                .iter()
+                .copied()
+                .max_by(|a, b| {
+                    dist_squared(a.0, a.1)
+                        .partial_cmp(&dist_squared(b.0, b.1))
+                        .unwrap_or(core::cmp::Ordering::Equal)
+                })
+                .unwrap();
+            return circle_from_2(q1, q2);
+        }
+
+        let b_len2 = b.length_squared();
+        let c_len2 = c.length_squared();
+        let offset = Vector2::new(
+            (c.y * b_len2 - b.y * c_len2) / d,
+            (b.x * c_len2 - c.x * b_len2) / d,
+        );
+        (p1 + offset, offset.length())
+    };
+
+    let is_inside =
+        |circle: (Vector2<T>, T), p: Vector2<T>| (p - circle.0).length().fuzzy_lt(circle.1);
+
+    let mut circle = (points[0], T::zero());
+    for i in 1..points.len() {
+        if is_inside(circle, points[i]) {
+            continue;
+        }
+
+        circle = (points[i], T::zero());
+        for j in 0..i {
+            if is_inside(circle, points[j]) {
+                continue;
+            }
+
+            circle = circle_from_2(points[i], points[j]);
+            for k in 0..j {
+                if !is_inside(circle, points[k]) {
+                    circle = circle_from_3(points[i], points[j], points[k]);
+                }
+            }
+        }
+    }
+
+    circle
+}
+
+/// How points on the boundary of a polyline are classified by [Polyline::contains_point].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum OnBoundary {
+    /// Points on the boundary are inside.
+    Inside,
+    /// Points on the boundary are outside.
+    Outside,
+}
+
+/// Result from calling [Polyline::winding_number_checked].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WindingResult {
+    /// Point is not on the polyline path, holds the winding number (see [Polyline::winding_number]).
+    Winding(i32),
+    /// Point is on the polyline path (within the epsilon given).
+    OnBoundary,
+}
+
+/// Continuity of the polyline path at a vertex, returned by [Polyline::continuity_at].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Continuity {
+    /// Position continuous only (tangent direction changes, e.g. a corner).
+    G0,
+    /// Tangent continuous (tangent direction is the same but curvature changes).
+    G1,
+    /// Curvature continuous (tangent direction and curvature are the same).
+    G2,
+}
+
+/// Error returned by [Polyline] operations that cannot be completed for the polyline given.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PlineError {
+    /// Non-uniform scaling was requested for a polyline that has arc segments (arcs cannot remain
+    /// circular when scaled non-uniformly).
+    NonUniformScaleWithArcs,
+    /// Vertex at `index` has a non-finite (NaN or infinite) x, y, or bulge value.
+    NonFiniteVertex {
+        /// Index of the vertex with the non-finite value.
+        index: usize,
+    },
+    /// Primitive at `index` does not start where the previous primitive ends.
+    DisconnectedPrimitive {
+        /// Index of the disconnected primitive.
+        index: usize,
+    },
+}
+
+impl fmt::Display for PlineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlineError::NonUniformScaleWithArcs => {
+                write!(f, "non-uniform scale cannot be applied to arc segments")
+            }
+            PlineError::NonFiniteVertex { index } => {
+                write!(f, "vertex at index {} has a non-finite value", index)
+            }
+            PlineError::DisconnectedPrimitive { index } => write!(
+                f,
+                "primitive at index {} does not start at the end of the previous primitive",
+                index
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PlineError {}
+
+/// Self intersect found by calling [Polyline::find_self_intersects].
+#[derive(Debug, Copy, Clone)]
+pub struct SelfIntersect<T>
+where
+    T: Real,
+{
+    /// The start vertex index of the first segment involved in the intersect.
+    pub start_index1: usize,
+    /// The start vertex index of the second segment involved in the intersect.
+    pub start_index2: usize,
+    /// The intersect point.
+    pub point: Vector2<T>,
+}
+
+/// Result from calling [Polyline::closest_point].
+#[derive(Debug, Copy, Clone)]
+pub struct ClosestPointResult<T>
+where
+    T: Real,
+{
+    /// The start vertex index of the closest segment.
+    pub seg_start_index: usize,
+    /// The closest point on the closest segment.
+    pub seg_point: Vector2<T>,
+    /// Position of `seg_point` along the closest segment as a fraction of the segment length (0 at
+    /// the segment start, 1 at the segment end), for arcs this is the fraction of the arc sweep.
+    pub seg_t: T,
+    /// The distance between the points.
+    pub distance: T,
+}
+
+/// Square cell used by [Polyline::max_inscribed_circle], ordered by `potential`.
+#[derive(Debug, Copy, Clone)]
+struct InscribedCell<T> {
+    center: Vector2<T>,
+    half_size: T,
+    /// Signed distance from the cell center to the polyline.
+    distance: T,
+    /// Maximum signed distance possible for any point within the cell.
+    potential: T,
+}
+
+impl<T> PartialEq for InscribedCell<T>
+where
+    T: Real,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T> Eq for InscribedCell<T> where T: Real {}
+
+impl<T> PartialOrd for InscribedCell<T>
+where
+    T: Real,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for InscribedCell<T>
+where
+    T: Real,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        // NaN potentials compare equal so the heap ordering stays total
+        self.potential
+            .partial_cmp(&other.potential)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl<T, D> Index<usize> for Polyline<T, D>
+where
+    T: Real,
+{
+    type Output = PlineVertex<T, D>;
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.vertex_data[index]
+    }
+}
+
+impl<T, D> IndexMut<usize> for Polyline<T, D>
+where
+    T: Real,
+{
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.invalidate_spatial_index();
+        &mut self.vertex_data[index]
+    }
+}
+
+impl<T, D> Default for Polyline<T, D>
+where
+    T: Real,
+{
+    /// Create a new empty open [Polyline] (same as [Polyline::new]).
+    #[inline]
+    fn default() -> Self {
+        Polyline {
+            vertex_data: Vec::new(),
+            is_closed: false,
+            pos_equal_eps: None,
+            spatial_index_cache: SpatialIndexCache::new(),
+        }
+    }
+}
+
+impl<T, D> Extend<PlineVertex<T, D>> for Polyline<T, D>
+where
+    T: Real,
+{
+    /// Append vertexes to the end of the polyline.
+    fn extend<I: IntoIterator<Item = PlineVertex<T, D>>>(&mut self, iter: I) {
+        self.invalidate_spatial_index();
+        self.vertex_data.extend(iter);
+    }
+}
+
+impl<T, D> FromIterator<PlineVertex<T, D>> for Polyline<T, D>
+where
+    T: Real,
+{
+    /// Create an open [Polyline] from vertexes.
+    fn from_iter<I: IntoIterator<Item = PlineVertex<T, D>>>(iter: I) -> Self {
+        Polyline {
+            vertex_data: iter.into_iter().collect(),
+            is_closed: false,
+            pos_equal_eps: None,
+            spatial_index_cache: SpatialIndexCache::new(),
+        }
+    }
+}
+
+impl<T> FromIterator<(T, T, T)> for Polyline<T>
+where
+    T: Real,
+{
+    /// Create an open [Polyline] from `(x, y, bulge)` tuples.
+    fn from_iter<I: IntoIterator<Item = (T, T, T)>>(iter: I) -> Self {
+        iter.into_iter()
+            .map(|(x, y, bulge)| PlineVertex::new(x, y, bulge))
+            .collect()
+    }
+}
+
+/// Construct a [Polyline] from a closed flag and a list of `(x, y, bulge)` vertex tuples.
+///
+/// # Examples
+///
+/// ```
+/// # use cavalier_contours::*;
+/// let polyline: Polyline = polyline!(true, [(0.0, 0.0, 0.0), (2.0, 0.0, 1.0), (2.0, 2.0, 0.0)]);
+/// assert!(polyline.is_closed());
+/// assert_eq!(polyline.len(), 3);
+/// assert!(polyline[1].fuzzy_eq(PlineVertex::new(2.0, 0.0, 1.0)));
+/// ```
+#[macro_export]
+macro_rules! polyline {
+    ($is_closed:expr, [$(($x:expr, $y:expr, $bulge:expr)),* $(,)?]) => {{
+        let mut pline = $crate::Polyline::new();
+        $(
+            pline.add($x, $y, $bulge);
+        )*
+        pline.set_is_closed($is_closed);
+        pline
+    }};
+}
+
+struct PlineSegIterator<'a, T>
+where
+    T: Real,
+{
+    polyline: &'a Polyline<T>,
+    vertex_windows: Windows<'a, PlineVertex<T>>,
+    is_closed_first_pass: bool,
+}
+
+impl<'a, T> PlineSegIterator<'a, T>
+where
+    T: Real,
+{
+    fn new(polyline: &'a Polyline<T>) -> PlineSegIterator<'a, T> {
+        let vertex_windows = polyline.vertex_data.windows(2);
+        let is_closed_first_pass = if polyline.vertex_data.len() < 2 {
+            false
+        } else {
+            polyline.is_closed
+        };
+        PlineSegIterator {
+            polyline,
+            vertex_windows,
+            is_closed_first_pass,
+        }
+    }
+}
+
+impl<'a, T> Iterator for PlineSegIterator<'a, T>
+where
+    T: Real,
+{
+    type Item = (PlineVertex<T>, PlineVertex<T>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_closed_first_pass {
+            self.is_closed_first_pass = false;
+            let ln = self.polyline.vertex_data.len();
+            return Some((self.polyline[ln - 1], self.polyline[0]));
+        }
+
+        if let Some(&[v1, v2]) = self.vertex_windows.next() {
+            Some((v1, v2))
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.vertex_windows.len() + if self.is_closed_first_pass { 1 } else { 0 };
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for PlineSegIterator<'a, T>
+where
+    T: Real,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if let Some(&[v1, v2]) = self.vertex_windows.next_back() {
+            return Some((v1, v2));
+        }
+
+        // closing segment is first in the forward direction so it is last from the back
+        if self.is_closed_first_pass {
+            self.is_closed_first_pass = false;
+            let ln = self.polyline.vertex_data.len();
+            return Some((self.polyline[ln - 1], self.polyline[0]));
+        }
+
+        None
+    }
+}
+
+impl<'a, T> ExactSizeIterator for PlineSegIterator<'a, T> where T: Real {}
+
+struct PlineSegIndexIterator {
+    pos: usize,
+    remaining: usize,
+    is_closed_first_pass: bool,
+}
+
+impl PlineSegIndexIterator {
+    fn new(vertex_count: usize, is_closed: bool) -> PlineSegIndexIterator {
+        let remaining = if vertex_count < 2 {
+            0
+        } else if is_closed {
+            vertex_count
+        } else {
+            vertex_count - 1
+        };
+        PlineSegIndexIterator {
+            pos: 1,
+            remaining,
+            is_closed_first_pass: is_closed,
+        }
+    }
+}
+
+impl Iterator for PlineSegIndexIterator {
+    type Item = (usize, usize);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        self.remaining -= 1;
+
+        if self.is_closed_first_pass {
+            self.is_closed_first_pass = false;
+            return Some((self.remaining, 0));
+        }
+
+        let pos = self.pos;
+        self.pos += 1;
+        Some((pos - 1, pos))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::f64::consts::PI;
+
+    use super::*;
+    use crate::FuzzyEq;
+
+    #[test]
+    fn iter_segments() {
+        let mut polyline = Polyline::<f64>::new();
+        assert_eq!(polyline.iter_segments().size_hint(), (0, Some(0)));
+        assert_eq!(polyline.iter_segments().collect::<Vec<_>>().len(), 0);
+
+        polyline.add(1.0, 2.0, 0.3);
+        assert_eq!(polyline.iter_segments().size_hint(), (0, Some(0)));
+        assert_eq!(polyline.iter_segments().collect::<Vec<_>>().len(), 0);
+
+        polyline.add(4.0, 5.0, 0.6);
+        assert_eq!(polyline.iter_segments().size_hint(), (1, Some(1)));
+        let one_seg = polyline.iter_segments().collect::<Vec<_>>();
+        assert_eq!(one_seg.len(), 1);
+        assert_fuzzy_eq!(one_seg[0].0, PlineVertex::new(1.0, 2.0, 0.3));
+        assert_fuzzy_eq!(one_seg[0].1, PlineVertex::new(4.0, 5.0, 0.6));
+
+        polyline.set_is_closed(true);
+        assert_eq!(polyline.iter_segments().size_hint(), (2, Some(2)));
+        let two_seg = polyline.iter_segments().collect::<Vec<_>>();
+        assert_eq!(two_seg.len(), 2);
+        assert_fuzzy_eq!(two_seg[0].0, PlineVertex::new(4.0, 5.0, 0.6));
+        assert_fuzzy_eq!(two_seg[0].1, PlineVertex::new(1.0, 2.0, 0.3));
+        assert_fuzzy_eq!(two_seg[1].0, PlineVertex::new(1.0, 2.0, 0.3));
+        assert_fuzzy_eq!(two_seg[1].1, PlineVertex::new(4.0, 5.0, 0.6));
+
+        polyline.add(0.5, 0.5, 0.5);
+        assert_eq!(polyline.iter_segments().size_hint(), (3, Some(3)));
+        let three_seg = polyline.iter_segments().collect::<Vec<_>>();
+        assert_fuzzy_eq!(three_seg[0].0, PlineVertex::new(0.5, 0.5, 0.5));
+        assert_fuzzy_eq!(three_seg[0].1, PlineVertex::new(1.0, 2.0, 0.3));
+        assert_fuzzy_eq!(three_seg[1].0, PlineVertex::new(1.0, 2.0, 0.3));
+        assert_fuzzy_eq!(three_seg[1].1, PlineVertex::new(4.0, 5.0, 0.6));
+        assert_fuzzy_eq!(three_seg[2].0, PlineVertex::new(4.0, 5.0, 0.6));
+        assert_fuzzy_eq!(three_seg[2].1, PlineVertex::new(0.5, 0.5, 0.5));
+
+        polyline.set_is_closed(false);
+        assert_eq!(polyline.iter_segments().size_hint(), (2, Some(2)));
+        let two_seg_open = polyline.iter_segments().collect::<Vec<_>>();
+        assert_fuzzy_eq!(two_seg_open[0].0, PlineVertex::new(1.0, 2.0, 0.3));
+        assert_fuzzy_eq!(two_seg_open[0].1, PlineVertex::new(4.0, 5.0, 0.6));
+        assert_fuzzy_eq!(two_seg_open[1].0, PlineVertex::new(4.0, 5.0, 0.6));
+        assert_fuzzy_eq!(two_seg_open[1].1, PlineVertex::new(0.5, 0.5, 0.5));
+    }
+
+    #[test]
+    fn iter_segment_indexes() {
+        let mut polyline = Polyline::<f64>::new();
+        assert_eq!(polyline.iter_segment_indexes().size_hint(), (0, Some(0)));
+        assert_eq!(polyline.iter_segment_indexes().collect::<Vec<_>>().len(), 0);
+
+        polyline.add(1.0, 2.0, 0.3);
+        assert_eq!(polyline.iter_segment_indexes().size_hint(), (0, Some(0)));
+        assert_eq!(polyline.iter_segment_indexes().collect::<Vec<_>>().len(), 0);
+
+        polyline.add(4.0, 5.0, 0.6);
+        assert_eq!(polyline.iter_segment_indexes().size_hint(), (1, Some(1)));
+        let one_seg = polyline.iter_segment_indexes().collect::<Vec<_>>();
+        assert_eq!(one_seg, vec![(0, 1)]);
+
+        polyline.set_is_closed(true);
+        assert_eq!(polyline.iter_segment_indexes().size_hint(), (2, Some(2)));
+        let two_seg = polyline.iter_segment_indexes().collect::<Vec<_>>();
+        assert_eq!(two_seg, vec![(1, 0), (0, 1)]);
+
+        polyline.add(0.5, 0.5, 0.5);
+        assert_eq!(polyline.iter_segment_indexes().size_hint(), (3, Some(3)));
+        let three_seg = polyline.iter_segment_indexes().collect::<Vec<_>>();
+        assert_eq!(three_seg, vec![(2, 0), (0, 1), (1, 2)]);
+
+        polyline.set_is_closed(false);
+        assert_eq!(polyline.iter_segment_indexes().size_hint(), (2, Some(2)));
+        let two_seg_open = polyline.iter_segment_indexes().collect::<Vec<_>>();
+        assert_eq!(two_seg_open, vec![(0, 1), (1, 2)]);
+    }
+
+    #[test]
+    fn invert_direction() {
+        let mut polyline = Polyline::new_closed();
+        polyline.add(0.0, 0.0, 0.1);
+        polyline.add(2.0, 0.0, 0.2);
+        polyline.add(2.0, 2.0, 0.3);
+        polyline.add(0.0, 2.0, 0.4);
+
+        polyline.invert_direction();
+
+        assert_fuzzy_eq!(polyline[0], PlineVertex::new(0.0, 2.0, -0.3));
+        assert_fuzzy_eq!(polyline[1], PlineVertex::new(2.0, 2.0, -0.2));
+        assert_fuzzy_eq!(polyline[2], PlineVertex::new(2.0, 0.0, -0.1));
+        assert_fuzzy_eq!(polyline[3], PlineVertex::new(0.0, 0.0, -0.4));
+    }
+
+    #[test]
+    fn invert_direction_open() {
+        let mut polyline = Polyline::new();
+        polyline.add(0.0, 0.0, 0.5);
+        polyline.add(2.0, 0.0, -1.0);
+        polyline.add(4.0, 1.0, 0.0);
+        polyline.add(5.0, 1.0, 0.3);
+
+        let mut inverted = polyline.clone();
+        inverted.invert_direction();
+        assert!(!inverted.is_closed());
+        assert_fuzzy_eq!(inverted[0], PlineVertex::new(5.0, 1.0, 0.0));
+        assert_fuzzy_eq!(inverted[1], PlineVertex::new(4.0, 1.0, 1.0));
+        assert_fuzzy_eq!(inverted[2], PlineVertex::new(2.0, 0.0, -0.5));
+        assert_fuzzy_eq!(inverted[3], PlineVertex::new(0.0, 0.0, 0.0));
+
+        let total_length = polyline.path_length();
+        assert_fuzzy_eq!(inverted.path_length(), total_length);
+        let count = 50;
+        for i in 0..=count {
+            let distance = total_length * (i as f64) / (count as f64);
+            let p1 = polyline.sample_at_arc_length(distance).unwrap();
+            let p2 = inverted
+                .sample_at_arc_length(total_length - distance)
+                .unwrap();
+            assert_fuzzy_eq!(p1, p2);
+        }
+
+        // inverting again gives back the same geometry
+        inverted.invert_direction();
+        assert_fuzzy_eq!(inverted[0], PlineVertex::new(0.0, 0.0, 0.5));
+        assert_fuzzy_eq!(inverted[1], PlineVertex::new(2.0, 0.0, -1.0));
+        assert_fuzzy_eq!(inverted[2], PlineVertex::new(4.0, 1.0, 0.0));
+        assert_fuzzy_eq!(inverted[3], PlineVertex::new(5.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn rotate() {
+        let mut square = Polyline::new_closed();
+        square.add(0.0, 0.0, 0.0);
+        square.add(1.0, 0.0, 0.0);
+        square.add(1.0, 1.0, 0.0);
+        square.add(0.0, 1.0, 0.0);
+
+        square.rotate(0.5 * PI, Vector2::new(0.5, 0.5));
+
+        assert_fuzzy_eq!(square[0], PlineVertex::new(1.0, 0.0, 0.0));
+        assert_fuzzy_eq!(square[1], PlineVertex::new(1.0, 1.0, 0.0));
+        assert_fuzzy_eq!(square[2], PlineVertex::new(0.0, 1.0, 0.0));
+        assert_fuzzy_eq!(square[3], PlineVertex::new(0.0, 0.0, 0.0));
+        assert_fuzzy_eq!(square.area(), 1.0);
+
+        // degenerate cases should not panic
+        let mut empty = Polyline::<f64>::new();
+        empty.rotate(PI, Vector2::zero());
+        assert_eq!(empty.len(), 0);
+
+        let mut single = Polyline::new();
+        single.add(1.0, 0.0, 0.5);
+        single.rotate(PI, Vector2::zero());
+        assert_fuzzy_eq!(single[0], PlineVertex::new(-1.0, 0.0, 0.5));
+    }
+
+    #[test]
+    fn mirror() {
+        let mut half_circle = Polyline::new_closed();
+        half_circle.add(0.0, 1.0, 1.0);
+        half_circle.add(2.0, 1.0, 0.0);
+        assert_fuzzy_eq!(half_circle.area(), 0.5 * PI);
+
+        // mirror across the x axis
+        half_circle.mirror(Vector2::new(0.0, 0.0), Vector2::new(1.0, 0.0));
+
+        assert_fuzzy_eq!(half_circle[0], PlineVertex::new(0.0, -1.0, -1.0));
+        assert_fuzzy_eq!(half_circle[1], PlineVertex::new(2.0, -1.0, 0.0));
+        assert_fuzzy_eq!(half_circle.area(), -0.5 * PI);
+        let extents = half_circle.extents().unwrap();
+        assert_fuzzy_eq!(extents.min_y, -1.0);
+        assert_fuzzy_eq!(extents.max_y, 0.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn mirror_coincident_points_panics() {
+        let mut polyline = Polyline::new();
+        polyline.add(0.0, 1.0, 1.0);
+        polyline.add(2.0, 1.0, 0.0);
+        polyline.mirror(Vector2::new(1.0, 1.0), Vector2::new(1.0, 1.0));
+    }
+
+    #[test]
+    fn transform() {
+        let mut circle = Polyline::new_closed();
+        circle.add(0.0, 0.0, 1.0);
+        circle.add(2.0, 0.0, 1.0);
+
+        {
+            // pure rotation by 90 degrees
+            let mut rotated = circle.clone();
+            let (s, c) = (0.5 * PI).sin_cos();
+            assert!(rotated.transform([c, s, -s, c, 0.0, 0.0]));
+            assert_fuzzy_eq!(rotated[0], PlineVertex::new(0.0, 0.0, 1.0));
+            assert_fuzzy_eq!(rotated[1], PlineVertex::new(0.0, 2.0, 1.0));
+            assert_fuzzy_eq!(rotated.area(), PI);
+        }
+
+        {
+            // uniform scale
+            let mut scaled = circle.clone();
+            assert!(scaled.transform([3.0, 0.0, 0.0, 3.0, 0.0, 0.0]));
+            assert_fuzzy_eq!(scaled[0], PlineVertex::new(0.0, 0.0, 1.0));
+            assert_fuzzy_eq!(scaled[1], PlineVertex::new(6.0, 0.0, 1.0));
+            assert_fuzzy_eq!(scaled.area(), 9.0 * PI);
+        }
+
+        {
+            // reflection across y axis flips arc direction
+            let mut reflected = circle.clone();
+            assert!(reflected.transform([-1.0, 0.0, 0.0, 1.0, 0.0, 0.0]));
+            assert_fuzzy_eq!(reflected[0], PlineVertex::new(0.0, 0.0, -1.0));
+            assert_fuzzy_eq!(reflected[1], PlineVertex::new(-2.0, 0.0, -1.0));
+            assert_fuzzy_eq!(reflected.area(), -PI);
+        }
+
+        {
+            // shear is not a similarity transform, positions are transformed but arcs are not
+            let mut sheared = circle.clone();
+            assert!(!sheared.transform([1.0, 0.0, 1.0, 1.0, 0.0, 0.0]));
+            assert_fuzzy_eq!(sheared[0], PlineVertex::new(0.0, 0.0, 1.0));
+            assert_fuzzy_eq!(sheared[1], PlineVertex::new(2.0, 0.0, 1.0));
+        }
+
+        {
+            // shear applied after converting arcs to lines transforms the geometry accurately
+            let mut sheared = circle.arcs_to_approx_lines(1e-3).unwrap();
+            let expected_area = sheared.area();
+            assert!(!sheared.transform([1.0, 0.0, 1.0, 1.0, 0.0, 0.0]));
+            assert!(sheared.iter().all(|v| v.bulge_is_zero()));
+            // shear preserves area
+            assert_fuzzy_eq!(sheared.area(), expected_area);
+        }
+    }
+
+    #[test]
+    fn scale_xy() {
+        let mut rectangle = Polyline::new_closed();
+        rectangle.add(0.0, 0.0, 0.0);
+        rectangle.add(2.0, 0.0, 0.0);
+        rectangle.add(2.0, 1.0, 0.0);
+        rectangle.add(0.0, 1.0, 0.0);
+
+        assert_eq!(rectangle.scale_xy(0.5, 3.0), Ok(()));
+        assert_fuzzy_eq!(rectangle[2], PlineVertex::new(1.0, 3.0, 0.0));
+        assert_fuzzy_eq!(rectangle.area(), 3.0);
+
+        let mut circle = Polyline::new_closed();
+        circle.add(0.0, 0.0, 1.0);
+        circle.add(2.0, 0.0, 1.0);
+
+        // non-uniform scale of arcs is rejected and leaves polyline unchanged
+        let original = circle.clone();
+        assert_eq!(
+            circle.scale_xy(2.0, 1.0),
+            Err(PlineError::NonUniformScaleWithArcs)
+        );
+        assert!(circle.fuzzy_eq(&original));
+
+        // equal scale factors behave the same as scale
+        let mut expected = circle.clone();
+        expected.scale(2.0);
+        assert_eq!(circle.scale_xy(2.0, 2.0), Ok(()));
+        assert!(circle.fuzzy_eq(&expected));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let mut polyline = Polyline::new_closed();
+        polyline.add(0.0, 0.0, 0.5);
+        polyline.add(2.0, 0.0, -1.0);
+        polyline.add(2.0, 2.0, 0.0);
+
+        let json = serde_json::to_string(&polyline).unwrap();
+        assert!(json.contains(r#""is_closed":true"#));
+        assert!(json.contains(r#"{"x":2.0,"y":0.0,"bulge":-1.0}"#));
+
+        let deserialized: Polyline = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.len(), polyline.len());
+        assert_eq!(deserialized.is_closed(), polyline.is_closed());
+        assert!(deserialized.fuzzy_eq(&polyline));
+
+        let v: Vector2 = serde_json::from_str(r#"{"x":1.0,"y":2.0}"#).unwrap();
+        assert_fuzzy_eq!(v, Vector2::new(1.0, 2.0));
+    }
+
+    #[test]
+    fn area() {
+        {
+            let mut circle = Polyline::new_closed();
+            circle.add(0.0, 0.0, 1.0);
+            circle.add(2.0, 0.0, 1.0);
+            assert_fuzzy_eq!(circle.area(), PI);
+            circle.invert_direction();
+            assert_fuzzy_eq!(circle.area(), -PI);
+        }
+
+        {
+            let mut half_circle = Polyline::new_closed();
+            half_circle.add(0.0, 0.0, -1.0);
+            half_circle.add(2.0, 0.0, 0.0);
+            assert_fuzzy_eq!(half_circle.area(), -0.5 * PI);
+            half_circle.invert_direction();
+            assert_fuzzy_eq!(half_circle.area(), 0.5 * PI);
+        }
+
+        {
+            let mut square = Polyline::new_closed();
+            square.add(0.0, 0.0, 0.0);
+            square.add(2.0, 0.0, 0.0);
+            square.add(2.0, 2.0, 0.0);
+            square.add(0.0, 2.0, 0.0);
+            assert_fuzzy_eq!(square.area(), 4.0);
+            square.invert_direction();
+            assert_fuzzy_eq!(square.area(), -4.0);
+        }
+
+        {
+            let mut open_polyline = Polyline::new();
+            open_polyline.add(0.0, 0.0, 0.0);
+            open_polyline.add(2.0, 0.0, 0.0);
+            open_polyline.add(2.0, 2.0, 0.0);
+            open_polyline.add(0.0, 2.0, 0.0);
+            assert_fuzzy_eq!(open_polyline.area(), 0.0);
+            open_polyline.invert_direction();
+            assert_fuzzy_eq!(open_polyline.area(), 0.0);
+        }
+
+        {
+            let empty_open_polyline = Polyline::<f64>::new();
+            assert_fuzzy_eq!(empty_open_polyline.area(), 0.0);
+        }
+
+        {
+            let empty_closed_polyline = Polyline::<f64>::new_closed();
+            assert_fuzzy_eq!(empty_closed_polyline.area(), 0.0);
+        }
+
+        {
+            let mut one_vertex_open_polyline = Polyline::<f64>::new();
+            one_vertex_open_polyline.add(1.0, 1.0, 0.0);
+            assert_fuzzy_eq!(one_vertex_open_polyline.area(), 0.0);
+        }
+
+        {
+            let mut one_vertex_closed_polyline = Polyline::<f64>::new_closed();
+            one_vertex_closed_polyline.add(1.0, 1.0, 0.0);
+            assert_fuzzy_eq!(one_vertex_closed_polyline.area(), 0.0);
+        }
+    }
+
+    #[test]
+    fn winding_number() {
+        let mut circle = Polyline::new_closed();
+        circle.add(0.0, 0.0, 1.0);
+        circle.add(2.0, 0.0, 1.0);
+        // points off the arc chords (inside each half of the circle)
+        assert_eq!(circle.winding_number(Vector2::new(1.0, -0.5)), 1);
+        assert_eq!(circle.winding_number(Vector2::new(1.0, 0.5)), 1);
+        assert_eq!(circle.winding_number(Vector2::new(1.0, -1.5)), 0);
+        circle.invert_direction();
+        assert_eq!(circle.winding_number(Vector2::new(1.0, -0.5)), -1);
+        assert_eq!(circle.winding_number(Vector2::new(1.0, 0.5)), -1);
+        assert_eq!(circle.winding_number(Vector2::new(1.0, 1.5)), 0);
+    }
+
+    #[test]
+    fn centroid() {
+        {
+            let mut circle = Polyline::new_closed();
+            circle.add(0.0, 0.0, 1.0);
+            circle.add(2.0, 0.0, 1.0);
+            let c = circle.centroid().unwrap();
+            assert_fuzzy_eq!(c.x, 1.0);
+            assert_fuzzy_eq!(c.y, 0.0);
+            circle.invert_direction();
+            let c = circle.centroid().unwrap();
+            assert_fuzzy_eq!(c.x, 1.0);
+            assert_fuzzy_eq!(c.y, 0.0);
+        }
+
+        {
+            // half circle below the x axis
+            let mut half_circle = Polyline::new_closed();
+            half_circle.add(0.0, 0.0, 1.0);
+            half_circle.add(2.0, 0.0, 0.0);
+            let c = half_circle.centroid().unwrap();
+            assert_fuzzy_eq!(c.x, 1.0);
+            assert_fuzzy_eq!(c.y, -4.0 / (3.0 * PI));
+        }
+
+        {
+            let mut l_shape = Polyline::new_closed();
+            l_shape.add(0.0, 0.0, 0.0);
+            l_shape.add(2.0, 0.0, 0.0);
+            l_shape.add(2.0, 1.0, 0.0);
+            l_shape.add(1.0, 1.0, 0.0);
+            l_shape.add(1.0, 2.0, 0.0);
+            l_shape.add(0.0, 2.0, 0.0);
+            let c = l_shape.centroid().unwrap();
+            assert_fuzzy_eq!(c.x, 5.0 / 6.0);
+            assert_fuzzy_eq!(c.y, 5.0 / 6.0);
+        }
+
+        {
+            let mut open_polyline = Polyline::new();
+            open_polyline.add(0.0, 0.0, 0.0);
+            open_polyline.add(2.0, 0.0, 0.0);
+            open_polyline.add(2.0, 2.0, 0.0);
+            assert!(open_polyline.centroid().is_none());
+            assert!(Polyline::<f64>::new_closed().centroid().is_none());
+        }
+    }
+
+    #[test]
+    fn find_self_intersects() {
+        {
+            // figure eight
+            let mut polyline = Polyline::new_closed();
+            polyline.add(0.0, 0.0, 0.0);
+            polyline.add(2.0, 2.0, 0.0);
+            polyline.add(2.0, 0.0, 0.0);
+            polyline.add(0.0, 2.0, 0.0);
+            assert!(polyline.self_intersects(None));
+            let intrs = polyline.find_self_intersects();
+            assert_eq!(intrs.len(), 1);
+            let (i1, i2) = (intrs[0].start_index1, intrs[0].start_index2);
+            assert_eq!((i1.min(i2), i1.max(i2)), (0, 2));
+            assert_fuzzy_eq!(intrs[0].point, Vector2::new(1.0, 1.0));
+        }
+
+        {
+            // arc crossed by the line segment that follows it
+            let mut polyline = Polyline::new();
+            polyline.add(0.0, 0.0, 1.0);
+            polyline.add(2.0, 0.0, 0.0);
+            polyline.add(1.0, -2.0, 0.0);
+            let index = polyline.create_approx_spatial_index().unwrap();
+            assert!(polyline.self_intersects(Some(&index)));
+            let intrs = polyline.find_self_intersects();
+            assert_eq!(intrs.len(), 1);
+            assert_fuzzy_eq!(intrs[0].point, Vector2::new(1.6, -0.8));
+        }
+
+        {
+            // two arcs crossing
+            let mut polyline = Polyline::new();
+            polyline.add(0.0, 0.0, 1.0);
+            polyline.add(2.0, 0.0, 0.0);
+            polyline.add(2.0, -1.5, 1.0);
+            polyline.add(0.0, -1.5, 0.0);
+            assert!(polyline.self_intersects(None));
+            let intrs = polyline.find_self_intersects();
+            assert_eq!(intrs.len(), 2);
+            for intr in &intrs {
+                assert_fuzzy_eq!(intr.point.y, -0.75);
+            }
+        }
+
+        {
+            // circle has no self intersects
+            let mut polyline = Polyline::new_closed();
+            polyline.add(0.0, 0.0, 1.0);
+            polyline.add(2.0, 0.0, 1.0);
+            assert!(!polyline.self_intersects(None));
+            assert!(polyline.find_self_intersects().is_empty());
+            assert!(!Polyline::<f64>::new().self_intersects(None));
+        }
+    }
+
+    #[test]
+    fn sample_at_arc_length() {
+        let mut half_circle = Polyline::new();
+        half_circle.add(0.0, 0.0, 1.0);
+        half_circle.add(2.0, 0.0, 0.0);
+        // midpoint lies on the arc (not the chord)
+        let midpoint = half_circle.sample_at_arc_length(PI / 2.0).unwrap();
+        assert_fuzzy_eq!(midpoint, Vector2::new(1.0, -1.0));
+        assert_fuzzy_eq!((midpoint - Vector2::new(1.0, 0.0)).length(), 1.0);
+        assert_fuzzy_eq!(
+            half_circle.sample_at_arc_length(-1.0).unwrap(),
+            Vector2::new(0.0, 0.0)
+        );
+
+        // clockwise arc
+        half_circle.invert_direction();
+        assert_fuzzy_eq!(
+            half_circle.sample_at_arc_length(PI / 2.0).unwrap(),
+            Vector2::new(1.0, -1.0)
+        );
+
+        // closed polyline wraps around
+        let mut circle = Polyline::new_closed();
+        circle.add(0.0, 0.0, 1.0);
+        circle.add(2.0, 0.0, 1.0);
+        assert_fuzzy_eq!(
+            circle.sample_at_arc_length(1.5 * PI).unwrap(),
+            Vector2::new(1.0, 1.0)
+        );
+        assert_fuzzy_eq!(
+            circle.sample_at_arc_length(3.5 * PI).unwrap(),
+            Vector2::new(1.0, 1.0)
+        );
+        assert_fuzzy_eq!(
+            circle.sample_at_arc_length(-0.5 * PI).unwrap(),
+            Vector2::new(1.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn sample_uniform() {
+        let mut circle = Polyline::new_closed();
+        circle.add(0.0, 0.0, 1.0);
+        circle.add(2.0, 0.0, 1.0);
+        let points = circle.sample_uniform(PI / 4.0);
+        assert_eq!(points.len(), 8);
+        for (i, point) in points.iter().enumerate() {
+            assert_fuzzy_eq!((*point - Vector2::new(1.0, 0.0)).length(), 1.0);
+            assert_fuzzy_eq!(
+                *point,
+                circle.sample_at_arc_length(i as f64 * PI / 4.0).unwrap()
+            );
+        }
+
+        assert!(circle.sample_uniform(0.0).is_empty());
+        assert!(Polyline::<f64>::new().sample_uniform(1.0).is_empty());
+    }
+
+    #[test]
+    fn tangent_at_arc_length() {
+        // counter clockwise half circle going over the top from (2, 0) to (0, 0)
+        let mut half_circle = Polyline::new();
+        half_circle.add(2.0, 0.0, 1.0);
+        half_circle.add(0.0, 0.0, 0.0);
+        let top_tangent = half_circle.tangent_at_arc_length(PI / 2.0).unwrap();
+        assert_fuzzy_eq!(top_tangent, Vector2::new(-1.0, 0.0));
+        assert_fuzzy_eq!(
+            half_circle.tangent_at_arc_length(0.0).unwrap(),
+            Vector2::new(0.0, 1.0)
+        );
+        assert_fuzzy_eq!(
+            half_circle.tangent_at_arc_length(PI).unwrap(),
+            Vector2::new(0.0, -1.0)
+        );
+
+        // clockwise direction reverses tangent
+        half_circle.invert_direction();
+        assert_fuzzy_eq!(
+            half_circle.tangent_at_arc_length(PI / 2.0).unwrap(),
+            Vector2::new(1.0, 0.0)
+        );
+
+        // vertex uses segment starting at the vertex
+        let mut polyline = Polyline::new();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(1.0, 0.0, 0.0);
+        polyline.add(1.0, 1.0, 0.0);
+        assert_fuzzy_eq!(
+            polyline.tangent_at_arc_length(1.0).unwrap(),
+            Vector2::new(0.0, 1.0)
+        );
+        assert!(Polyline::<f64>::new().tangent_at_arc_length(0.0).is_none());
+    }
+
+    #[test]
+    fn curvature_at_arc_length() {
+        let radius = 2.0;
+        let mut circle = Polyline::new_closed();
+        circle.add(0.0, 0.0, 1.0);
+        circle.add(2.0 * radius, 0.0, 1.0);
+        let circumference = 2.0 * PI * radius;
+        for k in 0..=16 {
+            let distance = circumference * (k as f64) / 16.0;
+            assert_fuzzy_eq!(
+                circle.curvature_at_arc_length(distance).unwrap(),
+                1.0 / radius
+            );
+        }
+
+        circle.invert_direction();
+        assert_fuzzy_eq!(circle.curvature_at_arc_length(1.0).unwrap(), -1.0 / radius);
+
+        // line segment followed by arc, vertex belongs to the segment starting at it
+        let mut polyline = Polyline::new();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(1.0, 0.0, 1.0);
+        polyline.add(3.0, 0.0, 0.0);
+        assert_fuzzy_eq!(polyline.curvature_at_arc_length(0.5).unwrap(), 0.0);
+        assert_fuzzy_eq!(polyline.curvature_at_arc_length(1.0).unwrap(), 1.0);
+        assert_fuzzy_eq!(polyline.curvature_at_arc_length(100.0).unwrap(), 1.0);
+        assert!(Polyline::<f64>::new()
+            .curvature_at_arc_length(0.0)
+            .is_none());
+    }
+
+    #[test]
+    fn min_area_bounding_rect() {
+        // thin 10 x 1 rectangle rotated 30 degrees
+        let mut polyline = Polyline::new_closed();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(10.0, 0.0, 0.0);
+        polyline.add(10.0, 1.0, 0.0);
+        polyline.add(0.0, 1.0, 0.0);
+        polyline.rotate(PI / 6.0, Vector2::new(0.0, 0.0));
+
+        let extents = polyline.extents().unwrap();
+        let extents_area = (extents.max_x - extents.min_x) * (extents.max_y - extents.min_y);
+        let (rect, angle) = polyline.min_area_bounding_rect().unwrap();
+        assert_eq!(rect.len(), 4);
+        assert!(rect.is_closed());
+        assert_fuzzy_eq!(rect.area(), 10.0);
+        assert!(rect.area() < extents_area);
+        assert_fuzzy_eq!(angle, PI / 6.0);
+        for v in polyline.iter() {
+            assert!(
+                rect.winding_number(v.pos()) != 0
+                    || rect.closest_point(v.pos()).unwrap().distance < 1e-5
+            );
+        }
+
+        // arcs are accounted for, circle bounding rect is the square around it
+        let mut circle = Polyline::new_closed();
+        circle.add(0.0, 0.0, 1.0);
+        circle.add(2.0, 0.0, 1.0);
+        let (rect, _) = circle.min_area_bounding_rect().unwrap();
+        assert!(rect.area() >= 4.0 - 1e-5);
+        assert!(rect.area() < 4.0 + 1e-2);
+
+        // calipers find the same minimum as checking every hull edge orientation
+        let points = vec![
+            Vector2::new(0.0, 0.0),
+            Vector2::new(7.0, 1.0),
+            Vector2::new(9.0, 4.0),
+            Vector2::new(8.0, 8.0),
+            Vector2::new(3.0, 9.0),
+            Vector2::new(-1.0, 5.0),
+            Vector2::new(4.0, 4.0),
+        ];
+        let hull = convex_hull(points);
+        let rect_area = |edge_angle: f64| {
+            let (sin, cos) = edge_angle.sin_cos();
+            let along = hull.iter().map(|p| p.x * cos + p.y * sin);
+            let across = hull.iter().map(|p| -p.x * sin + p.y * cos);
+            let span = |values: Vec<f64>| {
+                values.iter().cloned().fold(f64::MIN, f64::max)
+                    - values.iter().cloned().fold(f64::MAX, f64::min)
+            };
+            span(along.collect()) * span(across.collect())
+        };
+        let brute_force_min = (0..hull.len())
+            .map(|i| rect_area(crate::core_math::angle(hull[i], hull[(i + 1) % hull.len()])))
+            .fold(f64::MAX, f64::min);
+        let edge_angle = min_area_rect_edge_angle(&hull).unwrap();
+        assert_fuzzy_eq!(rect_area(edge_angle), brute_force_min);
+
+        assert!(Polyline::<f64>::new().min_area_bounding_rect().is_none());
+    }
+
+    #[test]
+    fn offset_rings() {
+        let mut square = Polyline::new_closed();
+        square.add(0.0, 0.0, 0.0);
+        square.add(10.0, 0.0, 0.0);
+        square.add(10.0, 10.0, 0.0);
+        square.add(0.0, 10.0, 0.0);
+
+        let rings = square.offset_rings(1.0, None);
+        assert_eq!(rings.len(), 4);
+        for (i, generation) in rings.iter().enumerate() {
+            assert_eq!(generation.len(), 1);
+            let ring = &generation[0];
+            let size = 10.0 - 2.0 * (i + 1) as f64;
+            assert!(ring.is_closed());
+            assert_fuzzy_eq!(ring.area(), size * size);
+            let extents = ring.extents().unwrap();
+            assert_fuzzy_eq!(extents.min_x, (i + 1) as f64);
+            assert_fuzzy_eq!(extents.max_x, 10.0 - (i + 1) as f64);
+        }
+
+        // clockwise offsets inward as well
+        square.invert_direction();
+        let rings = square.offset_rings(1.0, Some(2));
+        assert_eq!(rings.len(), 2);
+        assert_fuzzy_eq!(rings[1][0].area(), -36.0);
+
+        assert!(square.offset_rings(0.0, None).is_empty());
+        square.set_is_closed(false);
+        assert!(square.offset_rings(1.0, None).is_empty());
+    }
+
+    #[test]
+    fn set_start_vertex() {
+        let mut square = Polyline::new_closed();
+        square.add(0.0, 0.0, 0.0);
+        square.add(2.0, 0.0, 1.0);
+        square.add(2.0, 2.0, 0.0);
+        square.add(0.0, 2.0, 0.0);
+        let original = square.clone();
+
+        square.set_start_vertex(2);
+        assert_eq!(square.len(), 4);
+        assert!(square[0].fuzzy_eq(original[2]));
+        assert!(square[1].fuzzy_eq(original[3]));
+        assert!(square[2].fuzzy_eq(original[0]));
+        assert!(square[3].fuzzy_eq(original[1]));
+        assert_fuzzy_eq!(square.area(), original.area());
+        assert_fuzzy_eq!(square.path_length(), original.path_length());
+        let extents = square.extents().unwrap();
+        let original_extents = original.extents().unwrap();
+        assert_fuzzy_eq!(extents.max_x, original_extents.max_x);
+        assert_fuzzy_eq!(extents.min_y, original_extents.min_y);
+
+        square.set_start_vertex(0);
+        assert!(square[0].fuzzy_eq(original[2]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_start_vertex_open_panics() {
+        let mut polyline = Polyline::new();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(1.0, 0.0, 0.0);
+        polyline.set_start_vertex(1);
+    }
+
+    #[test]
+    fn contains_point() {
+        // square with a half circle arc bulging out on the right side
+        let mut polyline = Polyline::new_closed();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(2.0, 0.0, 1.0);
+        polyline.add(2.0, 2.0, 0.0);
+        polyline.add(0.0, 2.0, 0.0);
+
+        let eps = 1e-5;
+        let inside = Vector2::new(2.5, 1.0);
+        assert!(polyline.contains_point(inside, OnBoundary::Inside, eps));
+        assert!(polyline.contains_point(inside, OnBoundary::Outside, eps));
+
+        let outside = Vector2::new(3.5, 1.0);
+        assert!(!polyline.contains_point(outside, OnBoundary::Inside, eps));
+        assert!(!polyline.contains_point(outside, OnBoundary::Outside, eps));
+
+        // exactly on the arc
+        let on_arc = Vector2::new(3.0, 1.0);
+        assert!(polyline.contains_point(on_arc, OnBoundary::Inside, eps));
+        assert!(!polyline.contains_point(on_arc, OnBoundary::Outside, eps));
+
+        // just outside the arc is only on the boundary with a larger eps
+        let near_arc = Vector2::new(3.001, 1.0);
+        assert!(!polyline.contains_point(near_arc, OnBoundary::Inside, eps));
+        assert!(polyline.contains_point(near_arc, OnBoundary::Inside, 1e-2));
+
+        // clockwise direction gives same result
+        polyline.invert_direction();
+        assert!(polyline.contains_point(inside, OnBoundary::Outside, eps));
+        assert!(!polyline.contains_point(outside, OnBoundary::Inside, eps));
+
+        polyline.set_is_closed(false);
+        assert!(!polyline.contains_point(inside, OnBoundary::Inside, eps));
+    }
+
+    #[test]
+    fn remove_redundant() {
+        let mut polyline = Polyline::new();
+        for i in 0..5 {
+            polyline.add(i as f64, 2.0 * i as f64, 0.0);
+        }
+        assert_eq!(polyline.remove_redundant(1e-5), 3);
+        assert_eq!(polyline.len(), 2);
+        assert!(polyline[0].fuzzy_eq(PlineVertex::new(0.0, 0.0, 0.0)));
+        assert!(polyline[1].fuzzy_eq(PlineVertex::new(4.0, 8.0, 0.0)));
+        assert_eq!(polyline.remove_redundant(1e-5), 0);
+
+        // arc segments are not merged
+        let mut polyline = Polyline::new();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(1.0, 0.0, 0.5);
+        polyline.add(2.0, 0.0, 0.0);
+        polyline.add(3.0, 0.0, 0.0);
+        polyline.add(4.0, 0.0, 0.0);
+        assert_eq!(polyline.remove_redundant(1e-5), 1);
+        assert_eq!(polyline.len(), 4);
+        assert!(polyline[3].fuzzy_eq(PlineVertex::new(4.0, 0.0, 0.0)));
+
+        // turning back on itself is not redundant
+        let mut polyline = Polyline::new();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(2.0, 0.0, 0.0);
+        polyline.add(1.0, 0.0, 0.0);
+        assert_eq!(polyline.remove_redundant(1e-5), 0);
+
+        // closed polyline wraps around at index 0
+        let mut square = Polyline::new_closed();
+        square.add(1.0, 0.0, 0.0);
+        square.add(2.0, 0.0, 0.0);
+        square.add(2.0, 1.0, 0.0);
+        square.add(2.0, 2.0, 0.0);
+        square.add(0.0, 2.0, 0.0);
+        square.add(0.0, 0.0, 0.0);
+        let area = square.area();
+        assert_eq!(square.remove_redundant(1e-5), 2);
+        assert_eq!(square.len(), 4);
+        assert!(square[0].fuzzy_eq(PlineVertex::new(2.0, 0.0, 0.0)));
+        assert_fuzzy_eq!(square.area(), area);
+    }
+
+    #[test]
+    fn simplify() {
+        // noisy near straight line
+        let mut polyline = Polyline::new();
+        for i in 0..100 {
+            let x = i as f64 * 0.1;
+            polyline.add(x, 0.01 * (7.0 * x).sin(), 0.0);
+        }
+        let simplified = polyline.simplify(0.05);
+        assert_eq!(simplified.len(), 2);
+        assert!(simplified[0].fuzzy_eq(polyline[0]));
+        assert!(simplified[1].fuzzy_eq(polyline[99]));
+        // small tolerance keeps the noise
+        assert!(polyline.simplify(0.001).len() > 10);
+
+        // arc vertexes are kept
+        let mut polyline = Polyline::new();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(1.0, 0.001, 0.0);
+        polyline.add(2.0, 0.0, 0.5);
+        polyline.add(4.0, 0.0, 0.0);
+        polyline.add(5.0, 0.001, 0.0);
+        polyline.add(6.0, 0.0, 0.0);
+        let simplified = polyline.simplify(0.1);
+        assert_eq!(simplified.len(), 4);
+        assert!(simplified[1].fuzzy_eq(PlineVertex::new(2.0, 0.0, 0.5)));
+        assert!(simplified[2].fuzzy_eq(PlineVertex::new(4.0, 0.0, 0.0)));
+
+        // closed polyline stays a valid closed polyline
+        let mut square = Polyline::new_closed();
+        square.add(0.0, 0.0, 0.0);
+        square.add(1.0, 0.01, 0.0);
+        square.add(2.0, 0.0, 0.0);
+        square.add(2.0, 2.0, 0.0);
+        square.add(0.0, 2.0, 0.0);
+        let simplified = square.simplify(0.1);
+        assert!(simplified.is_closed());
+        assert_eq!(simplified.len(), 4);
+        assert_fuzzy_eq!(simplified.area(), 4.0);
+        let simplified = square.simplify(100.0);
+        assert!(simplified.is_closed());
+        assert_eq!(simplified.len(), 3);
+    }
+
+    #[test]
+    fn arcs_to_approx_lines_capped() {
+        let mut polyline = Polyline::new();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(10.0, 0.0, 1.0);
+        polyline.add(20.0, 0.0, -0.5);
+        polyline.add(30.0, 0.0, 0.0);
+
+        // arcs would need many more segments to meet the error distance
+        let uncapped = polyline.arcs_to_approx_lines(1e-6).unwrap();
+        assert!(uncapped.len() > 100);
+
+        let capped = polyline.arcs_to_approx_lines_capped(1e-6, 4).unwrap();
+        assert_eq!(capped.len(), 1 + 4 + 4 + 1);
+        assert!(capped.iter().all(|v| v.bulge_is_zero()));
+        // arc end points are kept
+        assert!(capped[1].pos().fuzzy_eq(Vector2::new(10.0, 0.0)));
+        assert!(capped[5].pos().fuzzy_eq(Vector2::new(20.0, 0.0)));
+        assert!(capped[9].pos().fuzzy_eq(Vector2::new(30.0, 0.0)));
+
+        // error distance used when it yields fewer segments
+        let coarse = polyline.arcs_to_approx_lines(1.0).unwrap();
+        let coarse_capped = polyline.arcs_to_approx_lines_capped(1.0, 100).unwrap();
+        assert_eq!(coarse.len(), coarse_capped.len());
+        assert!(coarse.fuzzy_eq(&coarse_capped));
+
+        // never less than one segment per arc
+        let min_capped = polyline.arcs_to_approx_lines_capped(1e-6, 0).unwrap();
+        assert_eq!(min_capped.len(), 4);
+    }
+
+    #[test]
+    fn arcs_to_approx_lines_arc_sweep_greater_than_half_circle() {
+        // bulge of 3 sweeps 4 * atan(3) (~286 degrees), arc passes through (1, -3)
+        let mut polyline = Polyline::new_closed();
+        polyline.add(0.0, 0.0, 3.0);
+        polyline.add(2.0, 0.0, 0.0);
+        let (radius, center) = seg_arc_radius_and_center(polyline[0], polyline[1]);
+
+        let error_distance = 1e-3;
+        let approx = polyline.arcs_to_approx_lines(error_distance).unwrap();
+        assert!(approx.len() > 10);
+        assert!(approx.iter().all(|v| v.bulge_is_zero()));
+        // all vertexes (including the line segment end points) lie on the arc circle
+        for v in approx.iter() {
+            assert_fuzzy_eq!((v.pos() - center).length(), radius);
+        }
+
+        // whole arc is followed (not the short way around)
+        let min_y = approx.iter().fold(0.0f64, |acc, v| acc.min(v.y));
+        assert!((min_y + 3.0).abs() < error_distance);
+        assert!((approx.area() - polyline.area()).abs() < 0.01);
+    }
+
+    #[test]
+    fn arcs_to_approx_lines_sweep_from_bulge() {
+        // arc sweeping more than a half circle, the previous sweep angle computed from the arc end
+        // point angles (delta_angle) went the short way around the circle
+        let mut polyline: Polyline = Polyline::new();
+        polyline.add(0.0, 0.0, 3.0);
+        polyline.add(2.0, 0.0, 0.0);
+        let (radius, center) = seg_arc_radius_and_center(polyline[0], polyline[1]);
+        let sweep = angle_from_bulge(3.0);
+        let end_points_delta = delta_angle(
+            angle(center, polyline[0].pos()),
+            angle(center, polyline[1].pos()),
+        );
+        assert!(end_points_delta.abs() < PI);
+        assert!(sweep > PI);
+        assert_fuzzy_eq!(sweep + end_points_delta.abs(), 2.0 * PI);
+
+        for approx in [
+            polyline.arcs_to_approx_lines(1e-3).unwrap(),
+            polyline.arcs_to_approx_lines_by_chord(0.05),
+        ] {
+            assert_fuzzy_eq!(approx[0].pos(), polyline[0].pos());
+            assert_fuzzy_eq!(approx.last().unwrap().pos(), polyline[1].pos());
+            // approximated length is close to the full arc length (not the short way around)
+            assert!((approx.path_length() - radius * sweep).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn flatten_to_points() {
+        let radius = 2.0;
+        let center = Vector2::new(3.0, 1.0);
+        let mut circle = Polyline::new_closed();
+        circle.add(center.x - radius, center.y, 1.0);
+        circle.add(center.x + radius, center.y, 1.0);
+
+        let error_distance = 0.01;
+        let points = circle.flatten_to_points(error_distance);
+        let approx = circle.arcs_to_approx_lines(error_distance).unwrap();
+        assert_eq!(points.len(), approx.len() + 1);
+        assert!(points.len() > 10);
+        assert!(points[0].fuzzy_eq(points[points.len() - 1]));
+        for p in &points {
+            assert_fuzzy_eq!((p - center).length(), radius);
+        }
+        // all segment midpoints are within error distance of the circle
+        for w in points.windows(2) {
+            let midpoint = (w[0] + w[1]).scale(0.5);
+            assert!(radius - (midpoint - center).length() <= error_distance + 1e-8);
+        }
+
+        let mut open = Polyline::new();
+        open.add(0.0, 0.0, 0.0);
+        open.add(1.0, 0.0, 0.0);
+        let points = open.flatten_to_points(error_distance);
+        assert_eq!(points.len(), 2);
+        assert!(Polyline::<f64>::new().flatten_to_points(0.1).is_empty());
+    }
+
+    #[test]
+    fn from_iter_and_macro() {
+        let mut manual = Polyline::new();
+        manual.add(0.0, 0.0, 0.0);
+        manual.add(1.0, 0.0, 0.0);
+
+        let from_tuples = Polyline::from_iter([(0.0, 0.0, 0.0), (1.0, 0.0, 0.0)]);
+        assert!(!from_tuples.is_closed());
+        assert_eq!(from_tuples.len(), manual.len());
+        assert!(from_tuples.fuzzy_eq(&manual));
+
+        let from_vertexes: Polyline = manual.iter().copied().collect();
+        assert!(!from_vertexes.is_closed());
+        assert_eq!(from_vertexes.len(), manual.len());
+        assert!(from_vertexes.fuzzy_eq(&manual));
+
+        let from_macro: Polyline = polyline!(false, [(0.0, 0.0, 0.0), (1.0, 0.0, 0.0),]);
+        assert!(!from_macro.is_closed());
+        assert_eq!(from_macro.len(), manual.len());
+        assert!(from_macro.fuzzy_eq(&manual));
+
+        let closed: Polyline = polyline!(true, [(0.0, 0.0, 1.0), (2.0, 0.0, 1.0)]);
+        assert!(closed.is_closed());
+        assert_fuzzy_eq!(closed.area(), PI);
+
+        let empty: Polyline = polyline!(true, []);
+        assert!(empty.is_closed());
+        assert_eq!(empty.len(), 0);
+    }
+
+    #[test]
+    fn default_and_extend() {
+        let mut polyline: Polyline = Polyline::default();
+        assert!(!polyline.is_closed());
+        assert_eq!(polyline.len(), 0);
+
+        polyline.extend(vec![
+            PlineVertex::new(0.0, 0.0, 0.0),
+            PlineVertex::new(1.0, 0.0, 1.0),
+        ]);
+        polyline.extend(Some(PlineVertex::new(1.0, 1.0, 0.0)));
+        assert_eq!(polyline.len(), 3);
+        assert!(polyline[1].fuzzy_eq(PlineVertex::new(1.0, 0.0, 1.0)));
+        assert!(polyline[2].fuzzy_eq(PlineVertex::new(1.0, 1.0, 0.0)));
+
+        #[derive(Default)]
+        struct Contains {
+            polyline: Polyline,
+        }
+        assert_eq!(Contains::default().polyline.len(), 0);
+    }
+
+    #[test]
+    fn iter_segments_rev() {
+        let mut polyline = Polyline::new();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(1.0, 0.0, 0.5);
+        polyline.add(1.0, 1.0, 0.0);
+        polyline.add(0.0, 1.0, -0.5);
+
+        let assert_rev_matches = |polyline: &Polyline| {
+            let mut forward: Vec<_> = polyline.iter_segments().collect();
+            forward.reverse();
+            let reverse: Vec<_> = polyline.iter_segments_rev().collect();
+            assert_eq!(forward.len(), reverse.len());
+            for ((f1, f2), (r1, r2)) in forward.iter().zip(reverse.iter()) {
+                assert!(f1.fuzzy_eq(*r1));
+                assert!(f2.fuzzy_eq(*r2));
+            }
+        };
+
+        assert_rev_matches(&polyline);
+        assert_eq!(polyline.iter_segments_rev().count(), 3);
+        let (first_start, first_end) = polyline.iter_segments_rev().next().unwrap();
+        assert!(first_start.fuzzy_eq(polyline[2]));
+        assert!(first_end.fuzzy_eq(polyline[3]));
+
+        polyline.set_is_closed(true);
+        assert_rev_matches(&polyline);
+        assert_eq!(polyline.iter_segments_rev().count(), 4);
+        let (last_start, last_end) = polyline.iter_segments_rev().last().unwrap();
+        assert!(last_start.fuzzy_eq(polyline[3]));
+        assert!(last_end.fuzzy_eq(polyline[0]));
+
+        let mut single = Polyline::new_closed();
+        single.add(0.0, 0.0, 0.0);
+        assert_eq!(single.iter_segments_rev().count(), 0);
+    }
+
+    #[test]
+    fn iter_segments_double_ended() {
+        let mut polyline = Polyline::new_closed();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(1.0, 0.0, 0.5);
+        polyline.add(1.0, 1.0, 0.0);
+        polyline.add(0.0, 1.0, -0.5);
+
+        let seg_eq = |seg: Option<(PlineVertex, PlineVertex)>, i: usize, j: usize| {
+            let (v1, v2) = seg.unwrap();
+            v1.fuzzy_eq(polyline[i]) && v2.fuzzy_eq(polyline[j])
+        };
+
+        let mut iter = polyline.iter_segments();
+        assert_eq!(iter.len(), 4);
+        assert!(seg_eq(iter.next_back(), 2, 3));
+        assert_eq!(iter.len(), 3);
+        assert!(seg_eq(iter.next(), 3, 0));
+        assert_eq!(iter.len(), 2);
+        assert!(seg_eq(iter.next_back(), 1, 2));
+        assert!(seg_eq(iter.next(), 0, 1));
+        assert_eq!(iter.len(), 0);
+        assert!(iter.next().is_none());
+        assert!(iter.next_back().is_none());
+
+        // closing segment reached from the back
+        let mut iter = polyline.iter_segments();
+        assert!(seg_eq(iter.next_back(), 2, 3));
+        assert!(seg_eq(iter.next_back(), 1, 2));
+        assert!(seg_eq(iter.next_back(), 0, 1));
+        assert_eq!(iter.len(), 1);
+        assert!(seg_eq(iter.next_back(), 3, 0));
+        assert_eq!(iter.len(), 0);
+        assert!(iter.next().is_none());
+
+        let mut open = polyline.clone();
+        open.set_is_closed(false);
+        let mut iter = open.iter_segments();
+        assert_eq!(iter.len(), 3);
+        assert!(seg_eq(iter.next_back(), 2, 3));
+        assert!(seg_eq(iter.next(), 0, 1));
+        assert!(seg_eq(iter.next_back(), 1, 2));
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn split_at_arc_length() {
+        // half circle going counter clockwise from (0, 0) to (2, 0)
+        let mut semicircle = Polyline::new();
+        semicircle.add(0.0, 0.0, 1.0);
+        semicircle.add(2.0, 0.0, 0.0);
+
+        let (prefix, suffix) = semicircle.split_at_arc_length(PI / 2.0);
+        assert!(!prefix.is_closed());
+        assert!(!suffix.is_closed());
+        assert_eq!(prefix.len(), 2);
+        assert_eq!(suffix.len(), 2);
+        assert_fuzzy_eq!(prefix.path_length(), PI / 2.0);
+        assert_fuzzy_eq!(suffix.path_length(), PI / 2.0);
+        let quarter_circle_bulge = (PI / 8.0).tan();
+        assert!(prefix[0].fuzzy_eq(PlineVertex::new(0.0, 0.0, quarter_circle_bulge)));
+        assert!(prefix[1].fuzzy_eq(PlineVertex::new(1.0, -1.0, 0.0)));
+        assert!(suffix[0].fuzzy_eq(PlineVertex::new(1.0, -1.0, quarter_circle_bulge)));
+        assert!(suffix[1].fuzzy_eq(PlineVertex::new(2.0, 0.0, 0.0)));
+
+        // cutting at vertexes and ends
+        let mut polyline = Polyline::new();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(1.0, 0.0, 1.0);
+        polyline.add(3.0, 0.0, 0.0);
+        let (prefix, suffix) = polyline.split_at_arc_length(1.0);
+        assert_eq!(prefix.len(), 2);
+        assert!(prefix[1].fuzzy_eq(PlineVertex::new(1.0, 0.0, 0.0)));
+        assert_eq!(suffix.len(), 2);
+        assert!(suffix[0].fuzzy_eq(PlineVertex::new(1.0, 0.0, 1.0)));
+        let (prefix, suffix) = polyline.split_at_arc_length(-1.0);
+        assert_eq!(prefix.len(), 1);
+        assert_eq!(suffix.len(), 3);
+        assert_fuzzy_eq!(suffix.path_length(), polyline.path_length());
+        let (prefix, suffix) = polyline.split_at_arc_length(100.0);
+        assert_eq!(prefix.len(), 3);
+        assert_fuzzy_eq!(prefix.path_length(), polyline.path_length());
+        assert_eq!(suffix.len(), 1);
+
+        // closed polyline is opened at the cut
+        let mut circle = Polyline::new_closed();
+        circle.add(0.0, 0.0, 1.0);
+        circle.add(2.0, 0.0, 1.0);
+        let (opened, rest) = circle.split_at_arc_length(PI / 2.0);
+        assert!(!opened.is_closed());
+        assert_eq!(rest.len(), 0);
+        assert_eq!(opened.len(), 4);
+        assert_fuzzy_eq!(opened.path_length(), 2.0 * PI);
+        assert!(opened[0].pos().fuzzy_eq(Vector2::new(1.0, -1.0)));
+        assert!(opened[3].fuzzy_eq(PlineVertex::new(1.0, -1.0, 0.0)));
+    }
+
+    #[test]
+    fn slice() {
+        let mut line = Polyline::new();
+        line.add(0.0, 0.0, 0.0);
+        line.add(3.0, 0.0, 0.0);
+        let middle = line.slice(1.0, 2.0);
+        assert!(!middle.is_closed());
+        assert_eq!(middle.len(), 2);
+        assert!(middle[0].fuzzy_eq(PlineVertex::new(1.0, 0.0, 0.0)));
+        assert!(middle[1].fuzzy_eq(PlineVertex::new(2.0, 0.0, 0.0)));
+        assert_eq!(line.slice(2.0, 1.0).len(), 0);
+
+        // middle third of a half circle, both cuts inside the same arc
+        let mut semicircle = Polyline::new();
+        semicircle.add(0.0, 0.0, 1.0);
+        semicircle.add(2.0, 0.0, 0.0);
+        let middle = semicircle.slice(PI / 3.0, 2.0 * PI / 3.0);
+        assert_eq!(middle.len(), 2);
+        assert_fuzzy_eq!(middle.path_length(), PI / 3.0);
+        let sixth_circle_bulge = (PI / 12.0).tan();
+        let start_angle = PI + PI / 3.0;
+        let end_angle = PI + 2.0 * PI / 3.0;
+        assert!(middle[0].fuzzy_eq(PlineVertex::new(
+            1.0 + start_angle.cos(),
+            start_angle.sin(),
+            sixth_circle_bulge
+        )));
+        assert!(middle[1].fuzzy_eq(PlineVertex::new(
+            1.0 + end_angle.cos(),
+            end_angle.sin(),
+            0.0
+        )));
+
+        // closed polyline wrapping around the start
+        let mut square = Polyline::new_closed();
+        square.add(0.0, 0.0, 0.0);
+        square.add(1.0, 0.0, 0.0);
+        square.add(1.0, 1.0, 0.0);
+        square.add(0.0, 1.0, 0.0);
+        let corner = square.slice(3.5, 0.5);
+        assert!(!corner.is_closed());
+        assert_eq!(corner.len(), 3);
+        assert!(corner[0].fuzzy_eq(PlineVertex::new(0.0, 0.5, 0.0)));
+        assert!(corner[1].fuzzy_eq(PlineVertex::new(0.0, 0.0, 0.0)));
+        assert!(corner[2].fuzzy_eq(PlineVertex::new(0.5, 0.0, 0.0)));
+        let side = square.slice(1.0, 2.0);
+        assert_eq!(side.len(), 2);
+        assert_fuzzy_eq!(side.path_length(), 1.0);
+        assert_fuzzy_eq!(square.slice(0.0, 4.0).path_length(), 4.0);
+    }
+
+    #[test]
+    fn join_polylines_arcs_into_circle() {
+        // four quarter arcs of a counter clockwise circle with radius 1 centered at the origin,
+        // out of order and with two of them inverted
+        let quarter_bulge = (PI / 8.0).tan();
+        let arc = |x1: f64, y1: f64, x2: f64, y2: f64, bulge: f64| {
+            let mut pline = Polyline::new();
+            pline.add(x1, y1, bulge);
+            pline.add(x2, y2, 0.0);
+            pline
+        };
+        let plines = [
+            arc(1.0, 0.0, 0.0, 1.0, quarter_bulge),
+            arc(0.0, -1.0, -1.0, 0.0, -quarter_bulge),
+            arc(0.0, -1.0, 1.0, 0.0, quarter_bulge),
+            arc(0.0, 1.0, -1.0, 0.0, quarter_bulge),
+        ];
+
+        let joined = join_polylines(&plines, 1e-5);
+        assert_eq!(joined.len(), 1);
+        let circle = &joined[0];
+        assert!(circle.is_closed());
+        assert_eq!(circle.len(), 4);
+        assert_fuzzy_eq!(circle.area(), PI);
+        assert_fuzzy_eq!(circle.path_length(), 2.0 * PI);
+        assert!(circle.iter().all(|v| v.bulge.fuzzy_eq(quarter_bulge)));
+
+        // pieces which cannot be joined pass through unchanged
+        let mut closed = Polyline::new_closed();
+        closed.add(5.0, 5.0, 1.0);
+        closed.add(6.0, 5.0, 1.0);
+        let lone = arc(10.0, 10.0, 11.0, 10.0, 0.5);
+        let joined = join_polylines(&[plines[0].clone(), closed, lone], 1e-5);
+        assert_eq!(joined.len(), 3);
+        assert!(!joined[0].is_closed());
+        assert!(joined[1].is_closed());
+        assert!(joined[2][0].fuzzy_eq(PlineVertex::new(10.0, 10.0, 0.5)));
+
+        // open chain
+        let joined = join_polylines(&plines[..2], 1e-5);
+        assert_eq!(joined.len(), 2);
+        let joined = join_polylines(&[plines[0].clone(), plines[3].clone()], 1e-5);
+        assert_eq!(joined.len(), 1);
+        assert!(!joined[0].is_closed());
+        assert_eq!(joined[0].len(), 3);
+        assert_fuzzy_eq!(joined[0].path_length(), PI);
+    }
+
+    #[test]
+    fn orient() {
+        let mut cw_square = Polyline::new_closed();
+        cw_square.add(0.0, 0.0, 0.0);
+        cw_square.add(0.0, 1.0, 0.0);
+        cw_square.add(1.0, 1.0, 0.0);
+        cw_square.add(1.0, 0.0, 0.0);
+        assert_fuzzy_eq!(cw_square.area(), -1.0);
+
+        let mut square = cw_square.clone();
+        assert!(square.orient(true));
+        assert_fuzzy_eq!(square.area(), 1.0);
+
+        // already counter clockwise is left untouched
+        let ccw_square = square.clone();
+        assert!(!square.orient(true));
+        assert!(square.fuzzy_eq(&ccw_square));
+
+        assert!(square.orient(false));
+        assert_fuzzy_eq!(square.area(), -1.0);
+        assert!(!square.orient(false));
+
+        let mut open = cw_square.clone();
+        open.set_is_closed(false);
+        assert!(!open.orient(true));
+        assert!(open.fuzzy_eq(&cw_square));
+    }
+
+    #[test]
+    fn insert_at_closest() {
+        let mut polyline = Polyline::new_closed();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(2.0, 0.0, 1.0);
+        polyline.add(2.0, 2.0, 0.0);
+        polyline.add(0.0, 2.0, -0.5);
+        let original = polyline.clone();
+
+        // point outside the arc bulging out from (2, 0) to (2, 2)
+        let index = polyline.insert_at_closest(Vector2::new(4.0, 1.0));
+        assert_eq!(index, Some(2));
+        assert_eq!(polyline.len(), 5);
+        assert!(polyline[2].pos().fuzzy_eq(Vector2::new(3.0, 1.0)));
+        assert_fuzzy_eq!(polyline.area(), original.area());
+        assert_fuzzy_eq!(polyline.path_length(), original.path_length());
+        let count = 40;
+        let step = original.path_length() / count as f64;
+        for k in 0..count {
+            let distance = k as f64 * step;
+            let p1 = original.sample_at_arc_length(distance).unwrap();
+            let p2 = polyline.sample_at_arc_length(distance).unwrap();
+            assert!(p1.fuzzy_eq(p2));
+        }
+
+        // closing arc segment (bulging inward)
+        let index = polyline.insert_at_closest(Vector2::new(0.25, 1.0));
+        assert_eq!(index, Some(5));
+        assert_eq!(polyline.len(), 6);
+        assert!(polyline[5].pos().fuzzy_eq(Vector2::new(0.5, 1.0)));
+        assert_fuzzy_eq!(polyline.area(), original.area());
+
+        // closest point on an existing vertex
+        let index = polyline.insert_at_closest(Vector2::new(-1.0, -1.0));
+        assert_eq!(index, Some(0));
+        assert_eq!(polyline.len(), 6);
+
+        // non-finite point
+        assert_eq!(
+            polyline.insert_at_closest(Vector2::new(f64::NAN, 1.0)),
+            None
+        );
+        assert_eq!(polyline.len(), 6);
+
+        // non-finite polyline
+        polyline[3].bulge = f64::INFINITY;
+        assert_eq!(polyline.insert_at_closest(Vector2::new(4.0, 1.0)), None);
+        assert_eq!(polyline.len(), 6);
+    }
+
+    #[test]
+    fn insert_at_closest_single_vertex() {
+        let mut polyline = Polyline::new();
+        polyline.add(1.0, 1.0, 0.0);
+        assert_eq!(polyline.insert_at_closest(Vector2::new(0.0, 0.0)), None);
+        assert_eq!(polyline.len(), 1);
+    }
+
+    #[test]
+    fn total_turning_angle() {
+        let mut square = Polyline::new_closed();
+        square.add(0.0, 0.0, 0.0);
+        square.add(1.0, 0.0, 0.0);
+        square.add(1.0, 1.0, 0.0);
+        square.add(0.0, 1.0, 0.0);
+        assert_fuzzy_eq!(square.total_turning_angle(), 2.0 * PI);
+        square.invert_direction();
+        assert_fuzzy_eq!(square.total_turning_angle(), -2.0 * PI);
+
+        // open path only turns at interior vertexes
+        square.set_is_closed(false);
+        assert_fuzzy_eq!(square.total_turning_angle(), -PI);
+
+        let mut circle = Polyline::new_closed();
+        circle.add(0.0, 0.0, 1.0);
+        circle.add(2.0, 0.0, 1.0);
+        assert_fuzzy_eq!(circle.total_turning_angle(), 2.0 * PI);
+        circle.invert_direction();
+        assert_fuzzy_eq!(circle.total_turning_angle(), -2.0 * PI);
+
+        // rounded square with a duplicate vertex
+        let mut rounded = Polyline::new_closed();
+        rounded.add(0.0, 0.0, 0.0);
+        rounded.add(2.0, 0.0, 0.0);
+        rounded.add(2.0, 0.0, (PI / 8.0).tan());
+        rounded.add(3.0, 1.0, 0.0);
+        rounded.add(3.0, 3.0, 0.0);
+        rounded.add(0.0, 3.0, 0.0);
+        assert_fuzzy_eq!(rounded.total_turning_angle(), 2.0 * PI);
+
+        // half circle arc
+        let mut arc = Polyline::new();
+        arc.add(0.0, 0.0, -1.0);
+        arc.add(2.0, 0.0, 0.0);
+        assert_fuzzy_eq!(arc.total_turning_angle(), -PI);
+        assert_fuzzy_eq!(Polyline::<f64>::new().total_turning_angle(), 0.0);
+    }
+
+    #[test]
+    fn bounding_circle() {
+        let radius = 2.5;
+        let center = Vector2::new(-1.0, 3.0);
+        let mut circle = Polyline::new_closed();
+        circle.add(center.x - radius, center.y, 1.0);
+        circle.add(center.x + radius, center.y, 1.0);
+        let (c, r) = circle.bounding_circle().unwrap();
+        assert_fuzzy_eq!(c, center, 1e-4);
+        assert_fuzzy_eq!(r, radius, 1e-4);
+
+        // arc bulge extends past the vertexes
+        let mut half_circle = Polyline::new();
+        half_circle.add(0.0, 0.0, 1.0);
+        half_circle.add(2.0, 0.0, 0.0);
+        let (c, r) = half_circle.bounding_circle().unwrap();
+        assert_fuzzy_eq!(c, Vector2::new(1.0, 0.0), 1e-4);
+        assert_fuzzy_eq!(r, 1.0, 1e-4);
+
+        // obtuse triangle circle is defined by its longest side
+        let mut triangle = Polyline::new_closed();
+        triangle.add(0.0, 0.0, 0.0);
+        triangle.add(10.0, 0.0, 0.0);
+        triangle.add(5.0, 1.0, 0.0);
+        triangle.add(5.0, 0.5, 0.0);
+        let (c, r) = triangle.bounding_circle().unwrap();
+        assert_fuzzy_eq!(c, Vector2::new(5.0, 0.0));
+        assert_fuzzy_eq!(r, 5.0);
+
+        let mut point = Polyline::new();
+        point.add(1.0, 2.0, 0.0);
+        let (c, r) = point.bounding_circle().unwrap();
+        assert_fuzzy_eq!(c, Vector2::new(1.0, 2.0));
+        assert_fuzzy_eq!(r, 0.0);
+        assert!(Polyline::<f64>::new().bounding_circle().is_none());
+    }
+
+    #[test]
+    fn parallel_offset_both() {
+        let mut square = Polyline::new_closed();
+        square.add(0.0, 0.0, 0.0);
+        square.add(5.0, 0.0, 0.0);
+        square.add(5.0, 5.0, 0.5);
+        square.add(0.0, 5.0, 0.0);
+
+        let assert_same = |a: &[Polyline], b: &[Polyline]| {
+            assert_eq!(a.len(), b.len());
+            for (p1, p2) in a.iter().zip(b) {
+                assert_eq!(p1.is_closed(), p2.is_closed());
+                assert_eq!(p1.len(), p2.len());
+                assert!(p1.fuzzy_eq(p2));
+            }
+        };
+
+        for &offset in &[0.5, -0.5, 2.0] {
+            let (positive, negative) = square.parallel_offset_both(offset);
+            assert!(!positive.is_empty());
+            assert!(!negative.is_empty());
+            assert_same(&positive, &square.parallel_offset(offset, None));
+            assert_same(&negative, &square.parallel_offset(-offset, None));
+        }
+
+        let (positive, negative) = Polyline::<f64>::new().parallel_offset_both(1.0);
+        assert!(positive.is_empty());
+        assert!(negative.is_empty());
+    }
+
+    #[test]
+    fn iter_typed_segments() {
+        let mut polyline = Polyline::new_closed();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(2.0, 0.0, -1.0);
+        polyline.add(4.0, 0.0, 0.0);
+        polyline.add(4.0, 2.0, 0.5);
+
+        let segments: Vec<_> = polyline.iter_typed_segments().collect();
+        assert_eq!(segments.len(), 4);
+        // closing segment first (same as iter_segments)
+        match segments[0] {
+            PlineSegment::Arc {
+                start,
+                end,
+                center,
+                radius,
+                ccw,
+                ..
+            } => {
+                let (expected_radius, expected_center) =
+                    seg_arc_radius_and_center(polyline[3], polyline[0]);
+                assert!(start.fuzzy_eq(Vector2::new(4.0, 2.0)));
+                assert!(end.fuzzy_eq(Vector2::new(0.0, 0.0)));
+                assert!(center.fuzzy_eq(expected_center));
+                assert_fuzzy_eq!(radius, expected_radius);
+                assert!(ccw);
+            }
+            _ => panic!("expected arc segment"),
+        }
+        assert_eq!(
+            segments[1],
+            PlineSegment::Line {
+                start: Vector2::new(0.0, 0.0),
+                end: Vector2::new(2.0, 0.0)
+            }
+        );
+        match segments[2] {
+            PlineSegment::Arc {
+                center,
+                radius,
+                start_angle,
+                end_angle,
+                ccw,
+                ..
+            } => {
+                assert!(center.fuzzy_eq(Vector2::new(3.0, 0.0)));
+                assert_fuzzy_eq!(radius, 1.0);
+                assert_fuzzy_eq!(start_angle, PI);
+                assert_fuzzy_eq!(end_angle, 0.0);
+                assert!(!ccw);
+            }
+            _ => panic!("expected arc segment"),
+        }
+        assert!(matches!(segments[3], PlineSegment::Line { .. }));
+        assert!(segments[3].start().fuzzy_eq(Vector2::new(4.0, 0.0)));
+        assert!(segments[3].end().fuzzy_eq(Vector2::new(4.0, 2.0)));
+    }
+
+    #[test]
+    fn closest_point_seg_t() {
+        let mut polyline = Polyline::new_closed();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(4.0, 0.0, 1.0);
+        polyline.add(4.0, 4.0, 0.0);
+        polyline.add(0.0, 4.0, -0.5);
+
+        // midpoint of each segment
+        for i in 0..polyline.len() {
+            let v1 = polyline[i];
+            let v2 = polyline[polyline.next_wrapping_index(i)];
+            let midpoint = seg_midpoint(v1, v2);
+            let result = polyline.closest_point(midpoint).unwrap();
+            assert_eq!(result.seg_start_index, i);
+            assert_fuzzy_eq!(result.seg_t, 0.5);
+            assert_fuzzy_eq!(result.distance, 0.0);
+        }
+
+        // quarter of the way along the half circle arc
+        let quarter_point =
+            Vector2::new(4.0 + 2.0 * (PI / 4.0).sin(), 2.0 - 2.0 * (PI / 4.0).cos());
+        let result = polyline.closest_point(quarter_point).unwrap();
+        assert_eq!(result.seg_start_index, 1);
+        assert_fuzzy_eq!(result.seg_t, 0.25);
+
+        // end of segment
+        let result = polyline.closest_point(Vector2::new(-1.0, -1.0)).unwrap();
+        assert!(result.seg_point.fuzzy_eq(Vector2::new(0.0, 0.0)));
+        assert!(result.seg_t.fuzzy_eq(0.0) || result.seg_t.fuzzy_eq(1.0));
+    }
+
+    #[test]
+    fn clip_to() {
+        let mut circle = Polyline::new_closed();
+        circle.add(-1.0, 0.0, 1.0);
+        circle.add(1.0, 0.0, 1.0);
+
+        let mut line = Polyline::new();
+        line.add(-3.0, 0.5, 0.0);
+        line.add(3.0, 0.5, 0.0);
+        let half_chord = 0.75f64.sqrt();
+
+        let inside = line.clip_to(&circle, true);
+        assert_eq!(inside.len(), 1);
+        assert!(!inside[0].is_closed());
+        assert_eq!(inside[0].len(), 2);
+        assert_fuzzy_eq!(inside[0][0], PlineVertex::new(-half_chord, 0.5, 0.0));
+        assert_fuzzy_eq!(inside[0][1], PlineVertex::new(half_chord, 0.5, 0.0));
+
+        let outside = line.clip_to(&circle, false);
+        assert_eq!(outside.len(), 2);
+        assert_fuzzy_eq!(outside[0].path_length(), 3.0 - half_chord);
+        assert_fuzzy_eq!(outside[1].path_length(), 3.0 - half_chord);
+
+        // crossing at circle vertexes
+        let mut line = Polyline::new();
+        line.add(-3.0, 0.0, 0.0);
+        line.add(3.0, 0.0, 0.0);
+        let inside = line.clip_to(&circle, true);
+        assert_eq!(inside.len(), 1);
+        assert_fuzzy_eq!(inside[0].path_length(), 2.0);
+
+        // entirely inside
+        let mut line = Polyline::new();
+        line.add(-0.5, 0.0, 0.0);
+        line.add(0.5, 0.0, 0.0);
+        assert_eq!(line.clip_to(&circle, true).len(), 1);
+        assert!(line.clip_to(&circle, false).is_empty());
+
+        // closed polyline clipped, outside piece wraps around the start
+        let mut rect = Polyline::new_closed();
+        rect.add(-3.0, -0.5, 0.0);
+        rect.add(3.0, -0.5, 0.0);
+        rect.add(3.0, 0.5, 0.0);
+        rect.add(-3.0, 0.5, 0.0);
+        let inside = rect.clip_to(&circle, true);
+        assert_eq!(inside.len(), 2);
+        let outside = rect.clip_to(&circle, false);
+        assert_eq!(outside.len(), 2);
+        let total_length = inside
+            .iter()
+            .chain(outside.iter())
+            .fold(0.0, |acc, p| acc + p.path_length());
+        assert_fuzzy_eq!(total_length, rect.path_length());
+        assert!(rect.clip_to(&circle, true).iter().all(|p| !p.is_closed()));
+
+        // closed polyline entirely inside
+        let mut small_circle = Polyline::new_closed();
+        small_circle.add(-0.5, 0.0, 1.0);
+        small_circle.add(0.5, 0.0, 1.0);
+        let inside = small_circle.clip_to(&circle, true);
+        assert_eq!(inside.len(), 1);
+        assert!(inside[0].fuzzy_eq(&small_circle));
+        assert!(small_circle.clip_to(&circle, false).is_empty());
+    }
+
+    #[test]
+    fn clip_to_uses_pos_equal_eps() {
+        let mut circle = Polyline::new_closed();
+        circle.add(-1.0, 0.0, 1.0);
+        circle.add(1.0, 0.0, 1.0);
+
+        // line crossing near the top of the circle, inside piece has length 0.05
+        let mut line = Polyline::new();
+        let y = (1.0f64 - 0.025 * 0.025).sqrt();
+        line.add(-3.0, y, 0.0);
+        line.add(3.0, y, 0.0);
+        let inside = line.clip_to(&circle, true);
+        assert_eq!(inside.len(), 1);
+        assert_fuzzy_eq!(inside[0].path_length(), 0.05);
+
+        // cuts closer than the stored epsilon are merged
+        line.set_pos_equal_eps(0.1);
+        assert!(line.clip_to(&circle, true).is_empty());
+        let outside = line.clip_to(&circle, false);
+        assert_eq!(outside.len(), 1);
+        assert_fuzzy_eq!(outside[0].path_length(), 6.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn clip_to_open_boundary_panics() {
+        let mut line = Polyline::new();
+        line.add(0.0, 0.0, 0.0);
+        line.add(1.0, 0.0, 0.0);
+        let boundary = line.clone();
+        line.clip_to(&boundary, true);
+    }
+
+    #[test]
+    fn retain_vertexes() {
+        let mut polyline = Polyline::new_closed();
+        polyline.add(-1.0, 0.0, 0.5);
+        polyline.add(0.0, -1.0, 0.0);
+        polyline.add(1.0, 0.0, 1.0);
+        polyline.add(-2.0, 1.0, 0.0);
+        polyline.add(2.0, 2.0, -0.5);
+        polyline.retain_vertexes(|v| v.x >= 0.0);
+        assert!(polyline.is_closed());
+        assert_eq!(polyline.len(), 3);
+        assert_fuzzy_eq!(polyline[0], PlineVertex::new(0.0, -1.0, 0.0));
+        assert_fuzzy_eq!(polyline[1], PlineVertex::new(1.0, 0.0, 1.0));
+        assert_fuzzy_eq!(polyline[2], PlineVertex::new(2.0, 2.0, -0.5));
+
+        polyline.retain_vertexes(|_| false);
+        assert!(polyline.is_closed());
+        assert_eq!(polyline.len(), 0);
+    }
+
+    #[test]
+    fn insert() {
+        let mut polyline = Polyline::new();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(2.0, 0.0, 0.5);
+        polyline.add(4.0, 0.0, 0.0);
+        polyline.insert(1, 1.0, 1.0, -0.5);
+        assert_eq!(polyline.len(), 4);
+        assert_fuzzy_eq!(polyline[0], PlineVertex::new(0.0, 0.0, 0.0));
+        assert_fuzzy_eq!(polyline[1], PlineVertex::new(1.0, 1.0, -0.5));
+        assert_fuzzy_eq!(polyline[2], PlineVertex::new(2.0, 0.0, 0.5));
+        assert_fuzzy_eq!(polyline[3], PlineVertex::new(4.0, 0.0, 0.0));
+
+        polyline.insert_vertex(4, PlineVertex::new(5.0, 0.0, 0.0));
+        polyline.insert_vertex(0, PlineVertex::new(-1.0, 0.0, 0.0));
+        assert_eq!(polyline.len(), 6);
+        assert_fuzzy_eq!(polyline[0], PlineVertex::new(-1.0, 0.0, 0.0));
+        assert_fuzzy_eq!(polyline[2], PlineVertex::new(1.0, 1.0, -0.5));
+        assert_fuzzy_eq!(polyline[5], PlineVertex::new(5.0, 0.0, 0.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_out_of_bounds_panics() {
+        let mut polyline = Polyline::new();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.insert(2, 1.0, 1.0, 0.0);
+    }
+
+    #[test]
+    fn cumulative_lengths() {
+        assert!(Polyline::<f64>::new().cumulative_lengths().is_empty());
+
+        let mut polyline = Polyline::new();
+        polyline.add(0.0, 0.0, 1.0);
+        let lengths = polyline.cumulative_lengths();
+        assert_eq!(lengths.len(), 1);
+        assert_fuzzy_eq!(lengths[0], 0.0);
+
+        polyline.add(2.0, 0.0, 0.0);
+        polyline.add(2.0, 3.0, -0.5);
+        polyline.add(0.0, 3.0, 0.0);
+        for is_closed in [false, true] {
+            polyline.set_is_closed(is_closed);
+            let lengths = polyline.cumulative_lengths();
+            let expected_len = if is_closed { 5 } else { 4 };
+            assert_eq!(lengths.len(), expected_len);
+            assert_fuzzy_eq!(lengths[0], 0.0);
+            assert_fuzzy_eq!(lengths[1], PI);
+            assert_fuzzy_eq!(lengths[2], PI + 3.0);
+            assert!(lengths.windows(2).all(|w| w[0] < w[1]));
+            assert_fuzzy_eq!(*lengths.last().unwrap(), polyline.path_length());
+        }
+    }
+
+    #[test]
+    fn vertex_at_arc_length() {
+        let mut polyline = Polyline::new();
+        assert!(polyline.vertex_at_arc_length(0.0).is_none());
+        polyline.add(0.0, 0.0, 0.0);
+        assert!(polyline.vertex_at_arc_length(0.0).is_none());
+        // line, half circle arc (length PI), line
+        polyline.add(2.0, 0.0, 1.0);
+        polyline.add(4.0, 0.0, 0.0);
+        polyline.add(4.0, 3.0, 0.0);
+        let total_length = 5.0 + PI;
+
+        let check = |pline: &Polyline<f64>, distance: f64, index: usize, seg_distance: f64| {
+            let result = pline.vertex_at_arc_length(distance).unwrap();
+            assert_eq!(result.0, index);
+            assert_fuzzy_eq!(result.1, seg_distance);
+        };
+
+        check(&polyline, 0.0, 0, 0.0);
+        check(&polyline, 1.0, 0, 1.0);
+        // landing on a vertex returns the segment starting at the vertex
+        check(&polyline, 2.0, 1, 0.0);
+        check(&polyline, 2.0 + PI / 2.0, 1, PI / 2.0);
+        check(&polyline, 3.0 + PI, 2, 1.0);
+        // clamped for open polylines
+        check(&polyline, -1.0, 0, 0.0);
+        check(&polyline, total_length, 2, 3.0);
+        check(&polyline, total_length + 5.0, 2, 3.0);
+
+        // wraps for closed polylines (closing segment has length 5)
+        polyline.set_is_closed(true);
+        let total_length = total_length + 5.0;
+        check(&polyline, total_length - 1.0, 3, 4.0);
+        check(&polyline, total_length + 1.0, 0, 1.0);
+        check(&polyline, -1.0, 3, 4.0);
+    }
+
+    #[test]
+    fn pos_equal_eps() {
+        let mut polyline = Polyline::<f64>::new();
+        assert_fuzzy_eq!(polyline.pos_equal_eps(), 1e-5);
+        polyline.add_or_merge(0.0, 0.0, 0.0);
+        polyline.add_or_merge(0.001, 0.0, 0.0);
+        assert_eq!(polyline.len(), 2);
+
+        polyline.clear();
+        polyline.set_pos_equal_eps(0.01);
+        assert_fuzzy_eq!(polyline.pos_equal_eps(), 0.01);
+        polyline.add_or_merge(0.0, 0.0, 0.0);
+        polyline.add_or_merge(0.001, 0.0, 0.5);
+        polyline.add_or_merge(1.0, 0.0, 0.0);
+        polyline.add_or_merge(1.0, 0.005, 0.0);
+        assert_eq!(polyline.len(), 2);
+        assert_fuzzy_eq!(polyline[0], PlineVertex::new(0.0, 0.0, 0.5));
+        assert_fuzzy_eq!(polyline[1], PlineVertex::new(1.0, 0.0, 0.0));
+
+        // kept when cloned
+        assert_fuzzy_eq!(polyline.clone().pos_equal_eps(), 0.01);
+
+        // used by methods which do not take an epsilon parameter
+        let mut line = Polyline::<f64>::new();
+        line.add(0.0, 0.0, 0.0);
+        line.add(4.0, 0.0, 0.0);
+        let mut snapped = line.clone();
+        snapped.set_pos_equal_eps(0.01);
+        assert_eq!(snapped.insert_at_closest(Vector2::new(0.005, 1.0)), Some(0));
+        assert_eq!(snapped.len(), 2);
+        assert_eq!(line.insert_at_closest(Vector2::new(0.005, 1.0)), Some(1));
+        assert_eq!(line.len(), 3);
+    }
+
+    #[test]
+    fn arcs_to_approx_lines_by_chord() {
+        // large arc (3/4 of a circle with radius 100) followed by a long line
+        let mut polyline = Polyline::new();
+        polyline.add(100.0, 0.0, (3.0 * PI / 8.0).tan());
+        polyline.add(0.0, -100.0, 0.0);
+        polyline.add(0.0, -500.0, 0.0);
+
+        for &max_chord in &[0.5, 7.0, 99.0, 250.0] {
+            let result = polyline.arcs_to_approx_lines_by_chord(max_chord);
+            assert!(!result.is_closed());
+            assert!(result.iter().all(|v| v.bulge_is_zero()));
+            assert_fuzzy_eq!(result[0].pos(), polyline[0].pos());
+            assert_fuzzy_eq!(*result.last().unwrap(), polyline[2]);
+            // line segment at the end is left as is
+            let arc_segs = result.len() - 2;
+            for i in 0..arc_segs {
+                let chord = (result[i + 1].pos() - result[i].pos()).length();
+                assert!(chord <= max_chord + 1e-9);
+                // all points lie on the arc
+                assert_fuzzy_eq!(result[i + 1].pos().length(), 100.0);
+            }
+            // fewest segments used (one less segment exceeds max_chord)
+            let sub_angle = 1.5 * PI / (arc_segs - 1) as f64;
+            assert!(sub_angle > PI || 200.0 * (sub_angle / 2.0).sin() > max_chord);
+        }
+
+        assert_eq!(polyline.arcs_to_approx_lines_by_chord(0.5).len(), 943 + 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn arcs_to_approx_lines_by_chord_zero_panics() {
+        let mut polyline = Polyline::new();
+        polyline.add(0.0, 0.0, 1.0);
+        polyline.add(2.0, 0.0, 0.0);
+        polyline.arcs_to_approx_lines_by_chord(0.0);
+    }
+
+    #[test]
+    fn snap_to_grid() {
+        let mut square = Polyline::new_closed();
+        square.add(0.03, -0.02, 0.0);
+        square.add(4.98, 0.04, 0.0);
+        square.add(5.01, 3.97, 0.0);
+        square.add(-0.049, 4.02, 0.0);
+        square.snap_to_grid(1.0, Vector2::zero(), false);
+        let expected = [(0.0, 0.0), (5.0, 0.0), (5.0, 4.0), (0.0, 4.0)];
+        assert_eq!(square.len(), expected.len());
+        for (v, (x, y)) in square.iter().zip(expected.iter()) {
+            // exact integer coordinates
+            assert_eq!(v.x, *x);
+            assert_eq!(v.y, *y);
+        }
+
+        // grid relative to origin
+        let mut polyline = Polyline::new();
+        polyline.add(0.4, 0.6, 0.25);
+        polyline.add(2.6, 2.4, 0.0);
+        polyline.snap_to_grid(2.0, Vector2::new(0.5, 0.5), false);
+        assert_fuzzy_eq!(polyline[0], PlineVertex::new(0.5, 0.5, 0.25));
+        assert_fuzzy_eq!(polyline[1], PlineVertex::new(2.5, 2.5, 0.0));
+
+        // coincident vertexes after snapping
+        let mut polyline = Polyline::new_closed();
+        polyline.add(0.1, 0.1, 0.0);
+        polyline.add(0.9, 0.0, 0.0);
+        polyline.add(1.1, 0.1, 0.5);
+        polyline.add(1.0, 1.0, 0.0);
+        polyline.add(0.1, -0.1, 0.0);
+        let mut kept = polyline.clone();
+        kept.snap_to_grid(1.0, Vector2::zero(), false);
+        assert_eq!(kept.len(), 5);
+        polyline.snap_to_grid(1.0, Vector2::zero(), true);
+        assert_eq!(polyline.len(), 3);
+        assert_fuzzy_eq!(polyline[0], PlineVertex::new(0.0, 0.0, 0.0));
+        assert_fuzzy_eq!(polyline[1], PlineVertex::new(1.0, 0.0, 0.5));
+        assert_fuzzy_eq!(polyline[2], PlineVertex::new(1.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn densify() {
+        let mut polyline = Polyline::new_closed();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(10.0, 0.0, 1.0);
+        polyline.add(10.0, 6.0, 0.0);
+        polyline.add(0.5, 6.0, -0.3);
+        let original = polyline.clone();
+
+        for &max_spacing in &[0.3, 1.0, 2.5, 100.0] {
+            for &is_closed in &[true, false] {
+                let mut original = original.clone();
+                original.set_is_closed(is_closed);
+                let mut densified = original.clone();
+                densified.densify(max_spacing);
+                assert_eq!(densified.is_closed(), is_closed);
+                assert!(densified
+                    .iter_segments()
+                    .all(|(v1, v2)| seg_length(v1, v2) <= max_spacing + 1e-9));
+                assert_fuzzy_eq!(densified.path_length(), original.path_length());
+                assert_fuzzy_eq!(densified.area(), original.area());
+                // all original vertexes are kept
+                assert!(original
+                    .iter()
+                    .all(|v| densified.iter().any(|u| u.pos().fuzzy_eq(v.pos()))));
+                // all new vertexes lie on the original path
+                assert!(densified.iter().all(|v| original
+                    .closest_point(v.pos())
+                    .unwrap()
+                    .distance
+                    < 1e-9));
+            }
+        }
+
+        // nothing to subdivide
+        let mut polyline = original.clone();
+        polyline.densify(100.0);
+        assert!(polyline.fuzzy_eq(&original));
+        assert_eq!(polyline.len(), original.len());
+    }
+
+    #[test]
+    fn same_loop() {
+        let mut square = Polyline::new_closed();
+        square.add(0.0, 0.0, 0.0);
+        square.add(2.0, 0.0, 0.5);
+        square.add(2.0, 2.0, 0.0);
+        square.add(0.0, 2.0, 0.0);
+
+        let mut other = Polyline::new_closed();
+        other.add(2.0, 2.0, 0.0);
+        other.add(0.0, 2.0, 0.0);
+        other.add(0.0, 0.0, 0.0);
+        other.add(2.0, 0.0, 0.5);
+        assert!(!square.fuzzy_eq(&other));
+        assert!(square.same_loop(&other, 1e-5));
+        assert!(other.same_loop(&square, 1e-5));
+
+        // opposite direction
+        let mut inverted = other.clone();
+        inverted.invert_direction();
+        inverted.set_start_vertex(1);
+        assert!(square.same_loop(&inverted, 1e-5));
+
+        // within eps
+        other.set_vertex(0, 2.0 + 1e-7, 2.0, 0.0);
+        assert!(square.same_loop(&other, 1e-5));
+        assert!(!square.same_loop(&other, 1e-9));
+
+        // different bulge
+        let mut different = square.clone();
+        different.set_vertex(1, 2.0, 0.0, -0.5);
+        assert!(!square.same_loop(&different, 1e-5));
+
+        // open polylines and different vertex counts
+        let mut open = square.clone();
+        open.set_is_closed(false);
+        assert!(!square.same_loop(&open, 1e-5));
+        assert!(!open.same_loop(&open, 1e-5));
+        let mut extra = square.clone();
+        extra.add(0.0, 1.0, 0.0);
+        assert!(!square.same_loop(&extra, 1e-5));
+    }
+
+    #[test]
+    fn canonicalize() {
+        let mut first = Polyline::new_closed();
+        first.add(3.0, 1.0, 0.0);
+        first.add(3.0, 3.0, 0.5);
+        first.add(1.0, 3.0, 0.0);
+        first.add(1.0, 1.0, -0.25);
+
+        // same loop starting at a different vertex in the opposite direction
+        let mut second = first.clone();
+        second.invert_direction();
+        second.set_start_vertex(1);
+        assert!(first.same_loop(&second, 1e-5));
+        assert!(!first.fuzzy_eq(&second));
+
+        first.canonicalize();
+        second.canonicalize();
+        assert_eq!(first.len(), second.len());
+        assert!(first.fuzzy_eq(&second));
+        assert!(first.area() > 0.0);
+        assert_fuzzy_eq!(first[0].pos(), Vector2::new(1.0, 1.0));
+
+        // canonicalizing again changes nothing
+        let mut again = first.clone();
+        again.canonicalize();
+        assert!(again.fuzzy_eq(&first));
+
+        // open polylines are unchanged
+        let mut open = Polyline::new();
+        open.add(2.0, 0.0, 0.0);
+        open.add(0.0, 0.0, 0.0);
+        let expected = open.clone();
+        open.canonicalize();
+        assert!(open.fuzzy_eq(&expected));
+    }
+
+    #[test]
+    fn max_inward_offset() {
+        let mut square = Polyline::new_closed();
+        square.add(0.0, 0.0, 0.0);
+        square.add(4.0, 0.0, 0.0);
+        square.add(4.0, 4.0, 0.0);
+        square.add(0.0, 4.0, 0.0);
+        let max_offset = square.max_inward_offset().unwrap();
+        assert_fuzzy_eq!(max_offset, 2.0, 1e-3);
+        assert!(!square.parallel_offset(max_offset, None).is_empty());
+        assert!(square.parallel_offset(max_offset + 1e-3, None).is_empty());
+
+        // clockwise gives the same result
+        square.invert_direction();
+        assert_fuzzy_eq!(square.max_inward_offset().unwrap(), 2.0, 1e-3);
+
+        // rectangle limited by the shorter side
+        let mut rect = Polyline::new_closed();
+        rect.add(0.0, 0.0, 0.0);
+        rect.add(10.0, 0.0, 0.0);
+        rect.add(10.0, 2.0, 0.0);
+        rect.add(0.0, 2.0, 0.0);
+        assert_fuzzy_eq!(rect.max_inward_offset().unwrap(), 1.0, 1e-3);
+
+        // circle
+        let mut circle = Polyline::new_closed();
+        circle.add(0.0, 0.0, 1.0);
+        circle.add(6.0, 0.0, 1.0);
+        assert_fuzzy_eq!(circle.max_inward_offset().unwrap(), 3.0, 1e-3);
+
+        // open or no area
+        let mut open = square.clone();
+        open.set_is_closed(false);
+        assert!(open.max_inward_offset().is_none());
+        let mut line = Polyline::new_closed();
+        line.add(0.0, 0.0, 0.0);
+        line.add(1.0, 0.0, 0.0);
+        assert!(line.max_inward_offset().is_none());
+    }
+
+    #[test]
+    fn iter_vertex_neighbors() {
+        let mut triangle = Polyline::new_closed();
+        triangle.add(0.0, 0.0, 0.0);
+        triangle.add(2.0, 0.0, 0.5);
+        triangle.add(1.0, 2.0, 0.0);
+        let triples = triangle.iter_vertex_neighbors().collect::<Vec<_>>();
+        assert_eq!(triples.len(), 3);
+        for (i, (prev, current, next)) in triples.into_iter().enumerate() {
+            assert_fuzzy_eq!(prev, triangle[(i + 2) % 3]);
+            assert_fuzzy_eq!(current, triangle[i]);
+            assert_fuzzy_eq!(next, triangle[(i + 1) % 3]);
+        }
+
+        // open polyline skips the ends
+        let mut open = triangle.clone();
+        open.set_is_closed(false);
+        let triples = open.iter_vertex_neighbors().collect::<Vec<_>>();
+        assert_eq!(triples.len(), 1);
+        assert_fuzzy_eq!(triples[0].0, open[0]);
+        assert_fuzzy_eq!(triples[0].1, open[1]);
+        assert_fuzzy_eq!(triples[0].2, open[2]);
+
+        open.remove_last();
+        assert_eq!(open.iter_vertex_neighbors().count(), 0);
+        assert_eq!(
+            Polyline::<f64>::new_closed()
+                .iter_vertex_neighbors()
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn fillet() {
+        let mut square = Polyline::new_closed();
+        square.add(0.0, 0.0, 0.0);
+        square.add(10.0, 0.0, 0.0);
+        square.add(10.0, 10.0, 0.0);
+        square.add(0.0, 10.0, 0.0);
+
+        let result = square.fillet(1.0);
+        assert!(result.is_closed());
+        assert_eq!(result.len(), 8);
+        let arcs = result
+            .iter_segments()
+            .filter(|(v1, _)| !v1.bulge_is_zero())
+            .collect::<Vec<_>>();
+        assert_eq!(arcs.len(), 4);
+        for (v1, v2) in arcs {
+            let (arc_radius, _) = seg_arc_radius_and_center(v1, v2);
+            assert_fuzzy_eq!(arc_radius, 1.0);
+            assert!(v1.bulge_is_pos());
+        }
+        assert_fuzzy_eq!(result.area(), 100.0 - 4.0 + PI);
+        assert_fuzzy_eq!(result.path_length(), 40.0 - 8.0 + 2.0 * PI);
+
+        // clockwise square gives clockwise arcs
+        let mut cw_square = square.clone();
+        cw_square.invert_direction();
+        let result = cw_square.fillet(1.0);
+        assert_eq!(result.len(), 8);
+        assert_fuzzy_eq!(result.area(), -(100.0 - 4.0 + PI));
+
+        // fillets meet in the middle of each side forming a circle
+        let result = square.fillet(5.0);
+        assert_eq!(result.len(), 4);
+        assert_fuzzy_eq!(result.area(), 25.0 * PI);
+
+        // corners too tight are skipped
+        let result = square.fillet(6.0);
+        assert!(result.fuzzy_eq(&square));
+        assert_eq!(result.len(), 4);
+
+        // arcs and open ends are not filleted
+        let mut polyline = Polyline::new();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(10.0, 0.0, 0.0);
+        polyline.add(10.0, 10.0, 0.5);
+        polyline.add(0.0, 10.0, 0.0);
+        let result = polyline.fillet(1.0);
+        assert_eq!(result.len(), 5);
+        assert_fuzzy_eq!(result[0], polyline[0]);
+        assert_fuzzy_eq!(result[1].pos(), Vector2::new(9.0, 0.0));
+        assert_fuzzy_eq!(result[2], PlineVertex::new(10.0, 1.0, 0.0));
+        assert_fuzzy_eq!(result[3], polyline[2]);
+        assert_fuzzy_eq!(result[4], polyline[3]);
+    }
+
+    #[test]
+    fn chamfer() {
+        let mut square = Polyline::new_closed();
+        square.add(0.0, 0.0, 0.0);
+        square.add(10.0, 0.0, 0.0);
+        square.add(10.0, 10.0, 0.0);
+        square.add(0.0, 10.0, 0.0);
+
+        let result = square.chamfer(2.0);
+        assert!(result.is_closed());
+        assert_eq!(result.len(), 8);
+        assert!(result.iter().all(|v| v.bulge_is_zero()));
+        let lengths = result
+            .iter_segments()
+            .map(|(v1, v2)| seg_length(v1, v2))
+            .collect::<Vec<_>>();
+        let diagonal = 2.0 * 2.0f64.sqrt();
+        assert_eq!(lengths.iter().filter(|l| l.fuzzy_eq(diagonal)).count(), 4);
+        assert_eq!(lengths.iter().filter(|l| l.fuzzy_eq(6.0)).count(), 4);
+        assert_fuzzy_eq!(result.area(), 100.0 - 4.0 * 2.0);
+
+        // edges too short
+        let result = square.chamfer(6.0);
+        assert!(result.fuzzy_eq(&square));
+        assert_eq!(result.len(), 4);
+    }
+
+    #[test]
+    fn is_convex() {
+        let mut circle = Polyline::new_closed();
+        circle.add(0.0, 0.0, 1.0);
+        circle.add(2.0, 0.0, 1.0);
+        assert!(circle.is_convex());
+        circle.invert_direction();
+        assert!(circle.is_convex());
+
+        let mut square = Polyline::new_closed();
+        square.add(0.0, 0.0, 0.0);
+        square.add(2.0, 0.0, 0.0);
+        square.add(2.0, 2.0, 0.0);
+        square.add(0.0, 2.0, 0.0);
+        assert!(square.is_convex());
+        square.invert_direction();
+        assert!(square.is_convex());
+
+        // collinear and duplicate vertexes are allowed
+        let mut square = Polyline::new_closed();
+        square.add(0.0, 0.0, 0.0);
+        square.add(1.0, 0.0, 0.0);
+        square.add(2.0, 0.0, 0.0);
+        square.add(2.0, 0.0, 0.0);
+        square.add(2.0, 2.0, 0.0);
+        square.add(0.0, 2.0, 0.0);
+        assert!(square.is_convex());
+
+        // rounded rectangle (arcs bulge outward)
+        let rounded = square.fillet(0.5);
+        assert!(rounded.is_convex());
+
+        // arc bulging inward
+        let mut bulged = square.clone();
+        bulged.set_vertex(4, 2.0, 2.0, -0.2);
+        assert!(!bulged.is_convex());
+
+        let mut l_shape = Polyline::new_closed();
+        l_shape.add(0.0, 0.0, 0.0);
+        l_shape.add(2.0, 0.0, 0.0);
+        l_shape.add(2.0, 1.0, 0.0);
+        l_shape.add(1.0, 1.0, 0.0);
+        l_shape.add(1.0, 2.0, 0.0);
+        l_shape.add(0.0, 2.0, 0.0);
+        assert!(!l_shape.is_convex());
+
+        // slight concave turn is detected regardless of the position equal epsilon
+        let mut dented = Polyline::new_closed();
+        dented.add(0.0, 0.0, 0.0);
+        dented.add(1.0, 0.001, 0.0);
+        dented.add(2.0, 0.0, 0.0);
+        dented.add(2.0, 2.0, 0.0);
+        dented.add(0.0, 2.0, 0.0);
+        assert!(!dented.is_convex());
+        dented.set_pos_equal_eps(0.01);
+        assert!(!dented.is_convex());
+
+        // self intersecting star turns consistently but winds around twice
+        let mut star = Polyline::new_closed();
+        for i in 0..5 {
+            let angle = (i as f64) * 4.0 * PI / 5.0;
+            star.add(angle.cos(), angle.sin(), 0.0);
+        }
+        assert!(!star.is_convex());
+
+        let mut open = square.clone();
+        open.set_is_closed(false);
+        assert!(!open.is_convex());
+    }
+
+    #[test]
+    fn remove_degenerate_segments() {
+        let mut polyline = Polyline::new_closed();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(2.0, 0.0, 0.0);
+        polyline.add(2.0, 1e-7, 1.0);
+        polyline.add(2.0, 2.0, 0.0);
+        polyline.add(0.0, 2.0, 0.0);
+        polyline.add(0.0, 0.0, 0.5);
+        let area = polyline.area();
+        assert_eq!(polyline.remove_degenerate_segments(1e-5), 2);
+        assert_eq!(polyline.len(), 4);
+        assert_fuzzy_eq!(polyline[1], PlineVertex::new(2.0, 0.0, 1.0));
+        assert_fuzzy_eq!(polyline[3], PlineVertex::new(0.0, 2.0, 0.0));
+        assert_fuzzy_eq!(polyline.area(), area, 1e-5);
+        assert_eq!(polyline.remove_degenerate_segments(1e-5), 0);
+
+        // open polyline end is not wrapped
+        let mut open = Polyline::new();
+        open.add(0.0, 0.0, 0.0);
+        open.add(0.0, 0.0, 0.0);
+        open.add(1.0, 0.0, 0.0);
+        open.add(0.0, 0.0, 0.0);
+        assert_eq!(open.remove_degenerate_segments(1e-5), 1);
+        assert_eq!(open.len(), 3);
+
+        // all vertexes coincident keeps one vertex
+        let mut point = Polyline::new_closed();
+        point.add(1.0, 1.0, 0.0);
+        point.add(1.0, 1.0, 0.0);
+        point.add(1.0, 1.0, 0.0);
+        assert_eq!(point.remove_degenerate_segments(1e-5), 2);
+        assert_eq!(point.len(), 1);
+    }
+
+    #[test]
+    fn validate_non_finite() {
+        let mut polyline = Polyline::new_closed();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(2.0, 0.0, 1.0);
+        polyline.add(2.0, 2.0, 0.0);
+        assert!(polyline.is_finite());
+        assert_eq!(polyline.validate(), Ok(()));
+
+        polyline.set_vertex(2, 2.0, f64::NAN, 0.0);
+        assert!(!polyline.is_finite());
+        assert_eq!(
+            polyline.validate(),
+            Err(PlineError::NonFiniteVertex { index: 2 })
+        );
+        assert!(polyline.extents().is_none());
+        assert!(polyline.closest_point(Vector2::zero()).is_none());
+        assert!(polyline.area().is_nan());
+
+        polyline.set_vertex(2, 2.0, 2.0, f64::NEG_INFINITY);
+        assert_eq!(
+            polyline.validate(),
+            Err(PlineError::NonFiniteVertex { index: 2 })
+        );
+    }
+
+    #[test]
+    fn spatial_index_cached_until_modified() {
+        let mut polyline = Polyline::new_closed();
+        assert!(polyline.spatial_index().is_none());
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(4.0, 0.0, 1.0);
+        polyline.add(4.0, 4.0, 0.0);
+
+        let first = polyline.spatial_index().unwrap() as *const _;
+        let second = polyline.spatial_index().unwrap() as *const _;
+        assert_eq!(first, second);
+        assert_eq!(polyline.spatial_index().unwrap().count(), 3);
+
+        polyline.add(0.0, 4.0, 0.0);
+        assert!(polyline.spatial_index_cache.get().is_none());
+        assert_eq!(polyline.spatial_index().unwrap().count(), 4);
+
+        polyline[1].bulge = 0.0;
+        assert!(polyline.spatial_index_cache.get().is_none());
+        assert!(polyline.spatial_index().is_some());
+        polyline.set_vertex(0, -1.0, 0.0, 0.0);
+        assert!(polyline.spatial_index_cache.get().is_none());
+        let query = |p: &Polyline<f64>| p.spatial_index().unwrap().query(-1.0, -0.1, -0.5, 0.1);
+        assert_eq!(query(&polyline).len(), 2);
+        polyline.set_is_closed(false);
+        assert!(polyline.spatial_index_cache.get().is_none());
+        assert_eq!(polyline.spatial_index().unwrap().count(), 3);
+        assert_eq!(query(&polyline).len(), 1);
+        polyline.translate(1.0, 0.0);
+        assert!(query(&polyline).is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn polyline_is_send_and_sync() {
+        fn assert_send_sync<P: Send + Sync>() {}
+        assert_send_sync::<Polyline<f64>>();
+        assert_send_sync::<Polyline<f32, u32>>();
     }
-}
 
-impl Iterator for PlineSegIndexIterator {
-    type Item = (usize, usize);
+    #[test]
+    fn reverse_range_middle_arcs() {
+        let mut polyline = Polyline::new_closed();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(2.0, 0.0, 0.5);
+        polyline.add(4.0, 0.0, -0.5);
+        polyline.add(6.0, 0.0, 0.0);
+        polyline.add(6.0, 4.0, 0.0);
+        polyline.add(0.0, 4.0, 0.0);
+        let original = polyline.clone();
+        let sample_midpoints = |p: &Polyline<f64>, range: core::ops::Range<usize>| {
+            range
+                .map(|i| seg_midpoint(p[i], p[i + 1]))
+                .collect::<Vec<_>>()
+        };
 
-    #[inline]
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.remaining == 0 {
-            return None;
+        polyline.reverse_range(1, 3);
+        assert!(polyline[1].fuzzy_eq(PlineVertex::new(6.0, 0.0, 0.5)));
+        assert!(polyline[2].fuzzy_eq(PlineVertex::new(4.0, 0.0, -0.5)));
+        assert!(polyline[3].fuzzy_eq(PlineVertex::new(2.0, 0.0, 0.0)));
+
+        // arc run is the same geometry traced in reverse
+        let mut reversed = sample_midpoints(&polyline, 1..3);
+        reversed.reverse();
+        let expected = sample_midpoints(&original, 1..3);
+        assert!(reversed
+            .iter()
+            .zip(expected.iter())
+            .all(|(a, b)| a.fuzzy_eq(*b)));
+        assert_fuzzy_eq!(
+            seg_length(polyline[1], polyline[2]) + seg_length(polyline[2], polyline[3]),
+            seg_length(original[1], original[2]) + seg_length(original[2], original[3])
+        );
+
+        // segments outside the range trace the same geometry
+        let seg_samples = |p: &Polyline<f64>, i: usize| {
+            let j = p.next_wrapping_index(i);
+            [p[i].pos(), seg_midpoint(p[i], p[j]), p[j].pos()]
+        };
+        for i in [4, 5] {
+            assert!(seg_samples(&polyline, i)
+                .iter()
+                .zip(seg_samples(&original, i).iter())
+                .all(|(a, b)| a.fuzzy_eq(*b)));
         }
 
-        self.remaining -= 1;
+        // total path length only changes by the segments joining the range (which now connect to
+        // the swapped range end vertexes)
+        let joining_length = |p: &Polyline<f64>| seg_length(p[0], p[1]) + seg_length(p[3], p[4]);
+        assert_fuzzy_eq!(
+            polyline.path_length() - joining_length(&polyline),
+            original.path_length() - joining_length(&original)
+        );
+        assert_fuzzy_eq!(joining_length(&polyline), 6.0 + 32.0f64.sqrt());
+
+        // reversing again restores the original
+        polyline.reverse_range(1, 3);
+        assert!(polyline
+            .iter()
+            .zip(original.iter())
+            .all(|(a, b)| a.fuzzy_eq(*b)));
+    }
 
-        if self.is_closed_first_pass {
-            self.is_closed_first_pass = false;
-            return Some((self.remaining, 0));
+    #[test]
+    fn reverse_range_whole_open_polyline() {
+        let mut polyline = Polyline::new();
+        polyline.add(0.0, 0.0, 0.5);
+        polyline.add(2.0, 0.0, 0.0);
+        polyline.add(3.0, 2.0, -1.0);
+        polyline.add(5.0, 2.0, 0.0);
+        let original = polyline.clone();
+
+        // reversing the whole range traces the same path in reverse
+        polyline.reverse_range(0, 3);
+        let length = original.path_length();
+        assert_fuzzy_eq!(polyline.path_length(), length);
+        for i in 0..=10 {
+            let d = length * i as f64 / 10.0;
+            let a = polyline.sample_at_arc_length(length - d).unwrap();
+            let b = original.sample_at_arc_length(d).unwrap();
+            assert_fuzzy_eq!(a, b);
         }
 
-        let pos = self.pos;
-        self.pos += 1;
-        Some((pos - 1, pos))
+        // closing the reversed polyline gives the opposite orientation
+        polyline.set_is_closed(true);
+        let mut closed = original.clone();
+        closed.set_is_closed(true);
+        polyline.last_mut().unwrap().bulge = 0.0;
+        closed.last_mut().unwrap().bulge = 0.0;
+        assert_fuzzy_eq!(polyline.area(), -closed.area());
     }
 
-    #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.remaining, Some(self.remaining))
+    #[test]
+    #[should_panic]
+    fn reverse_range_out_of_bounds() {
+        let mut polyline = Polyline::new();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(1.0, 0.0, 0.0);
+        polyline.reverse_range(0, 2);
     }
-}
 
-#[cfg(test)]
-mod tests {
+    #[test]
+    fn vertex_data_preserved_by_transforms() {
+        let mut polyline: Polyline<f64, u32> = [
+            PlineVertex::with_data(0.0, 0.0, 0.5, 10),
+            PlineVertex::with_data(2.0, 0.0, 0.0, 11),
+            PlineVertex::with_data(2.0, 2.0, -0.25, 12),
+        ]
+        .iter()
+        .copied()
+        .collect();
+        polyline.set_is_closed(true);
 
-    use std::f64::consts::PI;
+        polyline.translate(1.0, -1.0);
+        polyline.scale(2.0);
+        polyline.rotate(PI, Vector2::zero());
+        let data: Vec<u32> = polyline.iter().map(|v| v.data).collect();
+        assert_eq!(data, vec![10, 11, 12]);
+        assert_fuzzy_eq!(polyline[1].pos(), Vector2::new(-6.0, 2.0));
 
-    use super::*;
-    use crate::FuzzyEq;
+        // data stays attached to its vertex position while bulges shift to the new segments
+        let expected_area = polyline.without_data().area();
+        polyline.invert_direction();
+        let data: Vec<u32> = polyline.iter().map(|v| v.data).collect();
+        assert_eq!(data, vec![12, 11, 10]);
+        assert_fuzzy_eq!(polyline[0].pos(), Vector2::new(-6.0, -2.0));
+        assert_fuzzy_eq!(polyline[0].bulge, 0.0);
+        assert_fuzzy_eq!(polyline[1].bulge, -0.5);
+        assert_fuzzy_eq!(polyline[2].bulge, 0.25);
+        assert_fuzzy_eq!(polyline.without_data().area(), -expected_area);
+    }
 
     #[test]
-    fn iter_segments() {
-        let mut polyline = Polyline::<f64>::new();
-        assert_eq!(polyline.iter_segments().size_hint(), (0, Some(0)));
-        assert_eq!(polyline.iter_segments().collect::<Vec<_>>().len(), 0);
+    fn try_visit_segments_first_arc_index() {
+        let mut polyline = Polyline::new_closed();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(4.0, 0.0, 0.0);
+        polyline.add(4.0, 4.0, 0.5);
+        polyline.add(0.0, 4.0, -0.5);
+
+        let mut visited = 0;
+        let first_arc = polyline.try_visit_segments(|v1, _| {
+            visited += 1;
+            if v1.bulge_is_zero() {
+                ControlFlow::Continue(())
+            } else {
+                ControlFlow::Break(polyline.iter().position(|v| v.fuzzy_eq(v1)).unwrap())
+            }
+        });
+        // closing segment is visited first and is an arc
+        assert_eq!(first_arc, Some(3));
+        assert_eq!(visited, 1);
+
+        polyline[3].bulge = 0.0;
+        visited = 0;
+        let first_arc = polyline.try_visit_segments(|v1, _| {
+            visited += 1;
+            if v1.bulge_is_zero() {
+                ControlFlow::Continue(())
+            } else {
+                ControlFlow::Break(polyline.iter().position(|v| v.fuzzy_eq(v1)).unwrap())
+            }
+        });
+        assert_eq!(first_arc, Some(2));
+        assert_eq!(visited, 4);
 
-        polyline.add(1.0, 2.0, 0.3);
-        assert_eq!(polyline.iter_segments().size_hint(), (0, Some(0)));
-        assert_eq!(polyline.iter_segments().collect::<Vec<_>>().len(), 0);
+        polyline[2].bulge = 0.0;
+        let no_arc: Option<usize> = polyline.try_visit_segments(|v1, _| {
+            if v1.bulge_is_zero() {
+                ControlFlow::Continue(())
+            } else {
+                ControlFlow::Break(0)
+            }
+        });
+        assert_eq!(no_arc, None);
+    }
 
-        polyline.add(4.0, 5.0, 0.6);
-        assert_eq!(polyline.iter_segments().size_hint(), (1, Some(1)));
-        let one_seg = polyline.iter_segments().collect::<Vec<_>>();
-        assert_eq!(one_seg.len(), 1);
-        assert_fuzzy_eq!(one_seg[0].0, PlineVertex::new(1.0, 2.0, 0.3));
-        assert_fuzzy_eq!(one_seg[0].1, PlineVertex::new(4.0, 5.0, 0.6));
+    #[test]
+    fn offset_ring_area_circle() {
+        let mut circle = Polyline::new_closed();
+        circle.add(-5.0, 0.0, 1.0);
+        circle.add(5.0, 0.0, 1.0);
+        let expected = PI * (7.0 * 7.0 - 4.0 * 4.0);
+        assert_fuzzy_eq!(circle.offset_ring_area(1.0, -2.0), expected);
+        assert_fuzzy_eq!(circle.offset_ring_area(-2.0, 1.0), expected);
+
+        // clockwise direction flips the offset sign
+        let mut cw_circle = circle.clone();
+        cw_circle.invert_direction();
+        assert_fuzzy_eq!(cw_circle.offset_ring_area(-1.0, 2.0), expected);
+
+        // inward offset beyond the radius collapses the circle
+        assert_fuzzy_eq!(circle.offset_ring_area(6.0, 0.0), PI * 25.0);
+
+        let mut open = circle.clone();
+        open.set_is_closed(false);
+        assert_fuzzy_eq!(open.offset_ring_area(1.0, -2.0), 0.0);
+    }
 
-        polyline.set_is_closed(true);
-        assert_eq!(polyline.iter_segments().size_hint(), (2, Some(2)));
-        let two_seg = polyline.iter_segments().collect::<Vec<_>>();
-        assert_eq!(two_seg.len(), 2);
-        assert_fuzzy_eq!(two_seg[0].0, PlineVertex::new(4.0, 5.0, 0.6));
-        assert_fuzzy_eq!(two_seg[0].1, PlineVertex::new(1.0, 2.0, 0.3));
-        assert_fuzzy_eq!(two_seg[1].0, PlineVertex::new(1.0, 2.0, 0.3));
-        assert_fuzzy_eq!(two_seg[1].1, PlineVertex::new(4.0, 5.0, 0.6));
+    #[test]
+    fn offset_ring_area_split_offset() {
+        // dumbbell shape which splits into two polylines when offset inward
+        let mut polyline = Polyline::new_closed();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(4.0, 0.0, 0.0);
+        polyline.add(4.0, 1.5, 0.0);
+        polyline.add(6.0, 1.5, 0.0);
+        polyline.add(6.0, 0.0, 0.0);
+        polyline.add(10.0, 0.0, 0.0);
+        polyline.add(10.0, 4.0, 0.0);
+        polyline.add(6.0, 4.0, 0.0);
+        polyline.add(6.0, 2.5, 0.0);
+        polyline.add(4.0, 2.5, 0.0);
+        polyline.add(4.0, 4.0, 0.0);
+        polyline.add(0.0, 4.0, 0.0);
+
+        let inward = polyline.parallel_offset(1.0, None);
+        assert_eq!(inward.len(), 2);
+        let inward_area: f64 = inward.iter().map(|p| p.area()).sum();
+        // two 2x2 squares with rounded bumps from the concave neck corners
+        assert!(inward_area > 8.0 && inward_area < 9.0);
+        assert_fuzzy_eq!(
+            polyline.offset_ring_area(1.0, 0.0),
+            polyline.area() - inward_area
+        );
+    }
 
-        polyline.add(0.5, 0.5, 0.5);
-        assert_eq!(polyline.iter_segments().size_hint(), (3, Some(3)));
-        let three_seg = polyline.iter_segments().collect::<Vec<_>>();
-        assert_fuzzy_eq!(three_seg[0].0, PlineVertex::new(0.5, 0.5, 0.5));
-        assert_fuzzy_eq!(three_seg[0].1, PlineVertex::new(1.0, 2.0, 0.3));
-        assert_fuzzy_eq!(three_seg[1].0, PlineVertex::new(1.0, 2.0, 0.3));
-        assert_fuzzy_eq!(three_seg[1].1, PlineVertex::new(4.0, 5.0, 0.6));
-        assert_fuzzy_eq!(three_seg[2].0, PlineVertex::new(4.0, 5.0, 0.6));
-        assert_fuzzy_eq!(three_seg[2].1, PlineVertex::new(0.5, 0.5, 0.5));
+    #[test]
+    fn area_as_closed_matches_closed_area() {
+        let mut polyline = Polyline::new();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(4.0, 0.0, 1.0);
+        polyline.add(4.0, 4.0, 0.0);
+        polyline.add(0.0, 4.0, 0.5);
+        assert_fuzzy_eq!(polyline.area(), 0.0);
+        let area_as_closed = polyline.area_as_closed();
+        assert!(!polyline.is_closed());
+
+        // closing segment is a line so the last bulge must be zero to match
+        let mut closed = polyline.clone();
+        closed.last_mut().unwrap().bulge = 0.0;
+        closed.set_is_closed(true);
+        assert_fuzzy_eq!(area_as_closed, closed.area());
+        assert_fuzzy_eq!(closed.area_as_closed(), closed.area());
+        assert_fuzzy_eq!(area_as_closed, 16.0 + PI * 2.0);
+
+        let mut single = Polyline::new();
+        single.add(1.0, 1.0, 0.0);
+        assert_fuzzy_eq!(single.area_as_closed(), 0.0);
+    }
+
+    #[test]
+    fn hausdorff_distance_shifted() {
+        let mut polyline = Polyline::new_closed();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(4.0, 0.0, 1.0);
+        polyline.add(4.0, 4.0, 0.0);
+        polyline.add(0.0, 4.0, 0.0);
+
+        assert_fuzzy_eq!(polyline.hausdorff_distance(&polyline, 50), 0.0);
+
+        let mut shifted = polyline.clone();
+        shifted.translate(0.0, 0.5);
+        // bottom edge samples are the furthest from the shifted copy
+        assert_fuzzy_eq!(polyline.hausdorff_distance(&shifted, 50), 0.5);
+        assert_fuzzy_eq!(shifted.hausdorff_distance(&polyline, 50), 0.5);
+
+        let mut open = Polyline::new();
+        open.add(0.0, 0.0, 0.0);
+        open.add(3.0, 4.0, 0.0);
+        let mut open_shifted = open.clone();
+        open_shifted.translate(3.0, 4.0);
+        assert_fuzzy_eq!(open.hausdorff_distance(&open_shifted, 2), 5.0);
+
+        assert_eq!(
+            open.hausdorff_distance(&Polyline::new(), 10),
+            f64::max_value()
+        );
+    }
+
+    #[test]
+    fn resample_count_evenly_spaced() {
+        let mut line = Polyline::new();
+        line.add(0.0, 0.0, 0.0);
+        line.add(1.0, 0.0, 0.0);
+        let points = line.resample_count(5);
+        assert_eq!(points.len(), 5);
+        for (i, expected_x) in [0.0, 0.25, 0.5, 0.75, 1.0].iter().enumerate() {
+            assert_fuzzy_eq!(points[i], Vector2::new(*expected_x, 0.0));
+        }
+
+        assert!(line.resample_count(0).is_empty());
+        assert_eq!(line.resample_count(1), vec![Vector2::new(0.0, 0.0)]);
+        assert!(Polyline::<f64>::new().resample_count(3).is_empty());
+
+        // closed circle ends back at the start
+        let mut circle = Polyline::new_closed();
+        circle.add(0.0, 0.0, 1.0);
+        circle.add(2.0, 0.0, 1.0);
+        let points = circle.resample_count(5);
+        assert_eq!(points.len(), 5);
+        assert_fuzzy_eq!(points[1], Vector2::new(1.0, -1.0));
+        assert_fuzzy_eq!(points[2], Vector2::new(2.0, 0.0));
+        assert_fuzzy_eq!(points[3], Vector2::new(1.0, 1.0));
+        assert_fuzzy_eq!(points[4], Vector2::new(0.0, 0.0));
+    }
+
+    #[test]
+    fn winding_number_checked_on_arc() {
+        let mut polyline = Polyline::new_closed();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(2.0, 0.0, 1.0);
+        polyline.add(2.0, 2.0, 0.0);
+        polyline.add(0.0, 2.0, 0.0);
+        let eps = 1e-5;
+
+        // point exactly on the arc bulging out from x = 2
+        let on_arc = Vector2::new(3.0, 1.0);
+        assert_eq!(
+            polyline.winding_number_checked(on_arc, eps),
+            WindingResult::OnBoundary
+        );
+        assert_eq!(
+            polyline.winding_number_checked(Vector2::new(2.5, 1.0), eps),
+            WindingResult::Winding(1)
+        );
+        assert_eq!(
+            polyline.winding_number_checked(Vector2::new(3.5, 1.0), eps),
+            WindingResult::Winding(0)
+        );
+
+        polyline.invert_direction();
+        assert_eq!(
+            polyline.winding_number_checked(on_arc, eps),
+            WindingResult::OnBoundary
+        );
+        assert_eq!(
+            polyline.winding_number_checked(Vector2::new(2.5, 1.0), eps),
+            WindingResult::Winding(-1)
+        );
 
         polyline.set_is_closed(false);
-        assert_eq!(polyline.iter_segments().size_hint(), (2, Some(2)));
-        let two_seg_open = polyline.iter_segments().collect::<Vec<_>>();
-        assert_fuzzy_eq!(two_seg_open[0].0, PlineVertex::new(1.0, 2.0, 0.3));
-        assert_fuzzy_eq!(two_seg_open[0].1, PlineVertex::new(4.0, 5.0, 0.6));
-        assert_fuzzy_eq!(two_seg_open[1].0, PlineVertex::new(4.0, 5.0, 0.6));
-        assert_fuzzy_eq!(two_seg_open[1].1, PlineVertex::new(0.5, 0.5, 0.5));
+        assert_eq!(
+            polyline.winding_number_checked(on_arc, eps),
+            WindingResult::Winding(0)
+        );
     }
 
     #[test]
-    fn iter_segment_indexes() {
-        let mut polyline = Polyline::<f64>::new();
-        assert_eq!(polyline.iter_segment_indexes().size_hint(), (0, Some(0)));
-        assert_eq!(polyline.iter_segment_indexes().collect::<Vec<_>>().len(), 0);
+    fn geom_eq_tiny_bulge() {
+        let mut line = Polyline::new();
+        line.add(0.0, 0.0, 0.0);
+        line.add(10.0, 0.0, 0.0);
+        line.add(10.0, 5.0, 0.0);
+        let mut tiny_arc = line.clone();
+        tiny_arc[0].bulge = 1e-12;
+        assert!(!line.fuzzy_eq_eps(&tiny_arc, 1e-14));
+        assert!(line.geom_eq(&tiny_arc, 1e-5));
+        assert!(tiny_arc.geom_eq(&line, 1e-5));
+
+        // visible arc is not equal
+        let mut arc = line.clone();
+        arc[0].bulge = 0.1;
+        assert!(!line.geom_eq(&arc, 1e-5));
+
+        // open vs closed is not equal
+        let mut closed = line.clone();
+        closed.set_is_closed(true);
+        assert!(!line.geom_eq(&closed, 1e-5));
+
+        // same points but shorter path (missing last segment) is not equal
+        let mut shorter = line.clone();
+        shorter.remove_last();
+        assert!(!line.geom_eq(&shorter, 1e-5));
+        assert!(Polyline::<f64>::new().geom_eq(&Polyline::new(), 1e-5));
+    }
 
-        polyline.add(1.0, 2.0, 0.3);
-        assert_eq!(polyline.iter_segment_indexes().size_hint(), (0, Some(0)));
-        assert_eq!(polyline.iter_segment_indexes().collect::<Vec<_>>().len(), 0);
+    #[test]
+    fn max_inscribed_circle_square() {
+        let mut square = Polyline::new_closed();
+        square.add(0.0, 0.0, 0.0);
+        square.add(4.0, 0.0, 0.0);
+        square.add(4.0, 4.0, 0.0);
+        square.add(0.0, 4.0, 0.0);
+        let tolerance = 1e-3;
+        let (center, radius) = square.max_inscribed_circle(tolerance).unwrap();
+        assert_fuzzy_eq!(center, Vector2::new(2.0, 2.0));
+        assert_fuzzy_eq!(radius, 2.0);
+
+        // same result for clockwise direction
+        square.invert_direction();
+        let (_, radius) = square.max_inscribed_circle(tolerance).unwrap();
+        assert_fuzzy_eq!(radius, 2.0);
+
+        // L shape, largest circle is in the corner of the L touching the outer sides and the inner
+        // corner at (2, 2), radius r satisfies r = sqrt(2) * (2 - r)
+        let mut l_shape = Polyline::new_closed();
+        l_shape.add(0.0, 0.0, 0.0);
+        l_shape.add(10.0, 0.0, 0.0);
+        l_shape.add(10.0, 2.0, 0.0);
+        l_shape.add(2.0, 2.0, 0.0);
+        l_shape.add(2.0, 10.0, 0.0);
+        l_shape.add(0.0, 10.0, 0.0);
+        let (center, radius) = l_shape.max_inscribed_circle(tolerance).unwrap();
+        let expected_radius = 4.0 - 2.0 * 2.0f64.sqrt();
+        assert!(radius <= expected_radius + 1e-9);
+        assert!(radius > expected_radius - tolerance);
+        assert!(l_shape.contains_point(center, OnBoundary::Outside, 1e-5));
+
+        square.set_is_closed(false);
+        assert!(square.max_inscribed_circle(tolerance).is_none());
+    }
 
-        polyline.add(4.0, 5.0, 0.6);
-        assert_eq!(polyline.iter_segment_indexes().size_hint(), (1, Some(1)));
-        let one_seg = polyline.iter_segment_indexes().collect::<Vec<_>>();
-        assert_eq!(one_seg, vec![(0, 1)]);
+    #[test]
+    fn split_at_self_intersects_at_shared_vertex() {
+        // two triangles touching at the vertex (1, 1) which is passed through twice
+        let mut polyline = Polyline::new_closed();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(1.0, 1.0, 0.0);
+        polyline.add(2.0, 0.0, 0.0);
+        polyline.add(2.0, 2.0, 0.0);
+        polyline.add(1.0, 1.0, 0.0);
+        polyline.add(0.0, 2.0, 0.0);
+
+        let loops = polyline.split_at_self_intersects();
+        assert_eq!(loops.len(), 2);
+        for l in loops.iter() {
+            assert_eq!(l.len(), 3);
+            assert_fuzzy_eq!(l.area(), 1.0);
+        }
+    }
+
+    #[test]
+    fn split_at_self_intersects_figure_eight() {
+        // figure eight with arcs, crossing at (1, 1)
+        let mut polyline = Polyline::new_closed();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(2.0, 2.0, -0.5);
+        polyline.add(2.0, 0.0, 0.0);
+        polyline.add(0.0, 2.0, 0.5);
+        assert_eq!(polyline.find_self_intersects().len(), 1);
+
+        let loops = polyline.split_at_self_intersects();
+        assert_eq!(loops.len(), 2);
+        for l in loops.iter() {
+            assert!(l.is_closed());
+            assert!(!l.self_intersects(None));
+        }
+
+        // one loop winds clockwise and one counter clockwise
+        assert!(loops[0].area() * loops[1].area() < 0.0);
+        assert_fuzzy_eq!(loops[0].area() + loops[1].area(), polyline.area());
+        assert_fuzzy_eq!(
+            loops[0].path_length() + loops[1].path_length(),
+            polyline.path_length()
+        );
+
+        // polylines without self intersects are returned unchanged
+        let mut square = Polyline::new_closed();
+        square.add(0.0, 0.0, 0.0);
+        square.add(1.0, 0.0, 0.0);
+        square.add(1.0, 1.0, 0.0);
+        square.add(0.0, 1.0, 0.0);
+        let result = square.split_at_self_intersects();
+        assert_eq!(result.len(), 1);
+        assert!(result[0].fuzzy_eq(&square));
+    }
 
+    #[test]
+    fn continuity_at_line_arc_junction() {
+        let quarter_bulge = (PI / 8.0).tan();
+        let mut polyline = Polyline::new();
+        polyline.add(0.0, 0.0, 0.0);
+        // counter clockwise arcs around (2, 2) starting tangent to the line
+        polyline.add(2.0, 0.0, quarter_bulge);
+        polyline.add(4.0, 2.0, quarter_bulge);
+        polyline.add(2.0, 4.0, 0.0);
+        let eps = 1e-5;
+
+        assert_eq!(polyline.continuity_at(0, eps), Continuity::G2);
+        assert_eq!(polyline.continuity_at(1, eps), Continuity::G1);
+        assert_eq!(polyline.continuity_at(2, eps), Continuity::G2);
+        assert_eq!(polyline.continuity_at(3, eps), Continuity::G2);
+
+        // closing line segment forms corners at both ends
         polyline.set_is_closed(true);
-        assert_eq!(polyline.iter_segment_indexes().size_hint(), (2, Some(2)));
-        let two_seg = polyline.iter_segment_indexes().collect::<Vec<_>>();
-        assert_eq!(two_seg, vec![(1, 0), (0, 1)]);
+        assert_eq!(polyline.continuity_at(3, eps), Continuity::G0);
+        assert_eq!(polyline.continuity_at(0, eps), Continuity::G0);
 
-        polyline.add(0.5, 0.5, 0.5);
-        assert_eq!(polyline.iter_segment_indexes().size_hint(), (3, Some(3)));
-        let three_seg = polyline.iter_segment_indexes().collect::<Vec<_>>();
-        assert_eq!(three_seg, vec![(2, 0), (0, 1), (1, 2)]);
+        // line continuing into an arc with the wrong tangent
+        polyline[1].bulge = -quarter_bulge;
+        assert_eq!(polyline.continuity_at(1, eps), Continuity::G0);
+    }
 
-        polyline.set_is_closed(false);
-        assert_eq!(polyline.iter_segment_indexes().size_hint(), (2, Some(2)));
-        let two_seg_open = polyline.iter_segment_indexes().collect::<Vec<_>>();
-        assert_eq!(two_seg_open, vec![(0, 1), (1, 2)]);
+    #[test]
+    fn to_primitives_circle() {
+        let mut circle = Polyline::new_closed();
+        circle.add(0.0, 0.0, 1.0);
+        circle.add(2.0, 0.0, 1.0);
+        let primitives = circle.to_primitives();
+        assert_eq!(primitives.len(), 2);
+        for (i, prim) in primitives.iter().enumerate() {
+            match *prim {
+                Primitive::Arc {
+                    center,
+                    radius,
+                    start_angle,
+                    end_angle,
+                    ccw,
+                } => {
+                    assert_fuzzy_eq!(center, Vector2::new(1.0, 0.0));
+                    assert_fuzzy_eq!(radius, 1.0);
+                    assert!(ccw);
+                    let (expected_start, expected_end) = if i == 0 { (PI, 0.0) } else { (0.0, PI) };
+                    assert_fuzzy_eq!(normalize_radians(start_angle), expected_start);
+                    assert_fuzzy_eq!(normalize_radians(end_angle), expected_end);
+                }
+                Primitive::Line(..) => panic!("expected arc primitive"),
+            }
+        }
+
+        // lines resolve to their end points
+        let mut polyline = Polyline::new();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(3.0, 4.0, 0.0);
+        assert_eq!(
+            polyline.to_primitives(),
+            vec![Primitive::Line(
+                Vector2::new(0.0, 0.0),
+                Vector2::new(3.0, 4.0)
+            )]
+        );
     }
 
     #[test]
-    fn invert_direction() {
+    fn from_primitives_round_trip() {
         let mut polyline = Polyline::new_closed();
-        polyline.add(0.0, 0.0, 0.1);
-        polyline.add(2.0, 0.0, 0.2);
-        polyline.add(2.0, 2.0, 0.3);
-        polyline.add(0.0, 2.0, 0.4);
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(5.0, 0.0, 0.5);
+        polyline.add(5.0, 5.0, -0.3);
+        polyline.add(2.0, 6.0, 0.0);
+        polyline.add(0.0, 5.0, 0.2);
 
-        polyline.invert_direction();
+        let round_trip = Polyline::from_primitives(&polyline.to_primitives(), 1e-5).unwrap();
+        assert!(round_trip.is_closed());
+        assert!(round_trip.fuzzy_eq_eps(&polyline, 1e-9));
+        assert_eq!(round_trip.len(), polyline.len());
 
-        assert_fuzzy_eq!(polyline[0], PlineVertex::new(0.0, 2.0, -0.3));
-        assert_fuzzy_eq!(polyline[1], PlineVertex::new(2.0, 2.0, -0.2));
-        assert_fuzzy_eq!(polyline[2], PlineVertex::new(2.0, 0.0, -0.1));
-        assert_fuzzy_eq!(polyline[3], PlineVertex::new(0.0, 0.0, -0.4));
+        polyline.set_is_closed(false);
+        // bulge of the last vertex is unused for open polylines
+        polyline.last_mut().unwrap().bulge = 0.0;
+        let round_trip = Polyline::from_primitives(&polyline.to_primitives(), 1e-5).unwrap();
+        assert!(!round_trip.is_closed());
+        assert_eq!(round_trip.len(), polyline.len());
+        assert!(round_trip.fuzzy_eq_eps(&polyline, 1e-9));
+
+        // single full circle arc
+        let circle = [Primitive::Arc {
+            center: Vector2::new(1.0, 1.0),
+            radius: 2.0,
+            start_angle: 0.0,
+            end_angle: 0.0,
+            ccw: false,
+        }];
+        let polyline = Polyline::from_primitives(&circle, 1e-5).unwrap();
+        assert!(polyline.is_closed());
+        assert_eq!(polyline.len(), 2);
+        assert_fuzzy_eq!(polyline.area(), -4.0 * PI);
+
+        assert_eq!(
+            Polyline::<f64>::from_primitives(&[], 1e-5).unwrap().len(),
+            0
+        );
     }
 
     #[test]
-    fn area() {
-        {
-            let mut circle = Polyline::new_closed();
-            circle.add(0.0, 0.0, 1.0);
-            circle.add(2.0, 0.0, 1.0);
-            assert_fuzzy_eq!(circle.area(), PI);
-            circle.invert_direction();
-            assert_fuzzy_eq!(circle.area(), -PI);
-        }
+    fn scale_about_center_stays_centered() {
+        let mut square = Polyline::new_closed();
+        square.add(1.0, 1.0, 0.0);
+        square.add(3.0, 1.0, 0.5);
+        square.add(3.0, 3.0, 0.0);
+        square.add(1.0, 3.0, 0.0);
+        let area = square.area();
+        let center = Vector2::new(2.0, 2.0);
+
+        square.scale_about(3.0, center);
+        assert_fuzzy_eq!(square[0].pos(), Vector2::new(-1.0, -1.0));
+        assert_fuzzy_eq!(square[2].pos(), Vector2::new(5.0, 5.0));
+        assert_fuzzy_eq!(square[1].bulge, 0.5);
+        assert_fuzzy_eq!(square.area(), 9.0 * area);
+
+        square.scale_about(1.0 / 3.0, center);
+        assert_fuzzy_eq!(square[0].pos(), Vector2::new(1.0, 1.0));
+        assert_fuzzy_eq!(square[2].pos(), Vector2::new(3.0, 3.0));
+    }
 
-        {
-            let mut half_circle = Polyline::new_closed();
-            half_circle.add(0.0, 0.0, -1.0);
-            half_circle.add(2.0, 0.0, 0.0);
-            assert_fuzzy_eq!(half_circle.area(), -0.5 * PI);
-            half_circle.invert_direction();
-            assert_fuzzy_eq!(half_circle.area(), 0.5 * PI);
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn arcs_to_approx_lines_par_matches_serial() {
+        let mut polyline = Polyline::new();
+        for i in 0..2000 {
+            let bulge = match i % 3 {
+                0 => 0.0,
+                1 => 0.4,
+                _ => -0.9,
+            };
+            polyline.add(i as f64, (i % 5) as f64, bulge);
         }
 
-        {
-            let mut square = Polyline::new_closed();
-            square.add(0.0, 0.0, 0.0);
-            square.add(2.0, 0.0, 0.0);
-            square.add(2.0, 2.0, 0.0);
-            square.add(0.0, 2.0, 0.0);
-            assert_fuzzy_eq!(square.area(), 4.0);
-            square.invert_direction();
-            assert_fuzzy_eq!(square.area(), -4.0);
-        }
+        let assert_identical = |a: &Polyline<f64>, b: &Polyline<f64>| {
+            assert_eq!(a.is_closed(), b.is_closed());
+            assert_eq!(a.len(), b.len());
+            assert!(a
+                .iter()
+                .zip(b.iter())
+                .all(|(u, v)| u.x == v.x && u.y == v.y && u.bulge == v.bulge));
+        };
 
-        {
-            let mut open_polyline = Polyline::new();
-            open_polyline.add(0.0, 0.0, 0.0);
-            open_polyline.add(2.0, 0.0, 0.0);
-            open_polyline.add(2.0, 2.0, 0.0);
-            open_polyline.add(0.0, 2.0, 0.0);
-            assert_fuzzy_eq!(open_polyline.area(), 0.0);
-            open_polyline.invert_direction();
-            assert_fuzzy_eq!(open_polyline.area(), 0.0);
+        for &error_distance in &[1e-2, 1e-4] {
+            let serial = polyline.arcs_to_approx_lines(error_distance).unwrap();
+            let par = polyline.arcs_to_approx_lines_par(error_distance).unwrap();
+            assert!(serial.len() > polyline.len());
+            assert_identical(&serial, &par);
         }
 
-        {
-            let empty_open_polyline = Polyline::<f64>::new();
-            assert_fuzzy_eq!(empty_open_polyline.area(), 0.0);
+        polyline.set_is_closed(true);
+        let serial = polyline.arcs_to_approx_lines(1e-3).unwrap();
+        let par = polyline.arcs_to_approx_lines_par(1e-3).unwrap();
+        assert_identical(&serial, &par);
+
+        let empty = Polyline::<f64>::new();
+        assert_eq!(empty.arcs_to_approx_lines_par(1e-3).unwrap().len(), 0);
+    }
+
+    #[test]
+    fn flatten_iter_matches_approx_lines() {
+        let mut polyline = Polyline::new();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(5.0, 0.0, 1.0);
+        polyline.add(5.0, 5.0, -0.4);
+        polyline.add(2.0, 6.0, 0.0);
+        polyline.add(0.0, 5.0, 0.8);
+
+        for &is_closed in &[false, true] {
+            polyline.set_is_closed(is_closed);
+            for &error_distance in &[0.1, 1e-3] {
+                let approx = polyline.arcs_to_approx_lines(error_distance).unwrap();
+                let mut expected: Vec<_> = approx.iter().map(|v| v.pos()).collect();
+                if is_closed {
+                    expected.push(expected[0]);
+                }
+                let points: Vec<_> = polyline.flatten_iter(error_distance).collect();
+                assert!(expected.len() > polyline.len() + 1);
+                assert_eq!(points, expected);
+                assert_eq!(polyline.flatten_to_points(error_distance), expected);
+            }
         }
 
-        {
-            let empty_closed_polyline = Polyline::<f64>::new_closed();
-            assert_fuzzy_eq!(empty_closed_polyline.area(), 0.0);
+        assert_eq!(Polyline::<f64>::new().flatten_iter(0.1).count(), 0);
+        let mut single = Polyline::new();
+        single.add(1.0, 2.0, 0.0);
+        assert_eq!(
+            single.flatten_iter(0.1).collect::<Vec<_>>(),
+            vec![Vector2::new(1.0, 2.0)]
+        );
+    }
+
+    #[test]
+    fn closest_point_in_range_single_segment() {
+        let mut polyline = Polyline::new();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(10.0, 0.0, 0.0);
+        polyline.add(10.0, 10.0, 0.0);
+        polyline.add(0.0, 10.0, 0.0);
+
+        // point is globally closest to segment 2 but only segment 0 is scanned
+        let point = Vector2::new(4.0, 9.0);
+        let global = polyline.closest_point(point).unwrap();
+        assert_eq!(global.seg_start_index, 2);
+        let result = polyline.closest_point_in_range(point, 0, 0).unwrap();
+        assert_eq!(result.seg_start_index, 0);
+        assert_fuzzy_eq!(result.seg_point, Vector2::new(4.0, 0.0));
+        assert_fuzzy_eq!(result.seg_t, 0.4);
+        assert_fuzzy_eq!(result.distance, 9.0);
+
+        // full range matches closest_point
+        let full = polyline.closest_point_in_range(point, 0, 2).unwrap();
+        assert_eq!(full.seg_start_index, global.seg_start_index);
+        assert_fuzzy_eq!(full.seg_point, global.seg_point);
+
+        // open polylines do not wrap and have no closing segment
+        assert!(polyline.closest_point_in_range(point, 2, 0).is_none());
+        assert!(polyline.closest_point_in_range(point, 3, 3).is_none());
+
+        polyline.set_is_closed(true);
+        let result = polyline
+            .closest_point_in_range(Vector2::new(1.0, 4.0), 3, 0)
+            .unwrap();
+        assert_eq!(result.seg_start_index, 3);
+        assert_fuzzy_eq!(result.seg_point, Vector2::new(0.0, 4.0));
+        assert_fuzzy_eq!(result.seg_t, 0.6);
+
+        // non-finite values are only checked within the range
+        polyline.set_vertex(2, 10.0, f64::NAN, 0.0);
+        assert!(polyline.closest_point_in_range(point, 0, 0).is_some());
+        assert!(polyline.closest_point_in_range(point, 0, 1).is_none());
+        assert!(polyline.closest_point_in_range(point, 2, 2).is_none());
+    }
+
+    #[test]
+    fn closest_point_indexed_matches_brute_force() {
+        // wavy closed polyline with a mix of lines and arcs
+        let mut polyline = Polyline::new_closed();
+        let count = 1000;
+        for i in 0..count {
+            let angle = 2.0 * PI * i as f64 / count as f64;
+            let radius = 50.0 + 5.0 * (angle * 13.0).sin();
+            let bulge = if i % 3 == 0 {
+                0.0
+            } else {
+                0.2 * (angle * 7.0).cos()
+            };
+            polyline.add(radius * angle.cos(), radius * angle.sin(), bulge);
         }
 
-        {
-            let mut one_vertex_open_polyline = Polyline::<f64>::new();
-            one_vertex_open_polyline.add(1.0, 1.0, 0.0);
-            assert_fuzzy_eq!(one_vertex_open_polyline.area(), 0.0);
+        let index = polyline.create_approx_spatial_index().unwrap();
+        for i in 0..200 {
+            let t = i as f64 * 0.37;
+            let r = 70.0 * ((i as f64 * 0.61).sin() + 0.2);
+            let point = Vector2::new(r * t.cos(), r * t.sin());
+            let expected = polyline.closest_point(point).unwrap();
+            let result = polyline.closest_point_indexed(point, &index).unwrap();
+            assert_fuzzy_eq!(result.distance, expected.distance);
+            assert_fuzzy_eq!(result.seg_point, expected.seg_point);
+            let seg_start = result.seg_start_index;
+            let seg_end = polyline.next_wrapping_index(seg_start);
+            let cp = seg_closest_point(polyline[seg_start], polyline[seg_end], point);
+            assert_fuzzy_eq!(cp, result.seg_point);
         }
 
-        {
-            let mut one_vertex_closed_polyline = Polyline::<f64>::new_closed();
-            one_vertex_closed_polyline.add(1.0, 1.0, 0.0);
-            assert_fuzzy_eq!(one_vertex_closed_polyline.area(), 0.0);
+        polyline.set_is_closed(false);
+        let index = polyline.create_approx_spatial_index().unwrap();
+        let point = Vector2::new(60.0, -0.5);
+        let expected = polyline.closest_point(point).unwrap();
+        let result = polyline.closest_point_indexed(point, &index).unwrap();
+        assert_eq!(result.seg_start_index, expected.seg_start_index);
+        assert_fuzzy_eq!(result.seg_t, expected.seg_t);
+        assert_fuzzy_eq!(result.distance, expected.distance);
+
+        // non-finite values on a visited segment
+        let mut square = Polyline::new_closed();
+        square.add(0.0, 0.0, 0.0);
+        square.add(2.0, 0.0, 0.0);
+        square.add(2.0, 2.0, 0.0);
+        square.add(0.0, 2.0, 0.0);
+        let index = square.create_approx_spatial_index().unwrap();
+        square.set_vertex(1, 2.0, 0.0, f64::NAN);
+        assert!(square
+            .closest_point_indexed(Vector2::new(2.5, 1.0), &index)
+            .is_none());
+    }
+
+    #[test]
+    fn find_overlaps_doubling_back() {
+        // path goes out along the x axis, loops around and comes back along the same line
+        let mut polyline = Polyline::new();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(5.0, 0.0, 1.0);
+        polyline.add(5.0, 2.0, 0.0);
+        polyline.add(4.0, 2.0, 0.0);
+        polyline.add(4.0, 0.0, 0.0);
+        polyline.add(1.0, 0.0, 0.0);
+        assert_eq!(polyline.find_overlaps(1e-5), vec![(0, 4)]);
+
+        // doubling back on the immediately following segment
+        let mut polyline = Polyline::new();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(3.0, 0.0, 0.0);
+        polyline.add(1.0, 0.0, 0.0);
+        assert_eq!(polyline.find_overlaps(1e-5), vec![(0, 1)]);
+
+        // arc going back along itself
+        let mut polyline = Polyline::new();
+        polyline.add(0.0, 0.0, 1.0);
+        polyline.add(2.0, 0.0, -(PI / 8.0).tan());
+        polyline.add(1.0, -1.0, 0.0);
+        assert_eq!(polyline.find_overlaps(1e-5), vec![(0, 1)]);
+
+        // touching at a point is not an overlap
+        let mut polyline = Polyline::new_closed();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(2.0, 0.0, 0.0);
+        polyline.add(2.0, 2.0, 0.0);
+        polyline.add(1.0, 0.0, 0.0);
+        polyline.add(0.0, 2.0, 0.0);
+        assert!(polyline.find_overlaps(1e-5).is_empty());
+    }
+
+    #[test]
+    fn snap_arc_sweeps_to_right_angle() {
+        let mut polyline = Polyline::new_closed();
+        polyline.add(0.0, 0.0, bulge_from_angle(89f64.to_radians()));
+        polyline.add(4.0, 0.0, 0.0);
+        polyline.add(4.0, 4.0, -bulge_from_angle(89f64.to_radians()));
+        polyline.add(0.0, 4.0, 0.0);
+        polyline.snap_arc_sweeps(PI / 2.0);
+        assert_fuzzy_eq!(polyline[0].bulge, bulge_from_angle(PI / 2.0));
+        assert_fuzzy_eq!(polyline[1].bulge, 0.0);
+        assert_fuzzy_eq!(polyline[2].bulge, -bulge_from_angle(PI / 2.0));
+        assert_fuzzy_eq!(polyline[3].bulge, 0.0);
+        assert_fuzzy_eq!(polyline[0].pos(), Vector2::new(0.0, 0.0));
+        assert_fuzzy_eq!(polyline[1].pos(), Vector2::new(4.0, 0.0));
+
+        // small arcs round down to lines, near full circles do not snap to a full circle
+        let mut polyline = Polyline::new();
+        polyline.add(0.0, 0.0, bulge_from_angle(10f64.to_radians()));
+        polyline.add(1.0, 0.0, bulge_from_angle(350f64.to_radians()));
+        polyline.add(2.0, 0.0, 0.7);
+        polyline.snap_arc_sweeps(PI / 2.0);
+        assert_fuzzy_eq!(polyline[0].bulge, 0.0);
+        assert_fuzzy_eq!(angle_from_bulge(polyline[1].bulge), 1.5 * PI);
+        // last vertex of an open polyline is not the start of a segment
+        assert_fuzzy_eq!(polyline[2].bulge, 0.7);
+    }
+
+    #[test]
+    fn segment_areas_sum_to_area() {
+        let mut polyline = Polyline::new_closed();
+        polyline.add(1.0, 1.0, 0.0);
+        polyline.add(6.0, 1.0, 0.5);
+        polyline.add(6.0, 4.0, 0.0);
+        polyline.add(4.0, 5.0, -0.3);
+        polyline.add(1.0, 4.0, 0.0);
+
+        for _ in 0..2 {
+            let areas = polyline.segment_areas();
+            assert_eq!(areas.len(), polyline.len());
+            let sum: f64 = areas.iter().sum();
+            assert_fuzzy_eq!(sum / 2.0, polyline.area());
+            polyline.invert_direction();
         }
+
+        // line segment contribution is the shoelace term
+        let areas = polyline.segment_areas();
+        assert_fuzzy_eq!(areas[4], 1.0 * 1.0 - 4.0 * 1.0);
+
+        polyline.set_is_closed(false);
+        assert!(polyline.segment_areas().is_empty());
+    }
+
+    #[test]
+    fn try_parallel_offset_errors() {
+        let mut square = Polyline::new_closed();
+        square.add(0.0, 0.0, 0.0);
+        square.add(4.0, 0.0, 0.0);
+        square.add(4.0, 4.0, 0.0);
+        square.add(0.0, 4.0, 0.0);
+
+        // offset consumes the whole shape
+        assert_eq!(square.try_parallel_offset(3.0).map(|r| r.len()), Ok(0));
+        assert_eq!(square.try_parallel_offset(1.0).map(|r| r.len()), Ok(1));
+
+        assert_eq!(
+            square.try_parallel_offset(f64::NAN).map(|r| r.len()),
+            Err(OffsetError::NonFiniteOffset)
+        );
+
+        let mut nan_square = square.clone();
+        nan_square.set_vertex(2, f64::NAN, 4.0, 0.0);
+        assert_eq!(
+            nan_square.try_parallel_offset(1.0).map(|r| r.len()),
+            Err(OffsetError::InvalidVertex(PlineError::NonFiniteVertex {
+                index: 2
+            }))
+        );
+
+        let mut bow_tie = square.clone();
+        bow_tie.set_vertex(2, 0.0, 4.0, 0.0);
+        bow_tie.set_vertex(3, 4.0, 4.0, 0.0);
+        assert_eq!(
+            bow_tie.try_parallel_offset(0.5).map(|r| r.len()),
+            Err(OffsetError::SelfIntersecting)
+        );
+
+        let mut single = Polyline::new();
+        single.add(1.0, 1.0, 0.0);
+        assert_eq!(
+            single.try_parallel_offset(1.0).map(|r| r.len()),
+            Err(OffsetError::TooFewVertexes)
+        );
+    }
+
+    #[test]
+    fn merge_collinear_arcs_quarters() {
+        let quarter_bulge = bulge_from_angle(PI / 2.0);
+
+        // two quarter circles into a semicircle
+        let mut polyline = Polyline::new();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(2.0, 0.0, quarter_bulge);
+        polyline.add(3.0, 1.0, quarter_bulge);
+        polyline.add(2.0, 2.0, 0.0);
+        polyline.add(0.0, 2.0, 0.0);
+        let length = polyline.path_length();
+        polyline.merge_collinear_arcs(1e-5);
+        assert_eq!(polyline.len(), 4);
+        assert_fuzzy_eq!(polyline[1], PlineVertex::new(2.0, 0.0, 1.0));
+        assert_fuzzy_eq!(polyline[2], PlineVertex::new(2.0, 2.0, 0.0));
+        assert_fuzzy_eq!(polyline.path_length(), length);
+
+        // arcs going in opposite directions or on different circles are not merged
+        let mut polyline = Polyline::new();
+        polyline.add(0.0, 0.0, quarter_bulge);
+        polyline.add(1.0, -1.0, -quarter_bulge);
+        polyline.add(2.0, -2.0, quarter_bulge);
+        polyline.add(4.0, -2.0, 0.0);
+        polyline.merge_collinear_arcs(1e-5);
+        assert_eq!(polyline.len(), 4);
+
+        // closed circle made of four quarters (starting mid way through a run)
+        let mut polyline = Polyline::new_closed();
+        polyline.add(1.0, 0.0, quarter_bulge);
+        polyline.add(0.0, 1.0, quarter_bulge);
+        polyline.add(-1.0, 0.0, quarter_bulge);
+        polyline.add(0.0, -1.0, quarter_bulge);
+        polyline.merge_collinear_arcs(1e-5);
+        assert_eq!(polyline.len(), 2);
+        assert_fuzzy_eq!(angle_from_bulge(polyline[0].bulge), 1.5 * PI);
+        assert_fuzzy_eq!(angle_from_bulge(polyline[1].bulge), 0.5 * PI);
+        assert_fuzzy_eq!(polyline.area(), PI);
     }
 }
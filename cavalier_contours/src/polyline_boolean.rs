@@ -0,0 +1,680 @@
+use core::cmp::Ordering;
+
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+use static_aabb2d_index::StaticAABB2DIndexBuilder;
+
+use crate::{
+    core_math::{dist_squared, seg_midpoint, seg_split_at_point, seg_tangent_vector},
+    polyline_intersects::all_intersects,
+    utils::LookupMap,
+    Polyline, Real, Vector2,
+};
+
+/// Boolean operation to apply between two closed polylines.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum BooleanOp {
+    Union,
+    Intersection,
+    Difference,
+}
+
+/// Classification of a polyline slice relative to the other polyline in the boolean operation.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum SliceLocation {
+    Inside,
+    Outside,
+    /// Slice lies on top of the other polyline and has the same direction.
+    CoincidentSameDir,
+    /// Slice lies on top of the other polyline and has the opposite direction.
+    CoincidentOppositeDir,
+}
+
+/// Returns a copy of `polyline` with counter clockwise direction.
+fn ccw_copy<T>(polyline: &Polyline<T>) -> Polyline<T>
+where
+    T: Real,
+{
+    let mut result = polyline.clone();
+    if result.area() < T::zero() {
+        result.invert_direction();
+    }
+    result
+}
+
+/// Add `point` to the intersect list for the segment in `polyline` which starts at `start_index`.
+///
+/// Points which lie on the end vertex of the segment are added to the next segment (so every
+/// intersect at a vertex is associated with the segment starting at that vertex).
+fn add_split_point<T>(
+    lookup: &mut LookupMap<usize, Vec<Vector2<T>>>,
+    polyline: &Polyline<T>,
+    start_index: usize,
+    point: Vector2<T>,
+    pos_equal_eps: T,
+) where
+    T: Real,
+{
+    let next_index = polyline.next_wrapping_index(start_index);
+    let index = if polyline[next_index]
+        .pos()
+        .fuzzy_eq_eps(point, pos_equal_eps)
+    {
+        next_index
+    } else {
+        start_index
+    };
+
+    let points = lookup.entry(index).or_default();
+    if !points.iter().any(|p| p.fuzzy_eq_eps(point, pos_equal_eps)) {
+        points.push(point);
+    }
+}
+
+/// Slice the closed `polyline` at all the points in `split_points` (keyed by segment start index)
+/// returning the open polyline slices.
+fn slice_at_points<T>(
+    polyline: &Polyline<T>,
+    split_points: &mut LookupMap<usize, Vec<Vector2<T>>>,
+    pos_equal_eps: T,
+) -> Vec<Polyline<T>>
+where
+    T: Real,
+{
+    let mut result = Vec::new();
+
+    // sort intersects by distance from start vertex
+    for (&i, points) in split_points.iter_mut() {
+        let start_pos = polyline[i].pos();
+        points.sort_unstable_by(|&p1, &p2| {
+            let dist1 = dist_squared(p1, start_pos);
+            let dist2 = dist_squared(p2, start_pos);
+            dist1.partial_cmp(&dist2).unwrap_or(Ordering::Equal)
+        });
+    }
+
+    let start_index = match split_points.keys().min() {
+        Some(&i) => i,
+        None => return result,
+    };
+
+    let ln = polyline.len();
+    let mut current: Option<Polyline<T>> = None;
+    // visit every segment starting and ending at the first segment with a split point (the last
+    // visit only slices up to the first split point)
+    for k in 0..=ln {
+        let i = (start_index + k) % ln;
+        let next_vertex = polyline[polyline.next_wrapping_index(i)];
+        let mut seg_start = polyline[i];
+        if let Some(c) = current.as_mut() {
+            c.add_or_replace_vertex(seg_start, pos_equal_eps);
+        }
+
+        if let Some(points) = split_points.get(&i) {
+            let points = if k == ln { &points[..1] } else { &points[..] };
+            for &point in points {
+                let split = seg_split_at_point(seg_start, next_vertex, point, pos_equal_eps);
+                if let Some(mut slice) = current.take() {
+                    *slice.last_mut().unwrap() = split.updated_start;
+                    slice.add_or_replace(point.x, point.y, T::zero(), pos_equal_eps);
+                    if slice.len() > 1 {
+                        result.push(slice);
+                    }
+                }
+
+                let mut slice = Polyline::new();
+                slice.add_vertex(split.split_vertex);
+                current = Some(slice);
+                seg_start = split.split_vertex;
+            }
+        }
+    }
+
+    result
+}
+
+/// Determine where `slice` lies relative to the closed counter clockwise `other` polyline.
+///
+/// Returns `None` if the closest point on `other` cannot be found (`other` is not finite).
+fn slice_location<T>(
+    slice: &Polyline<T>,
+    other: &Polyline<T>,
+    pos_equal_eps: T,
+) -> Option<SliceLocation>
+where
+    T: Real,
+{
+    let midpoint = seg_midpoint(slice[0], slice[1]);
+    let closest = other.closest_point(midpoint)?;
+    if closest.distance < pos_equal_eps {
+        let i = closest.seg_start_index;
+        let j = other.next_wrapping_index(i);
+        let other_dir = seg_tangent_vector(other[i], other[j], closest.seg_point);
+        let slice_dir = seg_tangent_vector(slice[0], slice[1], midpoint);
+        if other_dir.dot(slice_dir) > T::zero() {
+            return Some(SliceLocation::CoincidentSameDir);
+        }
+
+        return Some(SliceLocation::CoincidentOppositeDir);
+    }
+
+    if other.winding_number(midpoint) != 0 {
+        Some(SliceLocation::Inside)
+    } else {
+        Some(SliceLocation::Outside)
+    }
+}
+
+/// Stitch open polyline `slices` together end to start to form closed polylines.
+fn stitch_slices_into_closed<T>(slices: &[Polyline<T>], join_eps: T) -> Vec<Polyline<T>>
+where
+    T: Real,
+{
+    let mut result = Vec::new();
+    if slices.is_empty() {
+        return result;
+    }
+
+    let spatial_index = {
+        let mut builder = StaticAABB2DIndexBuilder::new(slices.len());
+        for slice in slices {
+            let start_point = slice[0].pos();
+            builder.add(
+                start_point.x - join_eps,
+                start_point.y - join_eps,
+                start_point.x + join_eps,
+                start_point.y + join_eps,
+            );
+        }
+        builder.build().unwrap()
+    };
+
+    let mut visited_indexes = vec![false; slices.len()];
+    let mut query_results = Vec::new();
+    let mut query_stack = Vec::with_capacity(8);
+
+    for i in 0..slices.len() {
+        if visited_indexes[i] {
+            continue;
+        }
+
+        visited_indexes[i] = true;
+
+        let mut current_pline = Polyline::new();
+        let mut current_index = i;
+        let initial_start_point = slices[i][0].pos();
+        let mut loop_count = 0;
+        let max_loop_count = slices.len();
+        loop {
+            if loop_count > max_loop_count {
+                // prevent infinite loop, close what was formed
+                break;
+            }
+            loop_count += 1;
+
+            current_pline.extend_vertexes(&slices[current_index]);
+            let current_end_point = current_pline.last().unwrap().pos();
+
+            if current_end_point.fuzzy_eq_eps(initial_start_point, join_eps) {
+                // closed loop formed
+                break;
+            }
+
+            query_results.clear();
+            let mut spatial_index_visitor = |i: usize| -> bool {
+                if !visited_indexes[i] {
+                    query_results.push(i);
+                }
+                true
+            };
+            spatial_index.visit_query_with_stack(
+                current_end_point.x - join_eps,
+                current_end_point.y - join_eps,
+                current_end_point.x + join_eps,
+                current_end_point.y + join_eps,
+                &mut spatial_index_visitor,
+                &mut query_stack,
+            );
+
+            if query_results.is_empty() {
+                // nothing left to connect to, close what was formed
+                break;
+            }
+
+            query_results.sort_unstable();
+            visited_indexes[query_results[0]] = true;
+            current_pline.remove_last();
+            current_index = query_results[0];
+        }
+
+        if current_pline.len() > 1
+            && current_pline[0]
+                .pos()
+                .fuzzy_eq_eps(current_pline.last().unwrap().pos(), join_eps)
+        {
+            current_pline.remove_last();
+        }
+
+        if current_pline.len() > 1 {
+            current_pline.set_is_closed(true);
+            result.push(current_pline);
+        }
+    }
+
+    result
+}
+
+fn boolean<T>(pline1: &Polyline<T>, pline2: &Polyline<T>, op: BooleanOp) -> Vec<Polyline<T>>
+where
+    T: Real,
+{
+    // use the larger of the two position equal epsilons so coincident points of either polyline
+    // are treated as equal
+    let pos_equal_eps = num_traits::real::Real::max(pline1.pos_equal_eps(), pline2.pos_equal_eps());
+    let slice_join_eps = T::from(1e-4).unwrap();
+
+    let is_valid = |pline: &Polyline<T>| pline.is_closed() && pline.len() > 1 && pline.is_finite();
+    match (is_valid(pline1), is_valid(pline2)) {
+        (true, true) => {}
+        (true, false) => {
+            return match op {
+                BooleanOp::Union | BooleanOp::Difference => vec![pline1.clone()],
+                BooleanOp::Intersection => Vec::new(),
+            };
+        }
+        (false, true) => {
+            return match op {
+                BooleanOp::Union => vec![pline2.clone()],
+                BooleanOp::Intersection | BooleanOp::Difference => Vec::new(),
+            };
+        }
+        (false, false) => return Vec::new(),
+    }
+
+    let pline1 = ccw_copy(pline1);
+    let pline2 = ccw_copy(pline2);
+
+    let pline1_index = match pline1.create_approx_spatial_index() {
+        Some(index) => index,
+        None => return Vec::new(),
+    };
+    let (intrs, overlapping_intrs) = all_intersects(&pline1, &pline2, &pline1_index);
+
+    let mut split_points1 = LookupMap::new();
+    let mut split_points2 = LookupMap::new();
+    let mut add_intr = |start_index1: usize, start_index2: usize, point: Vector2<T>| {
+        add_split_point(
+            &mut split_points1,
+            &pline1,
+            start_index1,
+            point,
+            pos_equal_eps,
+        );
+        add_split_point(
+            &mut split_points2,
+            &pline2,
+            start_index2,
+            point,
+            pos_equal_eps,
+        );
+    };
+
+    for intr in &intrs {
+        add_intr(intr.start_index1, intr.start_index2, intr.position);
+    }
+
+    for intr in &overlapping_intrs {
+        add_intr(intr.start_index1, intr.start_index2, intr.point1);
+        add_intr(intr.start_index1, intr.start_index2, intr.point2);
+    }
+
+    if split_points1.is_empty() {
+        // no intersects, polylines are either disjoint or one is fully inside the other
+        let pline1_in_pline2 = pline2.winding_number(pline1[0].pos()) != 0;
+        let pline2_in_pline1 = pline1.winding_number(pline2[0].pos()) != 0;
+        return match op {
+            BooleanOp::Union => {
+                if pline1_in_pline2 {
+                    vec![pline2]
+                } else if pline2_in_pline1 {
+                    vec![pline1]
+                } else {
+                    vec![pline1, pline2]
+                }
+            }
+            BooleanOp::Intersection => {
+                if pline1_in_pline2 {
+                    vec![pline1]
+                } else if pline2_in_pline1 {
+                    vec![pline2]
+                } else {
+                    Vec::new()
+                }
+            }
+            BooleanOp::Difference => {
+                if pline1_in_pline2 {
+                    Vec::new()
+                } else if pline2_in_pline1 {
+                    // pline2 becomes a hole in pline1
+                    let mut hole = pline2;
+                    hole.invert_direction();
+                    vec![pline1, hole]
+                } else {
+                    vec![pline1]
+                }
+            }
+        };
+    }
+
+    let slices1 = slice_at_points(&pline1, &mut split_points1, pos_equal_eps);
+    let slices2 = slice_at_points(&pline2, &mut split_points2, pos_equal_eps);
+
+    let mut kept_slices = Vec::with_capacity(slices1.len() + slices2.len());
+    for slice in slices1 {
+        let keep = matches!(
+            (op, slice_location(&slice, &pline2, pos_equal_eps)),
+            (BooleanOp::Union, Some(SliceLocation::Outside))
+                | (BooleanOp::Union, Some(SliceLocation::CoincidentSameDir))
+                | (BooleanOp::Intersection, Some(SliceLocation::Inside))
+                | (
+                    BooleanOp::Intersection,
+                    Some(SliceLocation::CoincidentSameDir)
+                )
+                | (BooleanOp::Difference, Some(SliceLocation::Outside))
+                | (
+                    BooleanOp::Difference,
+                    Some(SliceLocation::CoincidentOppositeDir)
+                )
+        );
+
+        if keep {
+            kept_slices.push(slice);
+        }
+    }
+
+    for mut slice in slices2 {
+        // coincident slices are only ever kept from the first polyline
+        let keep = matches!(
+            (op, slice_location(&slice, &pline1, pos_equal_eps)),
+            (BooleanOp::Union, Some(SliceLocation::Outside))
+                | (BooleanOp::Intersection, Some(SliceLocation::Inside))
+                | (BooleanOp::Difference, Some(SliceLocation::Inside))
+        );
+
+        if keep {
+            if op == BooleanOp::Difference {
+                // slices of pline2 bound the result from the inside when subtracting
+                slice.invert_direction();
+            }
+            kept_slices.push(slice);
+        }
+    }
+
+    stitch_slices_into_closed(&kept_slices, slice_join_eps)
+}
+
+/// Compute the boolean union of two closed polylines `pline1` and `pline2`.
+///
+/// Returns the closed polylines which bound the union. If the polylines do not overlap then both
+/// are returned, if the union encloses a hole then the hole is returned as a separate clockwise
+/// polyline, all other polylines returned are counter clockwise. Open polylines (or polylines with
+/// less than 2 vertexes or non-finite vertexes) are treated as empty. Inputs are assumed to not be
+/// self intersecting.
+///
+/// # Examples
+///
+/// ```
+/// # use cavalier_contours::*;
+/// # use cavalier_contours::boolean::*;
+/// let mut pline1 = Polyline::new_closed();
+/// pline1.add(0.0, 0.0, 0.0);
+/// pline1.add(2.0, 0.0, 0.0);
+/// pline1.add(2.0, 2.0, 0.0);
+/// pline1.add(0.0, 2.0, 0.0);
+/// let mut pline2 = pline1.clone();
+/// pline2.translate(1.0, 1.0);
+/// let result = union(&pline1, &pline2);
+/// assert_eq!(result.len(), 1);
+/// assert!(result[0].area().fuzzy_eq(7.0));
+/// ```
+pub fn union<T>(pline1: &Polyline<T>, pline2: &Polyline<T>) -> Vec<Polyline<T>>
+where
+    T: Real,
+{
+    boolean(pline1, pline2, BooleanOp::Union)
+}
+
+/// Compute the boolean intersection of two closed polylines `pline1` and `pline2`.
+///
+/// Returns the closed counter clockwise polylines which bound the regions covered by both
+/// polylines. If one polyline is fully inside the other then the inner polyline is returned, if
+/// the polylines do not overlap (including only touching at a point) then nothing is returned.
+/// Open polylines (or polylines with less than 2 vertexes or non-finite vertexes) are treated as
+/// empty. Inputs are assumed to not be self intersecting.
+///
+/// # Examples
+///
+/// ```
+/// # use cavalier_contours::*;
+/// # use cavalier_contours::boolean::*;
+/// let mut pline1 = Polyline::new_closed();
+/// pline1.add(0.0, 0.0, 0.0);
+/// pline1.add(2.0, 0.0, 0.0);
+/// pline1.add(2.0, 2.0, 0.0);
+/// pline1.add(0.0, 2.0, 0.0);
+/// let mut pline2 = pline1.clone();
+/// pline2.translate(1.0, 1.0);
+/// let result = intersection(&pline1, &pline2);
+/// assert_eq!(result.len(), 1);
+/// assert!(result[0].area().fuzzy_eq(1.0));
+/// ```
+pub fn intersection<T>(pline1: &Polyline<T>, pline2: &Polyline<T>) -> Vec<Polyline<T>>
+where
+    T: Real,
+{
+    boolean(pline1, pline2, BooleanOp::Intersection)
+}
+
+/// Compute the boolean difference of two closed polylines (`pline1` minus `pline2`).
+///
+/// Returns the closed polylines which bound the region of `pline1` not covered by `pline2`. Outer
+/// boundaries are returned counter clockwise and holes (e.g. when `pline2` is fully inside `pline1`)
+/// are returned as separate clockwise polylines, so they can be distinguished using the sign of
+/// [Polyline::area]. Open polylines (or polylines with less than 2 vertexes or non-finite vertexes)
+/// are treated as empty. Inputs are assumed to not be self intersecting.
+///
+/// # Examples
+///
+/// ```
+/// # use cavalier_contours::*;
+/// # use cavalier_contours::boolean::*;
+/// let mut pline1 = Polyline::new_closed();
+/// pline1.add(0.0, 0.0, 0.0);
+/// pline1.add(2.0, 0.0, 0.0);
+/// pline1.add(2.0, 2.0, 0.0);
+/// pline1.add(0.0, 2.0, 0.0);
+/// let mut pline2 = pline1.clone();
+/// pline2.translate(1.0, 1.0);
+/// let result = difference(&pline1, &pline2);
+/// assert_eq!(result.len(), 1);
+/// assert!(result[0].area().fuzzy_eq(3.0));
+/// ```
+pub fn difference<T>(pline1: &Polyline<T>, pline2: &Polyline<T>) -> Vec<Polyline<T>>
+where
+    T: Real,
+{
+    boolean(pline1, pline2, BooleanOp::Difference)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+    use crate::FuzzyEq;
+
+    fn circle(center_x: f64, center_y: f64, radius: f64) -> Polyline<f64> {
+        let mut result = Polyline::new_closed();
+        result.add(center_x - radius, center_y, 1.0);
+        result.add(center_x + radius, center_y, 1.0);
+        result
+    }
+
+    #[test]
+    fn union_overlapping_circles() {
+        let result = union(&circle(0.0, 0.0, 1.0), &circle(1.0, 0.0, 1.0));
+        assert_eq!(result.len(), 1);
+        // union area = 2 circles - lens overlap
+        let lens_area = 2.0 * PI / 3.0 - 3.0f64.sqrt() / 2.0;
+        let expected_area = 2.0 * PI - lens_area;
+        assert_fuzzy_eq!(result[0].area(), expected_area);
+    }
+
+    #[test]
+    fn union_clockwise_input() {
+        let mut pline2 = circle(1.0, 0.0, 1.0);
+        pline2.invert_direction();
+        let result = union(&circle(0.0, 0.0, 1.0), &pline2);
+        assert_eq!(result.len(), 1);
+        let lens_area = 2.0 * PI / 3.0 - 3.0f64.sqrt() / 2.0;
+        assert_fuzzy_eq!(result[0].area(), 2.0 * PI - lens_area);
+    }
+
+    #[test]
+    fn union_disjoint() {
+        let result = union(&circle(0.0, 0.0, 1.0), &circle(5.0, 0.0, 1.0));
+        assert_eq!(result.len(), 2);
+        assert_fuzzy_eq!(result[0].area(), PI);
+        assert_fuzzy_eq!(result[1].area(), PI);
+    }
+
+    #[test]
+    fn union_nested() {
+        let result = union(&circle(0.0, 0.0, 1.0), &circle(0.0, 0.0, 3.0));
+        assert_eq!(result.len(), 1);
+        assert_fuzzy_eq!(result[0].area(), 9.0 * PI);
+    }
+
+    #[test]
+    fn union_shared_edge() {
+        let mut pline1 = Polyline::new_closed();
+        pline1.add(0.0, 0.0, 0.0);
+        pline1.add(1.0, 0.0, 0.0);
+        pline1.add(1.0, 1.0, 0.0);
+        pline1.add(0.0, 1.0, 0.0);
+        let mut pline2 = pline1.clone();
+        pline2.translate(1.0, 0.0);
+        let result = union(&pline1, &pline2);
+        assert_eq!(result.len(), 1);
+        assert_fuzzy_eq!(result[0].area(), 2.0);
+    }
+
+    fn square(min_x: f64, min_y: f64, size: f64) -> Polyline<f64> {
+        let mut result = Polyline::new_closed();
+        result.add(min_x, min_y, 0.0);
+        result.add(min_x + size, min_y, 0.0);
+        result.add(min_x + size, min_y + size, 0.0);
+        result.add(min_x, min_y + size, 0.0);
+        result
+    }
+
+    #[test]
+    fn intersection_overlapping_squares() {
+        let result = intersection(&square(0.0, 0.0, 2.0), &square(1.0, 1.0, 2.0));
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].len(), 4);
+        assert_fuzzy_eq!(result[0].area(), 1.0);
+        let extents = result[0].extents().unwrap();
+        assert_fuzzy_eq!(extents.min_x, 1.0);
+        assert_fuzzy_eq!(extents.min_y, 1.0);
+        assert_fuzzy_eq!(extents.max_x, 2.0);
+        assert_fuzzy_eq!(extents.max_y, 2.0);
+    }
+
+    #[test]
+    fn intersection_square_inside_circle() {
+        let inner = square(-0.5, -0.5, 1.0);
+        let result = intersection(&inner, &circle(0.0, 0.0, 2.0));
+        assert_eq!(result.len(), 1);
+        assert!(result[0].fuzzy_eq(&inner));
+
+        let result = intersection(&circle(0.0, 0.0, 2.0), &inner);
+        assert_eq!(result.len(), 1);
+        assert!(result[0].fuzzy_eq(&inner));
+    }
+
+    #[test]
+    fn intersection_overlapping_circles() {
+        let result = intersection(&circle(0.0, 0.0, 1.0), &circle(1.0, 0.0, 1.0));
+        assert_eq!(result.len(), 1);
+        let lens_area = 2.0 * PI / 3.0 - 3.0f64.sqrt() / 2.0;
+        assert_fuzzy_eq!(result[0].area(), lens_area);
+    }
+
+    #[test]
+    fn intersection_touching_at_point() {
+        let result = intersection(&circle(0.0, 0.0, 1.0), &circle(2.0, 0.0, 1.0));
+        assert!(result.is_empty());
+        let result = intersection(&square(0.0, 0.0, 1.0), &square(1.0, 1.0, 1.0));
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn intersection_disjoint() {
+        let result = intersection(&circle(0.0, 0.0, 1.0), &circle(5.0, 0.0, 1.0));
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn difference_circle_from_square_center() {
+        let result = difference(&square(-2.0, -2.0, 4.0), &circle(0.0, 0.0, 1.0));
+        assert_eq!(result.len(), 2);
+        let outer = result.iter().find(|p| p.area() > 0.0).unwrap();
+        let hole = result.iter().find(|p| p.area() < 0.0).unwrap();
+        assert_fuzzy_eq!(outer.area(), 16.0);
+        assert_fuzzy_eq!(hole.area(), -PI);
+    }
+
+    #[test]
+    fn difference_overlapping_circles() {
+        let result = difference(&circle(0.0, 0.0, 1.0), &circle(1.0, 0.0, 1.0));
+        assert_eq!(result.len(), 1);
+        let lens_area = 2.0 * PI / 3.0 - 3.0f64.sqrt() / 2.0;
+        assert_fuzzy_eq!(result[0].area(), PI - lens_area);
+    }
+
+    #[test]
+    fn difference_shared_edge() {
+        let result = difference(&square(0.0, 0.0, 2.0), &square(1.0, 0.0, 1.0));
+        assert_eq!(result.len(), 1);
+        assert_fuzzy_eq!(result[0].area(), 3.0);
+    }
+
+    #[test]
+    fn difference_fully_covered_or_disjoint() {
+        let result = difference(&square(-0.5, -0.5, 1.0), &circle(0.0, 0.0, 2.0));
+        assert!(result.is_empty());
+        let pline1 = circle(0.0, 0.0, 1.0);
+        let result = difference(&pline1, &circle(5.0, 0.0, 1.0));
+        assert_eq!(result.len(), 1);
+        assert!(result[0].fuzzy_eq(&pline1));
+    }
+
+    #[test]
+    fn non_finite_input_treated_as_empty() {
+        let pline1 = square(0.0, 0.0, 2.0);
+        let mut pline2 = square(1.0, 1.0, 2.0);
+        pline2[2].x = f64::NAN;
+
+        let result = union(&pline1, &pline2);
+        assert_eq!(result.len(), 1);
+        assert!(result[0].fuzzy_eq(&pline1));
+        assert!(intersection(&pline1, &pline2).is_empty());
+        assert!(intersection(&pline2, &pline1).is_empty());
+        let result = difference(&pline1, &pline2);
+        assert_eq!(result.len(), 1);
+        assert!(result[0].fuzzy_eq(&pline1));
+        assert!(difference(&pline2, &pline1).is_empty());
+    }
+}
@@ -0,0 +1,175 @@
+use core::fmt;
+
+use crate::{PlineError, PlineVertex, Polyline, Real};
+
+/// Error returned by [PolylineBuilder::try_add] when a vertex is rejected.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BuildError {
+    /// Vertex is not valid polyline vertex data, [PlineError::NonFiniteVertex] (with the index
+    /// the vertex would have been added at) if it has a non-finite x, y, or bulge value (same as
+    /// [Polyline::validate]).
+    InvalidVertex(PlineError),
+    /// Vertex position is coincident with the previous vertex position (only returned if
+    /// [PolylineBuilder::reject_coincident] is set).
+    CoincidentPoint,
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::InvalidVertex(err) => write!(f, "{}", err),
+            BuildError::CoincidentPoint => {
+                write!(f, "vertex is coincident with the previous vertex")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BuildError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BuildError::InvalidVertex(err) => Some(err),
+            BuildError::CoincidentPoint => None,
+        }
+    }
+}
+
+impl From<PlineError> for BuildError {
+    fn from(err: PlineError) -> Self {
+        BuildError::InvalidVertex(err)
+    }
+}
+
+/// Builder for a [Polyline] which validates each vertex as it is added.
+///
+/// Unlike [Polyline::add] (which accepts any values) vertexes with non-finite values are rejected,
+/// and optionally vertexes coincident with the previous vertex, so invalid input (e.g. from a
+/// parser) is caught at the vertex that caused it. A rejected vertex leaves the builder unchanged.
+///
+/// # Examples
+///
+/// ```
+/// # use cavalier_contours::*;
+/// let mut builder = PolylineBuilder::new().closed(true).reject_coincident(1e-5);
+/// builder.try_add(0.0, 0.0, 0.0).unwrap();
+/// builder.try_add(1.0, 0.0, 0.0).unwrap();
+/// assert_eq!(builder.try_add(1.0, 0.0, 0.5), Err(BuildError::CoincidentPoint));
+/// assert_eq!(
+///     builder.try_add(f64::NAN, 1.0, 0.0),
+///     Err(BuildError::InvalidVertex(PlineError::NonFiniteVertex { index: 2 }))
+/// );
+/// builder.try_add(1.0, 1.0, 0.0).unwrap();
+/// let polyline = builder.build();
+/// assert!(polyline.is_closed());
+/// assert_eq!(polyline.len(), 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PolylineBuilder<T = f64> {
+    polyline: Polyline<T>,
+    coincident_eps: Option<T>,
+}
+
+impl<T> PolylineBuilder<T>
+where
+    T: Real,
+{
+    /// Create a new builder for an open polyline which accepts coincident vertexes.
+    pub fn new() -> Self {
+        PolylineBuilder {
+            polyline: Polyline::new(),
+            coincident_eps: None,
+        }
+    }
+
+    /// Set whether the polyline built is closed.
+    pub fn closed(mut self, is_closed: bool) -> Self {
+        self.polyline.set_is_closed(is_closed);
+        self
+    }
+
+    /// Reject vertexes whose position is fuzzy equal (using `pos_equal_eps`) to the previous vertex
+    /// position.
+    pub fn reject_coincident(mut self, pos_equal_eps: T) -> Self {
+        self.coincident_eps = Some(pos_equal_eps);
+        self
+    }
+
+    /// Number of vertexes added so far.
+    pub fn len(&self) -> usize {
+        self.polyline.len()
+    }
+
+    /// Returns true if no vertexes have been added.
+    pub fn is_empty(&self) -> bool {
+        self.polyline.len() == 0
+    }
+
+    /// Validate and add a vertex, if the vertex is rejected then an error is returned and the
+    /// builder is left unchanged.
+    pub fn try_add(&mut self, x: T, y: T, bulge: T) -> Result<(), BuildError> {
+        let vertex = PlineVertex::new(x, y, bulge);
+        if !vertex.is_finite() {
+            let index = self.polyline.len();
+            return Err(PlineError::NonFiniteVertex { index }.into());
+        }
+
+        if let (Some(eps), Some(last)) = (self.coincident_eps, self.polyline.last()) {
+            if last.pos().fuzzy_eq_eps(vertex.pos(), eps) {
+                return Err(BuildError::CoincidentPoint);
+            }
+        }
+
+        self.polyline.add_vertex(vertex);
+        Ok(())
+    }
+
+    /// Finish building and return the polyline.
+    pub fn build(self) -> Polyline<T> {
+        self.polyline
+    }
+}
+
+impl<T> Default for PolylineBuilder<T>
+where
+    T: Real,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejected_vertex_leaves_builder_unchanged() {
+        let mut builder = PolylineBuilder::new();
+        builder.try_add(0.0, 0.0, 0.5).unwrap();
+        builder.try_add(2.0, 0.0, 0.0).unwrap();
+
+        let non_finite = Err(BuildError::InvalidVertex(PlineError::NonFiniteVertex {
+            index: 2,
+        }));
+        assert_eq!(builder.try_add(f64::NAN, 1.0, 0.0), non_finite);
+        assert_eq!(builder.try_add(1.0, 1.0, f64::INFINITY), non_finite);
+        assert_eq!(builder.len(), 2);
+
+        // coincident points are accepted unless rejection is enabled
+        builder.try_add(2.0, 0.0, 0.0).unwrap();
+        assert_eq!(builder.len(), 3);
+        let mut builder = builder.reject_coincident(1e-5);
+        assert_eq!(
+            builder.try_add(2.0, 1e-6, 0.0),
+            Err(BuildError::CoincidentPoint)
+        );
+        assert_eq!(builder.len(), 3);
+
+        let polyline = builder.build();
+        assert!(!polyline.is_closed());
+        assert!(polyline.is_finite());
+        assert!(polyline[0].fuzzy_eq(PlineVertex::new(0.0, 0.0, 0.5)));
+        assert!(polyline[2].fuzzy_eq(PlineVertex::new(2.0, 0.0, 0.0)));
+    }
+}
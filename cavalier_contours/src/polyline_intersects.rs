@@ -1,9 +1,11 @@
-use std::collections::HashSet;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
 
 use static_aabb2d_index::StaticAABB2DIndex;
 
 use crate::{
-    intersects::PlineSegIntr, pline_seg_intersect::pline_seg_intr, Polyline, Real, Vector2,
+    core_math::seg_fast_approx_bounding_box, intersects::PlineSegIntr,
+    pline_seg_intersect::pline_seg_intr, utils::lookup_set_with_capacity, Polyline, Real, Vector2,
 };
 
 #[derive(Debug, Clone, Copy)]
@@ -182,7 +184,7 @@ pub fn visit_global_self_intersects<T, F, G>(
         return;
     }
 
-    let mut visited_pairs = HashSet::with_capacity(ln);
+    let mut visited_pairs = lookup_set_with_capacity(ln);
     let mut query_stack = Vec::with_capacity(8);
     let fuzz = T::fuzzy_epsilon();
 
@@ -295,6 +297,21 @@ where
     T: Real,
 {
     let mut intrs = Vec::new();
+    all_self_intersects_into(polyline, spatial_index, pos_equal_eps, &mut intrs);
+    intrs
+}
+
+/// Same as [all_self_intersects] but the intersects are written to `intrs` (which is cleared
+/// first) so its buffer can be reused.
+pub fn all_self_intersects_into<T>(
+    polyline: &Polyline<T>,
+    spatial_index: &StaticAABB2DIndex<T>,
+    pos_equal_eps: T,
+    intrs: &mut Vec<PlineIntersect<T>>,
+) where
+    T: Real,
+{
+    intrs.clear();
     let mut overlapping_intrs = Vec::new();
     let mut visitor = |intr: PlineIntersect<T>| {
         intrs.push(intr);
@@ -321,13 +338,226 @@ where
             overlapping_intr.point2,
         ));
     }
+}
 
-    intrs
+/// Visits all intersects between `pline1` and `pline2`. `pline1_spatial_index` is used to query for
+/// the segments of `pline1` which may intersect each segment of `pline2`.
+///
+/// For the intersects visited `start_index1` is the segment start index in `pline1` and
+/// `start_index2` is the segment start index in `pline2`. Intersects which lie on a vertex may be
+/// visited more than once (once for each segment which touches the vertex).
+pub fn visit_intersects<T, F, G>(
+    pline1: &Polyline<T>,
+    pline2: &Polyline<T>,
+    pline1_spatial_index: &StaticAABB2DIndex<T>,
+    visitor: &mut F,
+    overlap_visitor: &mut G,
+) where
+    T: Real,
+    F: FnMut(PlineIntersect<T>) -> bool,
+    G: FnMut(PlineOverlappingIntersect<T>) -> bool,
+{
+    if pline1.len() < 2 || pline2.len() < 2 {
+        return;
+    }
+
+    let mut query_stack = Vec::with_capacity(8);
+    let fuzz = T::fuzzy_epsilon();
+
+    let mut break_loop = false;
+    for (i2, j2) in pline2.iter_segment_indexes() {
+        let u1 = pline2[i2];
+        let u2 = pline2[j2];
+        let mut query_visitor = |i1: usize| -> bool {
+            let j1 = pline1.next_wrapping_index(i1);
+            let v1 = pline1[i1];
+            let v2 = pline1[j1];
+
+            let mut continue_visiting = true;
+            match pline_seg_intr(v1, v2, u1, u2) {
+                PlineSegIntr::NoIntersect => {}
+                PlineSegIntr::TangentIntersect { point } | PlineSegIntr::OneIntersect { point } => {
+                    continue_visiting = visitor(PlineIntersect::new(i1, i2, point));
+                }
+                PlineSegIntr::TwoIntersects { point1, point2 } => {
+                    continue_visiting = visitor(PlineIntersect::new(i1, i2, point1));
+                    if continue_visiting {
+                        continue_visiting = visitor(PlineIntersect::new(i1, i2, point2));
+                    }
+                }
+                PlineSegIntr::OverlappingLines { point1, point2 }
+                | PlineSegIntr::OverlappingArcs { point1, point2 } => {
+                    continue_visiting =
+                        overlap_visitor(PlineOverlappingIntersect::new(i1, i2, point1, point2));
+                }
+            }
+
+            break_loop = !continue_visiting;
+            continue_visiting
+        };
+
+        let bb = seg_fast_approx_bounding_box(u1, u2);
+        pline1_spatial_index.visit_query_with_stack(
+            bb.min_x - fuzz,
+            bb.min_y - fuzz,
+            bb.max_x + fuzz,
+            bb.max_y + fuzz,
+            &mut query_visitor,
+            &mut query_stack,
+        );
+
+        if break_loop {
+            break;
+        }
+    }
+}
+
+pub fn all_intersects<T>(
+    pline1: &Polyline<T>,
+    pline2: &Polyline<T>,
+    pline1_spatial_index: &StaticAABB2DIndex<T>,
+) -> (Vec<PlineIntersect<T>>, Vec<PlineOverlappingIntersect<T>>)
+where
+    T: Real,
+{
+    let mut intrs = Vec::new();
+    let mut overlapping_intrs = Vec::new();
+    let mut intr_visitor = |intr: PlineIntersect<T>| {
+        intrs.push(intr);
+        true
+    };
+    let mut overlapping_visitor = |overlapping_intr: PlineOverlappingIntersect<T>| {
+        overlapping_intrs.push(overlapping_intr);
+        true
+    };
+
+    visit_intersects(
+        pline1,
+        pline2,
+        pline1_spatial_index,
+        &mut intr_visitor,
+        &mut overlapping_visitor,
+    );
+
+    (intrs, overlapping_intrs)
+}
+
+/// Intersect between two polylines found by calling [find_intersects].
+#[derive(Debug, Copy, Clone)]
+pub enum IntersectPoint<T>
+where
+    T: Real,
+{
+    /// Segments intersect at a single point.
+    Point {
+        /// The start vertex index of the segment in the first polyline.
+        start_index1: usize,
+        /// The start vertex index of the segment in the second polyline.
+        start_index2: usize,
+        /// The intersect point.
+        point: Vector2<T>,
+    },
+    /// Segments overlap (coincident lines or arcs) from `point1` to `point2`.
+    Overlap {
+        /// The start vertex index of the segment in the first polyline.
+        start_index1: usize,
+        /// The start vertex index of the segment in the second polyline.
+        start_index2: usize,
+        /// Start point of the overlap.
+        point1: Vector2<T>,
+        /// End point of the overlap.
+        point2: Vector2<T>,
+    },
+}
+
+/// Find all the intersects between `pline1` and `pline2`.
+///
+/// `pline1_spatial_index` is used to query for the segments of `pline1` which may intersect each
+/// segment of `pline2`, if `None` is given then a spatial index is created. Segment indexes held
+/// by each [IntersectPoint] are the start vertex indexes of the segments involved. Intersects which
+/// lie on a vertex shared between two consecutive segments are only reported once (for the segment
+/// which starts at the vertex). Overlapping segments are reported as [IntersectPoint::Overlap].
+///
+/// # Examples
+///
+/// ```
+/// # use cavalier_contours::*;
+/// # use cavalier_contours::intersects::*;
+/// let mut pline1: Polyline = Polyline::new();
+/// pline1.add(0.0, 0.0, 0.0);
+/// pline1.add(2.0, 2.0, 0.0);
+/// let mut pline2: Polyline = Polyline::new();
+/// pline2.add(0.0, 2.0, 0.0);
+/// pline2.add(2.0, 0.0, 0.0);
+/// let intersects = find_intersects(&pline1, &pline2, None);
+/// assert_eq!(intersects.len(), 1);
+/// assert!(matches!(
+///     intersects[0],
+///     IntersectPoint::Point { start_index1: 0, start_index2: 0, point }
+///         if point.fuzzy_eq(Vector2::new(1.0, 1.0))
+/// ));
+/// ```
+pub fn find_intersects<T>(
+    pline1: &Polyline<T>,
+    pline2: &Polyline<T>,
+    pline1_spatial_index: Option<&StaticAABB2DIndex<T>>,
+) -> Vec<IntersectPoint<T>>
+where
+    T: Real,
+{
+    if pline1.len() < 2 || pline2.len() < 2 {
+        return Vec::new();
+    }
+
+    let mut _constructed_index = None;
+    let index = if let Some(x) = pline1_spatial_index {
+        x
+    } else {
+        _constructed_index = pline1.create_approx_spatial_index();
+        _constructed_index.as_ref().unwrap()
+    };
+
+    // true if point is at the end of the segment starting at i and another segment starts there
+    let at_shared_end = |pline: &Polyline<T>, i: usize, point: Vector2<T>| -> bool {
+        let j = pline.next_wrapping_index(i);
+        (pline.is_closed() || j != pline.len() - 1) && point.fuzzy_eq(pline[j].pos())
+    };
+
+    let mut result = Vec::new();
+    let mut visitor = |intr: PlineIntersect<T>| {
+        if !at_shared_end(pline1, intr.start_index1, intr.position)
+            && !at_shared_end(pline2, intr.start_index2, intr.position)
+        {
+            result.push(IntersectPoint::Point {
+                start_index1: intr.start_index1,
+                start_index2: intr.start_index2,
+                point: intr.position,
+            });
+        }
+        true
+    };
+
+    let mut overlapping_intrs = Vec::new();
+    let mut overlap_visitor = |intr: PlineOverlappingIntersect<T>| {
+        overlapping_intrs.push(IntersectPoint::Overlap {
+            start_index1: intr.start_index1,
+            start_index2: intr.start_index2,
+            point1: intr.point1,
+            point2: intr.point2,
+        });
+        true
+    };
+
+    visit_intersects(pline1, pline2, index, &mut visitor, &mut overlap_visitor);
+    result.extend(overlapping_intrs);
+
+    result
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::FuzzyEq;
     #[test]
     fn local_self_intersects_tests() {
         {
@@ -385,4 +615,96 @@ mod tests {
             assert_fuzzy_eq!(intrs[0].position, pline[2].pos());
         }
     }
+
+    #[test]
+    fn find_intersects_lines() {
+        // zig zag crossing a straight line twice (once at a vertex)
+        let mut pline1 = Polyline::new();
+        pline1.add(0.0, 0.0, 0.0);
+        pline1.add(10.0, 0.0, 0.0);
+        let mut pline2 = Polyline::new();
+        pline2.add(1.0, -1.0, 0.0);
+        pline2.add(3.0, 1.0, 0.0);
+        pline2.add(5.0, 0.0, 0.0);
+        pline2.add(7.0, -1.0, 0.0);
+        let intrs = find_intersects(&pline1, &pline2, None);
+        assert_eq!(intrs.len(), 2);
+        let mut points = intrs
+            .iter()
+            .map(|intr| match *intr {
+                IntersectPoint::Point {
+                    start_index1,
+                    start_index2,
+                    point,
+                } => {
+                    assert_eq!(start_index1, 0);
+                    (start_index2, point)
+                }
+                IntersectPoint::Overlap { .. } => panic!("unexpected overlap"),
+            })
+            .collect::<Vec<_>>();
+        points.sort_by_key(|(i, _)| *i);
+        assert_eq!(points[0].0, 0);
+        assert_fuzzy_eq!(points[0].1, Vector2::new(2.0, 0.0));
+        // intersect at vertex reported once for the segment starting at the vertex
+        assert_eq!(points[1].0, 2);
+        assert_fuzzy_eq!(points[1].1, Vector2::new(5.0, 0.0));
+
+        // overlapping lines
+        let mut pline3 = Polyline::new();
+        pline3.add(-2.0, 0.0, 0.0);
+        pline3.add(4.0, 0.0, 0.0);
+        let index = pline1.create_approx_spatial_index().unwrap();
+        let intrs = find_intersects(&pline1, &pline3, Some(&index));
+        assert_eq!(intrs.len(), 1);
+        match intrs[0] {
+            IntersectPoint::Overlap { point1, point2, .. } => {
+                let (min_x, max_x) = if point1.x < point2.x {
+                    (point1.x, point2.x)
+                } else {
+                    (point2.x, point1.x)
+                };
+                assert_fuzzy_eq!(min_x, 0.0);
+                assert_fuzzy_eq!(max_x, 4.0);
+            }
+            IntersectPoint::Point { .. } => panic!("expected overlap"),
+        }
+    }
+
+    #[test]
+    fn find_intersects_line_and_arc() {
+        // half circle arc from (0, 0) to (2, 0) through (1, -1)
+        let mut arc = Polyline::new();
+        arc.add(0.0, 0.0, 1.0);
+        arc.add(2.0, 0.0, 0.0);
+        let mut line = Polyline::new();
+        line.add(-1.0, -0.5, 0.0);
+        line.add(3.0, -0.5, 0.0);
+        let intrs = find_intersects(&arc, &line, None);
+        assert_eq!(intrs.len(), 2);
+        let x = 0.75f64.sqrt();
+        for intr in intrs {
+            match intr {
+                IntersectPoint::Point {
+                    start_index1,
+                    start_index2,
+                    point,
+                } => {
+                    assert_eq!(start_index1, 0);
+                    assert_eq!(start_index2, 0);
+                    assert!(
+                        point.fuzzy_eq(Vector2::new(1.0 - x, -0.5))
+                            || point.fuzzy_eq(Vector2::new(1.0 + x, -0.5))
+                    );
+                }
+                IntersectPoint::Overlap { .. } => panic!("unexpected overlap"),
+            }
+        }
+
+        // no intersects
+        let mut far_line = Polyline::new();
+        far_line.add(-1.0, 5.0, 0.0);
+        far_line.add(3.0, 5.0, 0.0);
+        assert!(find_intersects(&arc, &far_line, None).is_empty());
+    }
 }
@@ -1,22 +1,26 @@
-use core::panic;
-use std::collections::HashMap;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::fmt;
 
 use static_aabb2d_index::{StaticAABB2DIndex, StaticAABB2DIndexBuilder};
 
 use crate::{
+    base_traits::is_finite,
     core_math::{
         angle, bulge_from_angle, delta_angle, dist_squared, point_from_parametric,
         point_within_arc_sweep, seg_arc_radius_and_center, seg_closest_point,
-        seg_fast_approx_bounding_box, seg_midpoint, seg_split_at_point,
+        seg_fast_approx_bounding_box, seg_midpoint, seg_split_at_point, seg_tangent_vector,
     },
     intersects::{
         circle_circle_intr, line_circle_intr, line_line_intr, pline_seg_intr, CircleCircleIntr,
         LineCircleIntr, LineLineIntr, PlineSegIntr,
     },
-    polyline_intersects::all_self_intersects,
-    PlineVertex, Polyline, Real, Vector2,
+    polyline_intersects::{all_self_intersects_into, PlineIntersect},
+    utils::LookupMap,
+    PlineError, PlineVertex, Polyline, Real, Vector2,
 };
 
+#[derive(Debug, Copy, Clone)]
 pub struct RawPlineOffsetSeg<T>
 where
     T: Real,
@@ -30,14 +34,14 @@ where
 pub fn create_untrimmed_raw_offset_segs<T>(
     polyline: &Polyline<T>,
     offset: T,
-) -> Vec<RawPlineOffsetSeg<T>>
-where
+    result: &mut Vec<RawPlineOffsetSeg<T>>,
+) where
     T: Real,
 {
-    let mut result = Vec::new();
+    result.clear();
     let ln = polyline.len();
     if ln < 2 {
-        return result;
+        return;
     }
 
     let segment_count = if polyline.is_closed() {
@@ -47,49 +51,78 @@ where
     };
     result.reserve(segment_count);
 
-    let process_line_seg = |v1: PlineVertex<T>, v2: PlineVertex<T>| -> RawPlineOffsetSeg<T> {
+    for (v1, v2) in polyline.iter_segments() {
+        result.push(raw_offset_seg(v1, v2, offset));
+    }
+}
+
+fn raw_offset_seg<T>(v1: PlineVertex<T>, v2: PlineVertex<T>, offset: T) -> RawPlineOffsetSeg<T>
+where
+    T: Real,
+{
+    if v1.bulge_is_zero() {
         let line_v = v2.pos() - v1.pos();
         let offset_v = line_v.unit_perp().scale(offset);
-        RawPlineOffsetSeg {
+        return RawPlineOffsetSeg {
             v1: PlineVertex::from_vector2(v1.pos() + offset_v, T::zero()),
             v2: PlineVertex::from_vector2(v2.pos() + offset_v, T::zero()),
             orig_v2_pos: v2.pos(),
             collapsed_arc: false,
-        }
-    };
-
-    let process_arc_seg = |v1: PlineVertex<T>, v2: PlineVertex<T>| -> RawPlineOffsetSeg<T> {
-        let (arc_radius, arc_center) = seg_arc_radius_and_center(v1, v2);
-        let offs = if v1.bulge_is_neg() { offset } else { -offset };
-        let radius_after_offset = arc_radius + offs;
-        let v1_to_center = (v1.pos() - arc_center).normalize();
-        let v2_to_center = (v2.pos() - arc_center).normalize();
-
-        let (new_v1_bulge, collapsed_arc) = if radius_after_offset.fuzzy_lt(T::zero()) {
-            // collapsed arc, offset arc start and end points towards arc center and turn into line
-            // handles case where offset vertexes are equal and simplifies path for clipping algorithm
-            (T::zero(), true)
-        } else {
-            (v1.bulge, false)
         };
+    }
 
-        RawPlineOffsetSeg {
-            v1: PlineVertex::from_vector2(v1_to_center.scale(offs) + v1.pos(), new_v1_bulge),
-            v2: PlineVertex::from_vector2(v2_to_center.scale(offs) + v2.pos(), v2.bulge),
-            orig_v2_pos: v2.pos(),
-            collapsed_arc,
-        }
+    let (arc_radius, arc_center) = seg_arc_radius_and_center(v1, v2);
+    let offs = if v1.bulge_is_neg() { offset } else { -offset };
+    let radius_after_offset = arc_radius + offs;
+    let v1_to_center = (v1.pos() - arc_center).normalize();
+    let v2_to_center = (v2.pos() - arc_center).normalize();
+
+    let (new_v1_bulge, collapsed_arc) = if radius_after_offset.fuzzy_lt(T::zero()) {
+        // collapsed arc, offset arc start and end points towards arc center and turn into line
+        // handles case where offset vertexes are equal and simplifies path for clipping algorithm
+        (T::zero(), true)
+    } else {
+        (v1.bulge, false)
     };
 
-    for (v1, v2) in polyline.iter_segments() {
-        if v1.bulge_is_zero() {
-            result.push(process_line_seg(v1, v2));
-        } else {
-            result.push(process_arc_seg(v1, v2));
-        }
+    RawPlineOffsetSeg {
+        v1: PlineVertex::from_vector2(v1_to_center.scale(offs) + v1.pos(), new_v1_bulge),
+        v2: PlineVertex::from_vector2(v2_to_center.scale(offs) + v2.pos(), v2.bulge),
+        orig_v2_pos: v2.pos(),
+        collapsed_arc,
     }
+}
 
-    result
+/// Raw offset of the single polyline segment defined by `v1` to `v2`, returns the offset segment
+/// start and end vertexes.
+///
+/// This is the building block used by [Polyline::parallel_offset] before segments are joined and
+/// trimmed. A positive `offset` shifts the segment to the left of its direction. Line segments are
+/// shifted along their normal, arc segments keep the same center and bulge with the radius
+/// adjusted by the offset (decreased for counter clockwise arcs offset to the left). If the
+/// offset collapses an arc (radius would be negative) then the offset end points are returned as a
+/// line segment (zero bulge). The bulge of `v2` is passed through unchanged.
+///
+/// # Examples
+///
+/// ```
+/// # use cavalier_contours::*;
+/// let v1 = PlineVertex::new(0.0, 0.0, 0.0);
+/// let v2 = PlineVertex::new(10.0, 0.0, 0.0);
+/// let (u1, u2) = offset_segment(v1, v2, 1.0);
+/// assert!(u1.fuzzy_eq(PlineVertex::new(0.0, 1.0, 0.0)));
+/// assert!(u2.fuzzy_eq(PlineVertex::new(10.0, 1.0, 0.0)));
+/// ```
+pub fn offset_segment<T>(
+    v1: PlineVertex<T>,
+    v2: PlineVertex<T>,
+    offset: T,
+) -> (PlineVertex<T>, PlineVertex<T>)
+where
+    T: Real,
+{
+    let seg = raw_offset_seg(v1, v2, offset);
+    (seg.v1, seg.v2)
 }
 
 #[inline]
@@ -134,13 +167,15 @@ fn connect_using_arc<T>(
     let ep = s2.v1.pos();
     let bulge = bulge_for_connection(arc_center, sp, ep, connection_arcs_ccw);
     result.add_or_replace(sp.x, sp.y, bulge, pos_equal_eps);
-    result.add_or_replace(ep.x, ep.y, T::zero(), pos_equal_eps);
+    // keep the bulge of the second segment (it may be an arc)
+    result.add_or_replace(ep.x, ep.y, s2.v1.bulge, pos_equal_eps);
 }
 
 fn line_line_join<T>(
     s1: &RawPlineOffsetSeg<T>,
     s2: &RawPlineOffsetSeg<T>,
     connection_arcs_ccw: bool,
+    join_style: JoinStyle<T>,
     pos_equal_eps: T,
     result: &mut Polyline<T>,
 ) where
@@ -152,7 +187,7 @@ fn line_line_join<T>(
     let u2 = &s2.v2;
 
     debug_assert!(
-        v1.bulge_is_zero() && v2.bulge_is_zero(),
+        v1.bulge_is_zero() && u1.bulge_is_zero(),
         "both segments should be lines"
     );
 
@@ -175,8 +210,36 @@ fn line_line_join<T>(
             }
             LineLineIntr::FalseIntersect { seg1_t, seg2_t } => {
                 if seg1_t > T::one() && is_false_intersect(seg2_t) {
-                    // extend and join the lines together using arc
-                    connect_using_arc(s1, s2, connection_arcs_ccw, result, pos_equal_eps);
+                    // outward corner, join the lines together using the join style
+                    match join_style {
+                        JoinStyle::Round => {
+                            connect_using_arc(s1, s2, connection_arcs_ccw, result, pos_equal_eps);
+                        }
+                        JoinStyle::Miter { limit } => {
+                            // miter point is where the extended lines intersect, its distance from
+                            // the original corner relative to the offset distance gives the ratio
+                            // compared against the limit
+                            let miter_point = point_from_parametric(v1.pos(), v2.pos(), seg1_t);
+                            let offset_dist_squared = dist_squared(v2.pos(), s1.orig_v2_pos);
+                            let miter_dist_squared = dist_squared(miter_point, s1.orig_v2_pos);
+                            if miter_dist_squared <= limit * limit * offset_dist_squared {
+                                result.add_or_replace(
+                                    miter_point.x,
+                                    miter_point.y,
+                                    T::zero(),
+                                    pos_equal_eps,
+                                );
+                            } else {
+                                // beyond miter limit, fall back to bevel
+                                result.add_or_replace(v2.x, v2.y, T::zero(), pos_equal_eps);
+                                result.add_or_replace(u1.x, u1.y, u1.bulge, pos_equal_eps);
+                            }
+                        }
+                        JoinStyle::Bevel => {
+                            result.add_or_replace(v2.x, v2.y, T::zero(), pos_equal_eps);
+                            result.add_or_replace(u1.x, u1.y, u1.bulge, pos_equal_eps);
+                        }
+                    }
                 } else {
                     result.add_or_replace(v2.x, v2.y, T::zero(), pos_equal_eps);
                     result.add_or_replace(u1.x, u1.y, u1.bulge, pos_equal_eps);
@@ -201,7 +264,7 @@ fn line_arc_join<T>(
     let u2 = &s2.v2;
 
     debug_assert!(
-        v1.bulge_is_zero() && !v2.bulge_is_zero(),
+        v1.bulge_is_zero() && !u1.bulge_is_zero(),
         "first segment should be line, second segment should be arc"
     );
 
@@ -210,7 +273,7 @@ fn line_arc_join<T>(
     let mut process_intersect = |t: T, intersect: Vector2<T>| {
         let true_line_intr = !is_false_intersect(t);
         let true_arc_intr =
-            point_within_arc_sweep(arc_center, u1.pos(), u2.pos(), u1.bulge_is_pos(), intersect);
+            point_within_arc_sweep(arc_center, u1.pos(), u2.pos(), u1.bulge_is_neg(), intersect);
 
         if true_line_intr && true_arc_intr {
             // trim at intersect
@@ -227,7 +290,7 @@ fn line_arc_join<T>(
                     pos_equal_eps,
                 )
             } else {
-                result.add_or_replace(intersect.y, intersect.y, u1.bulge, pos_equal_eps);
+                result.add_or_replace(intersect.x, intersect.y, u1.bulge, pos_equal_eps);
             }
             return;
         }
@@ -285,7 +348,7 @@ fn arc_line_join<T>(
     let u2 = &s2.v2;
 
     debug_assert!(
-        !v1.bulge_is_zero() && v2.bulge_is_zero(),
+        !v1.bulge_is_zero() && u1.bulge_is_zero(),
         "first segment should be arc, second segment should be line"
     );
 
@@ -294,7 +357,7 @@ fn arc_line_join<T>(
     let mut process_intersect = |t: T, intersect: Vector2<T>| {
         let true_line_intr = !is_false_intersect(t);
         let true_arc_intr =
-            point_within_arc_sweep(arc_center, v1.pos(), v2.pos(), v1.bulge_is_pos(), intersect);
+            point_within_arc_sweep(arc_center, v1.pos(), v2.pos(), v1.bulge_is_neg(), intersect);
 
         if true_line_intr && true_arc_intr {
             let prev_vertex = result.last().unwrap();
@@ -363,7 +426,7 @@ fn arc_arc_join<T>(
     let u2 = &s2.v2;
 
     debug_assert!(
-        !v1.bulge_is_zero() && !v2.bulge_is_zero(),
+        !v1.bulge_is_zero() && !u1.bulge_is_zero(),
         "both segments should be arcs"
     );
 
@@ -375,14 +438,14 @@ fn arc_arc_join<T>(
             arc1_center,
             v1.pos(),
             v2.pos(),
-            v1.bulge_is_pos(),
+            v1.bulge_is_neg(),
             intersect,
         );
         let true_arc_intr2 = point_within_arc_sweep(
             arc2_center,
             u1.pos(),
             u2.pos(),
-            u1.bulge_is_pos(),
+            u1.bulge_is_neg(),
             intersect,
         );
 
@@ -451,26 +514,31 @@ fn arc_arc_join<T>(
     }
 }
 
+/// Create the raw offset polyline of `polyline` in `result`, `raw_offset_segs` is a scratch
+/// buffer for the untrimmed raw offset segments (both are cleared first).
 pub fn create_raw_offset_polyline<T>(
     polyline: &Polyline<T>,
     offset: T,
+    join_style: JoinStyle<T>,
     pos_equal_eps: T,
-) -> Polyline<T>
-where
+    raw_offset_segs: &mut Vec<RawPlineOffsetSeg<T>>,
+    result: &mut Polyline<T>,
+) where
     T: Real,
 {
+    result.reset();
     if polyline.len() < 2 {
-        return Polyline::new();
+        return;
     }
 
-    let raw_offset_segs = create_untrimmed_raw_offset_segs(polyline, offset);
+    create_untrimmed_raw_offset_segs(polyline, offset, raw_offset_segs);
     if raw_offset_segs.len() == 0 {
-        return Polyline::new();
+        return;
     }
 
     // detect single collapsed arc segment
     if raw_offset_segs.len() == 1 && raw_offset_segs[0].collapsed_arc {
-        return Polyline::new();
+        return;
     }
 
     let connection_arcs_ccw = offset < T::zero();
@@ -480,14 +548,21 @@ where
             let s1_is_line = s1.v1.bulge_is_zero();
             let s2_is_line = s2.v1.bulge_is_zero();
             match (s1_is_line, s2_is_line) {
-                (true, true) => line_line_join(s1, s2, connection_arcs_ccw, pos_equal_eps, result),
+                (true, true) => line_line_join(
+                    s1,
+                    s2,
+                    connection_arcs_ccw,
+                    join_style,
+                    pos_equal_eps,
+                    result,
+                ),
                 (true, false) => line_arc_join(s1, s2, connection_arcs_ccw, pos_equal_eps, result),
                 (false, true) => arc_line_join(s1, s2, connection_arcs_ccw, pos_equal_eps, result),
                 (false, false) => arc_arc_join(s1, s2, connection_arcs_ccw, pos_equal_eps, result),
             }
         };
 
-    let mut result = Polyline::with_capacity(polyline.len());
+    result.reserve(polyline.len());
     result.set_is_closed(polyline.is_closed());
 
     // add the very first vertex
@@ -496,34 +571,28 @@ where
     // join first two segments and determine if first vertex was replaced (to know how to handle
     // last two segment joins for closed polyline)
     if raw_offset_segs.len() > 1 {
-        join_seg_pair(&raw_offset_segs[0], &raw_offset_segs[1], &mut result);
+        join_seg_pair(&raw_offset_segs[0], &raw_offset_segs[1], result);
     }
 
     let first_vertex_replaced = result.len() == 1;
 
     for i in 2..raw_offset_segs.len() {
-        join_seg_pair(&raw_offset_segs[i - 1], &raw_offset_segs[i], &mut result);
+        join_seg_pair(&raw_offset_segs[i - 1], &raw_offset_segs[i], result);
     }
 
     if polyline.is_closed() && result.len() > 1 {
-        // join closing segments at vertex indexes (n, 0) and (0, 1)
+        // join closing segments at vertex indexes (n, 0) and (0, 1), joining only updates the last
+        // vertex and appends vertexes so the closing join is done directly on result
         let s1 = &raw_offset_segs.last().unwrap();
         let s2 = &raw_offset_segs[0];
+        join_seg_pair(s1, s2, result);
 
-        // temp polyline to capture results of joining (to avoid mutating result)
-        let mut closing_part_result = Polyline::new();
-        closing_part_result.add_vertex(*result.last().unwrap());
-        join_seg_pair(s1, s2, &mut closing_part_result);
-
-        // update last vertexes
-        *result.last_mut().unwrap() = closing_part_result[0];
-        for v in closing_part_result.iter().skip(1) {
-            result.add_vertex(*v);
-        }
+        // last vertex is now the updated first vertex, remove it (first vertex is updated below)
+        let updated_first_pos = result.last().unwrap().pos();
+        result.remove_last();
 
         // update first vertex (only if it has not already been updated/replaced)
         if !first_vertex_replaced {
-            let updated_first_pos = closing_part_result.last().unwrap().pos();
             if result[0].bulge_is_zero() {
                 // just update position
                 result[0].x = updated_first_pos.x;
@@ -569,8 +638,6 @@ where
     if result.len() == 1 {
         result.clear();
     }
-
-    result
 }
 
 fn point_valid_for_offset<T>(
@@ -606,6 +673,87 @@ where
     point_valid
 }
 
+/// Find all the intersects between a circle with radius `offset.abs()` at `circle_center` and
+/// `polyline` (used to find where a raw offset polyline crosses the offset circles at the ends of an
+/// open polyline), intersects are added to `output` as pairs of (segment start index, point).
+fn offset_circle_intersects_with_pline<T>(
+    polyline: &Polyline<T>,
+    offset: T,
+    circle_center: Vector2<T>,
+    spatial_index: &StaticAABB2DIndex<T>,
+    query_stack: &mut Vec<usize>,
+    query_results: &mut Vec<usize>,
+    output: &mut Vec<(usize, Vector2<T>)>,
+) where
+    T: Real,
+{
+    let circle_radius = offset.abs();
+
+    query_results.clear();
+    let mut visitor = |i: usize| -> bool {
+        query_results.push(i);
+        true
+    };
+    spatial_index.visit_query_with_stack(
+        circle_center.x - circle_radius,
+        circle_center.y - circle_radius,
+        circle_center.x + circle_radius,
+        circle_center.y + circle_radius,
+        &mut visitor,
+        query_stack,
+    );
+
+    // intersects at the start of a segment are skipped since they are found as the end of the
+    // previous segment
+    let valid_line_intr = |t: T| -> bool { !is_false_intersect(t) && t.abs() > T::fuzzy_epsilon() };
+
+    for &i in query_results.iter() {
+        let v1 = polyline[i];
+        let v2 = polyline[i + 1];
+        if v1.bulge_is_zero() {
+            match line_circle_intr(v1.pos(), v2.pos(), circle_radius, circle_center) {
+                LineCircleIntr::NoIntersect => {}
+                LineCircleIntr::TangentIntersect { t0 } => {
+                    if valid_line_intr(t0) {
+                        output.push((i, point_from_parametric(v1.pos(), v2.pos(), t0)));
+                    }
+                }
+                LineCircleIntr::TwoIntersects { t0, t1 } => {
+                    if valid_line_intr(t0) {
+                        output.push((i, point_from_parametric(v1.pos(), v2.pos(), t0)));
+                    }
+                    if valid_line_intr(t1) {
+                        output.push((i, point_from_parametric(v1.pos(), v2.pos(), t1)));
+                    }
+                }
+            }
+        } else {
+            let (arc_radius, arc_center) = seg_arc_radius_and_center(v1, v2);
+            let valid_arc_intr = |p: Vector2<T>| -> bool {
+                !v1.pos().fuzzy_eq(p)
+                    && point_within_arc_sweep(arc_center, v1.pos(), v2.pos(), v1.bulge_is_neg(), p)
+            };
+            match circle_circle_intr(arc_radius, arc_center, circle_radius, circle_center) {
+                CircleCircleIntr::NoIntersect | CircleCircleIntr::Overlapping => {}
+                CircleCircleIntr::TangentIntersect { point } => {
+                    if valid_arc_intr(point) {
+                        output.push((i, point));
+                    }
+                }
+                CircleCircleIntr::TwoIntersects { point1, point2 } => {
+                    if valid_arc_intr(point1) {
+                        output.push((i, point1));
+                    }
+                    if valid_arc_intr(point2) {
+                        output.push((i, point2));
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct OpenPolylineSlice<T> {
     intr_start_index: usize,
     polyline: Polyline<T>,
@@ -626,62 +774,113 @@ pub fn slices_from_raw_offset<T>(
     orig_polyline_index: &StaticAABB2DIndex<T>,
     offset: T,
     options: &PlineOffsetOptions<T>,
-) -> Vec<OpenPolylineSlice<T>>
-where
+    workspace: &mut OffsetWorkspace<T>,
+    result: &mut Vec<OpenPolylineSlice<T>>,
+) where
     T: Real,
 {
-    let mut result = Vec::new();
+    let OffsetWorkspace {
+        query_stack,
+        query_results,
+        circle_intrs,
+        self_intrs,
+        intersects_lookup,
+        spare_intr_lists,
+        spare_plines,
+        ..
+    } = workspace;
+
+    // keep the buffers of the previous slices and intersect lists for reuse
+    spare_plines.extend(result.drain(..).map(|slice| slice.polyline));
+    intersects_lookup.retain(|_, intr_list| {
+        intr_list.clear();
+        spare_intr_lists.push(core::mem::take(intr_list));
+        false
+    });
+
     if raw_offset_polyline.len() < 2 {
-        return result;
+        return;
     }
 
     let pos_equal_eps = options.pos_equal_eps;
     let offset_dist_eps = options.offset_dist_eps;
 
     let raw_offset_index = raw_offset_polyline.create_approx_spatial_index().unwrap();
-    let self_intrs = all_self_intersects(raw_offset_polyline, &raw_offset_index, pos_equal_eps);
+    all_self_intersects_into(
+        raw_offset_polyline,
+        &raw_offset_index,
+        pos_equal_eps,
+        self_intrs,
+    );
 
-    let mut query_stack = Vec::new();
-    if self_intrs.len() == 0 {
-        // no self intersects, test point on polyline is valid
+    // using unordered_map rather than map for performance (as is used in
+    // dualSliceAtIntersectsForOffset) since all slices will stitch together to form closed
+    // loops so later when slices are stitched together the order that slices are visited
+    // does not matter
+    let mut add_intersect = |i: usize, point: Vector2<T>| {
+        intersects_lookup
+            .entry(i)
+            .or_insert_with(|| spare_intr_lists.pop().unwrap_or_default())
+            .push(point);
+    };
+
+    for si in self_intrs.iter() {
+        add_intersect(si.start_index1, si.position);
+        add_intersect(si.start_index2, si.position);
+    }
+
+    if !original_polyline.is_closed() {
+        // find intersects between circles generated at original open polyline end points and raw
+        // offset polyline
+        circle_intrs.clear();
+        offset_circle_intersects_with_pline(
+            raw_offset_polyline,
+            offset,
+            original_polyline[0].pos(),
+            &raw_offset_index,
+            query_stack,
+            query_results,
+            circle_intrs,
+        );
+        offset_circle_intersects_with_pline(
+            raw_offset_polyline,
+            offset,
+            original_polyline.last().unwrap().pos(),
+            &raw_offset_index,
+            query_stack,
+            query_results,
+            circle_intrs,
+        );
+
+        for &(i, point) in circle_intrs.iter() {
+            add_intersect(i, point);
+        }
+    }
+
+    if intersects_lookup.is_empty() {
+        // no intersects, test point on polyline is valid
         if !point_valid_for_offset(
             &original_polyline,
             offset,
             &orig_polyline_index,
             raw_offset_polyline[0].pos(),
-            &mut query_stack,
+            query_stack,
             offset_dist_eps,
         ) {
             // not valid
-            return result;
+            return;
         }
 
         // is valid, copy and convert raw offset into open slice
-        let mut slice = raw_offset_polyline.clone();
-        slice.set_is_closed(false);
-        let mut first_vertex = raw_offset_polyline[0];
-        first_vertex.bulge = T::zero();
-        slice.add_vertex(first_vertex);
-        result.push(OpenPolylineSlice::new(usize::max_value(), slice));
-        return result;
-    }
-
-    // using unordered_map rather than map for performance (as is used in
-    // dualSliceAtIntersectsForOffset) since all slices will stitch together to form closed
-    // loops so later when slices are stitched together the order that slices are visited
-    // does not matter
-    let mut intersects_lookup =
-        HashMap::<usize, Vec<Vector2<T>>>::with_capacity(2 * self_intrs.len());
-
-    for si in &self_intrs {
-        intersects_lookup
-            .entry(si.start_index1)
-            .or_insert_with(|| Vec::new())
-            .push(si.position);
-        intersects_lookup
-            .entry(si.start_index2)
-            .or_insert_with(|| Vec::new())
-            .push(si.position);
+        let mut slice = reuse_pline(spare_plines);
+        slice.extend_vertexes(raw_offset_polyline);
+        if original_polyline.is_closed() {
+            let mut first_vertex = raw_offset_polyline[0];
+            first_vertex.bulge = T::zero();
+            slice.add_vertex(first_vertex);
+        }
+        result.push(OpenPolylineSlice::new(usize::MAX, slice));
+        return;
     }
 
     // sort intersects by distance from start vertex
@@ -730,6 +929,75 @@ where
         )
     };
 
+    if !original_polyline.is_closed() {
+        // build first open polyline slice that ends at the first intersect since we will not wrap
+        // back to capture it as in the case of a closed polyline
+        let mut slice = reuse_pline(spare_plines);
+        let mut index = 0;
+        let mut is_valid_pline = true;
+        let max_index = raw_offset_polyline.len() - 1;
+        loop {
+            let current_vertex = raw_offset_polyline[index];
+            // check that vertex point is valid
+            if !point_valid_dist(current_vertex.pos(), query_stack) {
+                is_valid_pline = false;
+                break;
+            }
+
+            // check that the segment does not intersect original polyline
+            if let Some(&last_vertex) = slice.last() {
+                if intersects_original_pline(last_vertex, current_vertex, query_stack) {
+                    is_valid_pline = false;
+                    break;
+                }
+            }
+
+            // add vertex
+            slice.add_or_replace_vertex(current_vertex, pos_equal_eps);
+
+            if let Some(intr_list) = intersects_lookup.get(&index) {
+                // there is an intersect, slice is done, check if final segment is valid
+                let intersect_point = intr_list[0];
+                if !point_valid_dist(intersect_point, query_stack) {
+                    is_valid_pline = false;
+                    break;
+                }
+
+                let split = seg_split_at_point(
+                    current_vertex,
+                    raw_offset_polyline[index + 1],
+                    intersect_point,
+                    pos_equal_eps,
+                );
+
+                let slice_end_vertex = PlineVertex::from_vector2(intersect_point, T::zero());
+                // check midpoint is valid
+                let midpoint = seg_midpoint(split.updated_start, slice_end_vertex);
+                if !point_valid_dist(midpoint, query_stack) {
+                    is_valid_pline = false;
+                    break;
+                }
+
+                // trim last added vertex and add final intersect point
+                *slice.last_mut().unwrap() = split.updated_start;
+                slice.add_or_replace_vertex(slice_end_vertex, pos_equal_eps);
+                break;
+            }
+
+            if index == max_index {
+                break;
+            }
+
+            index += 1;
+        }
+
+        if is_valid_pline && slice.len() > 1 {
+            result.push(OpenPolylineSlice::new(usize::MAX, slice));
+        } else {
+            spare_plines.push(slice);
+        }
+    }
+
     for (&start_index, intr_list) in intersects_lookup.iter() {
         let next_index = raw_offset_polyline.next_wrapping_index(start_index);
         let start_vertex = raw_offset_polyline[start_index];
@@ -755,32 +1023,28 @@ where
                 }
 
                 // test start point
-                if !point_valid_dist(split.updated_start.pos(), &mut query_stack) {
+                if !point_valid_dist(split.updated_start.pos(), query_stack) {
                     continue;
                 }
 
                 // test end point
-                if !point_valid_dist(split.split_vertex.pos(), &mut query_stack) {
+                if !point_valid_dist(split.split_vertex.pos(), query_stack) {
                     continue;
                 }
 
                 // test segment midpoint
                 let midpoint = seg_midpoint(split.updated_start, split.split_vertex);
-                if !point_valid_dist(midpoint, &mut query_stack) {
+                if !point_valid_dist(midpoint, query_stack) {
                     continue;
                 }
 
                 // test intersection with original polyline
-                if intersects_original_pline(
-                    split.updated_start,
-                    split.split_vertex,
-                    &mut query_stack,
-                ) {
+                if intersects_original_pline(split.updated_start, split.split_vertex, query_stack) {
                     continue;
                 }
 
                 // passed all tests, add the slice
-                let mut slice = Polyline::new();
+                let mut slice = reuse_pline(spare_plines);
                 slice.add_vertex(split.updated_start);
                 slice.add_vertex(split.split_vertex);
                 result.push(OpenPolylineSlice::new(start_index, slice));
@@ -790,12 +1054,12 @@ where
         // build the slice between the last intersect in the intr_list and the next intersect found
         // check that the first point is valid
         let slice_start_point = *intr_list.last().unwrap();
-        if !point_valid_dist(slice_start_point, &mut query_stack) {
+        if !point_valid_dist(slice_start_point, query_stack) {
             continue;
         }
 
         let split = seg_split_at_point(start_vertex, end_vertex, slice_start_point, pos_equal_eps);
-        let mut slice = Polyline::new();
+        let mut slice = reuse_pline(spare_plines);
         slice.add_vertex(split.split_vertex);
 
         let mut index = next_index;
@@ -811,13 +1075,13 @@ where
 
             let current_vertex = raw_offset_polyline[index];
             // check that vertex point is valid
-            if !point_valid_dist(current_vertex.pos(), &mut query_stack) {
+            if !point_valid_dist(current_vertex.pos(), query_stack) {
                 is_valid_pline = false;
                 break;
             }
 
             // check that the segment does not intersect original polyline
-            if intersects_original_pline(*slice.last().unwrap(), current_vertex, &mut query_stack) {
+            if intersects_original_pline(*slice.last().unwrap(), current_vertex, query_stack) {
                 is_valid_pline = false;
                 break;
             }
@@ -831,7 +1095,7 @@ where
 
                 // check intersect point is valid (which will be the end of the slice)
                 let intersect_point = next_intr_list[0];
-                if !point_valid_dist(intersect_point, &mut query_stack) {
+                if !point_valid_dist(intersect_point, query_stack) {
                     is_valid_pline = false;
                     break;
                 }
@@ -847,7 +1111,7 @@ where
                 let slice_end_vertex = PlineVertex::from_vector2(intersect_point, T::zero());
                 // check midpoint is valid
                 let midpoint = seg_midpoint(split.updated_start, slice_end_vertex);
-                if !point_valid_dist(midpoint, &mut query_stack) {
+                if !point_valid_dist(midpoint, query_stack) {
                     is_valid_pline = false;
                     break;
                 }
@@ -857,7 +1121,12 @@ where
                 slice.add_or_replace_vertex(slice_end_vertex, pos_equal_eps);
                 break;
             }
-            // else there is not an intersect, increment index and continue
+            // else there is not an intersect, slice ends at the end of an open polyline
+            if !original_polyline.is_closed() && index == raw_offset_polyline.len() - 1 {
+                break;
+            }
+
+            // increment index and continue
             index = raw_offset_polyline.next_wrapping_index(index);
         }
 
@@ -871,31 +1140,57 @@ where
 
         if is_valid_pline {
             result.push(OpenPolylineSlice::new(start_index, slice));
+        } else {
+            spare_plines.push(slice);
         }
     }
+}
 
-    result
+/// Take a polyline from `spare_plines` (reset to an empty open polyline) to reuse its vertex
+/// buffer or create a new polyline if there are none.
+fn reuse_pline<T>(spare_plines: &mut Vec<Polyline<T>>) -> Polyline<T>
+where
+    T: Real,
+{
+    match spare_plines.pop() {
+        Some(mut pline) => {
+            pline.reset();
+            pline
+        }
+        None => Polyline::new(),
+    }
 }
 
 pub fn stitch_slices_together<T>(
-    slices: &Vec<OpenPolylineSlice<T>>,
+    slices: &[OpenPolylineSlice<T>],
     is_closed: bool,
     orig_max_index: usize,
     options: &PlineOffsetOptions<T>,
-) -> Vec<Polyline<T>>
-where
+    workspace: &mut OffsetWorkspace<T>,
+    result: &mut Vec<Polyline<T>>,
+) where
     T: Real,
 {
-    let mut result = Vec::new();
+    let OffsetWorkspace {
+        query_stack,
+        query_results,
+        visited_indexes,
+        spare_plines,
+        ..
+    } = workspace;
+
+    // keep the buffers of the previous results for reuse
+    spare_plines.append(result);
     if slices.len() == 0 {
-        return result;
+        return;
     }
 
     let join_eps = options.slice_join_eps;
     let pos_equal_eps = options.pos_equal_eps;
 
     if slices.len() == 1 {
-        let mut pline = slices[0].polyline.clone();
+        let mut pline = reuse_pline(spare_plines);
+        pline.extend_vertexes(&slices[0].polyline);
 
         if is_closed
             && pline[0]
@@ -907,6 +1202,7 @@ where
         }
 
         result.push(pline);
+        return;
     }
 
     let spatial_index = {
@@ -923,9 +1219,8 @@ where
         builder.build().unwrap()
     };
 
-    let mut visited_indexes = vec![false; slices.len()];
-    let mut query_results = Vec::new();
-    let mut query_stack = Vec::with_capacity(8);
+    visited_indexes.clear();
+    visited_indexes.resize(slices.len(), false);
 
     for i in 0..slices.len() {
         if visited_indexes[i] {
@@ -934,7 +1229,7 @@ where
 
         visited_indexes[i] = true;
 
-        let mut current_pline = Polyline::new();
+        let mut current_pline = reuse_pline(spare_plines);
         let mut current_index = i;
         let initial_start_point = slices[i].polyline[0].pos();
         let mut loop_count = 0;
@@ -964,7 +1259,7 @@ where
                 current_end_point.x + join_eps,
                 current_end_point.y + join_eps,
                 &mut spatial_index_visitor,
-                &mut query_stack,
+                query_stack,
             );
 
             let get_index_dist = |i: usize| -> usize {
@@ -972,8 +1267,11 @@ where
                 if current_loop_start_index <= slice.intr_start_index {
                     slice.intr_start_index - current_loop_start_index
                 } else {
-                    // forward wrapping distance (distance to end + distance to index)
-                    orig_max_index - current_loop_start_index + slice.intr_start_index
+                    // forward wrapping distance (distance to end + distance to index), wrapping
+                    // arithmetic since the first slice of an open polyline has start index max value
+                    orig_max_index
+                        .wrapping_sub(current_loop_start_index)
+                        .wrapping_add(slice.intr_start_index)
                 }
             };
 
@@ -1001,6 +1299,8 @@ where
                     }
 
                     result.push(current_pline);
+                } else {
+                    spare_plines.push(current_pline);
                 }
                 break;
             }
@@ -1011,10 +1311,9 @@ where
             current_index = query_results[0];
         }
     }
-
-    result
 }
 
+#[derive(Debug, Copy, Clone)]
 pub struct PlineOffsetOptions<T>
 where
     T: Real,
@@ -1022,6 +1321,8 @@ where
     pub pos_equal_eps: T,
     pub slice_join_eps: T,
     pub offset_dist_eps: T,
+    pub join_style: JoinStyle<T>,
+    pub prune_self_intersects: bool,
 }
 
 impl<T> Default for PlineOffsetOptions<T>
@@ -1033,10 +1334,87 @@ where
             pos_equal_eps: T::from(1e-5).unwrap(),
             slice_join_eps: T::from(1e-4).unwrap(),
             offset_dist_eps: T::from(1e-4).unwrap(),
+            join_style: JoinStyle::default(),
+            prune_self_intersects: true,
         }
     }
 }
 
+/// Reusable scratch buffers for parallel offsetting (see [Polyline::parallel_offset_into]).
+///
+/// Offsetting in a loop with the same workspace avoids reallocating the intermediate buffers for
+/// every call (raw offset segments and polyline, self intersects, intersect lookup, slices, and
+/// the result polylines returned to the workspace when the output vector is reused), the buffers
+/// grow to fit the largest offset computed and are retained. The spatial indexes of the raw offset
+/// polyline and slice end points are still built for every call (the polyline being offset uses
+/// its cached [Polyline::spatial_index]).
+///
+/// # Examples
+///
+/// ```
+/// # use cavalier_contours::*;
+/// let mut polyline: Polyline = Polyline::new_closed();
+/// polyline.add(0.0, 0.0, 0.0);
+/// polyline.add(10.0, 0.0, 0.0);
+/// polyline.add(10.0, 10.0, 0.0);
+/// polyline.add(0.0, 10.0, 0.0);
+/// let mut workspace = OffsetWorkspace::new();
+/// let mut result = Vec::new();
+/// for i in 1..5 {
+///     polyline.parallel_offset_into(i as f64, &mut workspace, &mut result);
+///     assert_eq!(result.len(), 1);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct OffsetWorkspace<T = f64>
+where
+    T: Real,
+{
+    query_stack: Vec<usize>,
+    query_results: Vec<usize>,
+    circle_intrs: Vec<(usize, Vector2<T>)>,
+    visited_indexes: Vec<bool>,
+    raw_offset_segs: Vec<RawPlineOffsetSeg<T>>,
+    raw_offset: Polyline<T>,
+    self_intrs: Vec<PlineIntersect<T>>,
+    intersects_lookup: LookupMap<usize, Vec<Vector2<T>>>,
+    slices: Vec<OpenPolylineSlice<T>>,
+    // cleared buffers kept to be reused
+    spare_intr_lists: Vec<Vec<Vector2<T>>>,
+    spare_plines: Vec<Polyline<T>>,
+}
+
+impl<T> OffsetWorkspace<T>
+where
+    T: Real,
+{
+    /// Create a new workspace with empty buffers.
+    pub fn new() -> Self {
+        OffsetWorkspace {
+            query_stack: Vec::new(),
+            query_results: Vec::new(),
+            circle_intrs: Vec::new(),
+            visited_indexes: Vec::new(),
+            raw_offset_segs: Vec::new(),
+            raw_offset: Polyline::new(),
+            self_intrs: Vec::new(),
+            intersects_lookup: LookupMap::default(),
+            slices: Vec::new(),
+            spare_intr_lists: Vec::new(),
+            spare_plines: Vec::new(),
+        }
+    }
+}
+
+impl<T> Default for OffsetWorkspace<T>
+where
+    T: Real,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 pub fn parallel_offset<T>(
     polyline: &Polyline<T>,
     offset: T,
@@ -1046,21 +1424,1018 @@ pub fn parallel_offset<T>(
 where
     T: Real,
 {
+    let mut result = Vec::new();
+    parallel_offset_into(
+        polyline,
+        offset,
+        spatial_index,
+        options,
+        &mut OffsetWorkspace::new(),
+        &mut result,
+    );
+    result
+}
+
+/// Same as [parallel_offset] but the results are written to `out` (which is cleared first, with
+/// its polylines kept by `workspace` for reuse) and the intermediate buffers in `workspace` are
+/// reused.
+pub fn parallel_offset_into<T>(
+    polyline: &Polyline<T>,
+    offset: T,
+    spatial_index: Option<&StaticAABB2DIndex<T>>,
+    options: Option<PlineOffsetOptions<T>>,
+    workspace: &mut OffsetWorkspace<T>,
+    out: &mut Vec<Polyline<T>>,
+) where
+    T: Real,
+{
+    // keep the buffers of the previous results for reuse
+    workspace.spare_plines.append(out);
     if polyline.len() < 2 {
-        return Vec::new();
+        return;
     }
 
-    let opt = options.unwrap_or_default();
+    let opt = options.unwrap_or_else(|| PlineOffsetOptions {
+        pos_equal_eps: polyline.pos_equal_eps(),
+        ..Default::default()
+    });
+
+    let index = match spatial_index {
+        Some(x) => x,
+        None => polyline.spatial_index().unwrap(),
+    };
+
+    // raw offset and slices buffers are taken out of the workspace so the other buffers can be
+    // borrowed while slicing and stitching
+    let mut raw_offset = core::mem::take(&mut workspace.raw_offset);
+    create_raw_offset_polyline(
+        &polyline,
+        offset,
+        opt.join_style,
+        opt.pos_equal_eps,
+        &mut workspace.raw_offset_segs,
+        &mut raw_offset,
+    );
 
-    let mut _constructed_index = None;
-    let index = if let Some(x) = spatial_index {
-        x
+    if raw_offset.len() < 2 {
+        // offset collapsed the polyline
+    } else if !opt.prune_self_intersects {
+        let mut pline = reuse_pline(&mut workspace.spare_plines);
+        pline.extend_vertexes(&raw_offset);
+        pline.set_is_closed(raw_offset.is_closed());
+        out.push(pline);
     } else {
-        _constructed_index = Some(polyline.create_approx_spatial_index().unwrap());
-        _constructed_index.as_ref().unwrap()
+        let mut slices = core::mem::take(&mut workspace.slices);
+        slices_from_raw_offset(
+            &polyline,
+            &raw_offset,
+            index,
+            offset,
+            &opt,
+            workspace,
+            &mut slices,
+        );
+        stitch_slices_together(
+            &slices,
+            polyline.is_closed(),
+            raw_offset.len() - 1,
+            &opt,
+            workspace,
+            out,
+        );
+        workspace.slices = slices;
+    }
+
+    workspace.raw_offset = raw_offset;
+}
+
+/// Error returned by [Polyline::try_parallel_offset] when the input cannot be offset.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum OffsetError {
+    /// Polyline has less than 2 vertexes (no segments to offset).
+    TooFewVertexes,
+    /// Polyline vertex data is not valid, [PlineError::NonFiniteVertex] with the index of the first
+    /// vertex that has a non-finite value (same as [Polyline::validate]).
+    InvalidVertex(PlineError),
+    /// Offset distance is not finite (NaN or infinite).
+    NonFiniteOffset,
+    /// Segment starting at vertex `index` has zero length (its end points are coincident).
+    ZeroLengthSegment {
+        /// Start vertex index of the zero length segment.
+        index: usize,
+    },
+    /// Closed polyline self intersects (or has overlapping segments).
+    SelfIntersecting,
+    /// Polyline is not valid for a reason other than its vertex data (any [PlineError] other than
+    /// [PlineError::NonFiniteVertex] converted to an offset error).
+    InvalidPolyline(PlineError),
+}
+
+impl fmt::Display for OffsetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OffsetError::TooFewVertexes => write!(f, "polyline has less than 2 vertexes"),
+            OffsetError::InvalidVertex(err) => write!(f, "{}", err),
+            OffsetError::NonFiniteOffset => write!(f, "offset distance is not finite"),
+            OffsetError::ZeroLengthSegment { index } => {
+                write!(f, "segment starting at index {} has zero length", index)
+            }
+            OffsetError::SelfIntersecting => write!(f, "closed polyline self intersects"),
+            OffsetError::InvalidPolyline(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OffsetError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            OffsetError::InvalidVertex(err) | OffsetError::InvalidPolyline(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<PlineError> for OffsetError {
+    fn from(err: PlineError) -> Self {
+        match err {
+            PlineError::NonFiniteVertex { .. } => OffsetError::InvalidVertex(err),
+            _ => OffsetError::InvalidPolyline(err),
+        }
+    }
+}
+
+/// Same as [parallel_offset] (with no spatial index or options given) but the input is validated
+/// first so an empty result always means the offset consumed the whole shape, see
+/// [Polyline::try_parallel_offset].
+pub fn try_parallel_offset<T>(
+    polyline: &Polyline<T>,
+    offset: T,
+) -> Result<Vec<Polyline<T>>, OffsetError>
+where
+    T: Real,
+{
+    if polyline.len() < 2 {
+        return Err(OffsetError::TooFewVertexes);
+    }
+
+    polyline.validate()?;
+
+    if !is_finite(offset) {
+        return Err(OffsetError::NonFiniteOffset);
+    }
+
+    let pos_equal_eps = polyline.pos_equal_eps();
+    if let Some((index, _)) = polyline.iter_segment_indexes().find(|&(i, j)| {
+        polyline[i]
+            .pos()
+            .fuzzy_eq_eps(polyline[j].pos(), pos_equal_eps)
+    }) {
+        return Err(OffsetError::ZeroLengthSegment { index });
+    }
+
+    let spatial_index = polyline.spatial_index();
+    if polyline.is_closed() && polyline.self_intersects(spatial_index) {
+        return Err(OffsetError::SelfIntersecting);
+    }
+
+    Ok(parallel_offset(polyline, offset, spatial_index, None))
+}
+
+/// Same as [parallel_offset] but each resulting polyline is paired with the source segment index
+/// of each of its segments (see [Polyline::parallel_offset_with_source]).
+///
+/// Every point on a valid offset segment is at the offset distance from the segment it was
+/// created from and no closer to any other segment, so the source of each offset segment is found
+/// as the closest input segment to the offset segment midpoint (only the segments within the
+/// offset distance in the spatial index are checked). Ties between segments at the same distance
+/// (e.g. coincident segments) go to the segment with the midpoint on its offset side, then to the
+/// segment ending at the closest point.
+pub fn parallel_offset_with_source<T>(
+    polyline: &Polyline<T>,
+    offset: T,
+    spatial_index: Option<&StaticAABB2DIndex<T>>,
+) -> Vec<(Polyline<T>, Vec<usize>)>
+where
+    T: Real,
+{
+    let index = match spatial_index {
+        Some(x) => x,
+        None => match polyline.spatial_index() {
+            Some(x) => x,
+            None => return Vec::new(),
+        },
+    };
+
+    let opt = PlineOffsetOptions {
+        pos_equal_eps: polyline.pos_equal_eps(),
+        ..Default::default()
+    };
+    let pos_equal_eps = opt.pos_equal_eps;
+    let query_dist = offset.abs() + opt.offset_dist_eps;
+    let mut query_stack = Vec::new();
+
+    let mut source_seg_index = |point: Vector2<T>| -> usize {
+        // (segment index, distance, point on offset side, closest point at segment end)
+        let mut best: Option<(usize, T, bool, bool)> = None;
+        let mut visitor = |i: usize| -> bool {
+            let v1 = polyline[i];
+            let v2 = polyline[polyline.next_wrapping_index(i)];
+            let cp = seg_closest_point(v1, v2, point);
+            let dist = (point - cp).length();
+            let side = seg_tangent_vector(v1, v2, cp).perp_dot(point - cp);
+            let on_offset_side = if offset > T::zero() {
+                side > T::zero()
+            } else {
+                side < T::zero()
+            };
+            // corner join arcs are closest to the corner, prefer the segment ending at the corner
+            let at_seg_end = cp.fuzzy_eq_eps(v2.pos(), pos_equal_eps);
+            let is_better = match best {
+                None => true,
+                Some((_, best_dist, best_side, best_end)) => {
+                    if dist.fuzzy_eq_eps(best_dist, pos_equal_eps) {
+                        (on_offset_side, at_seg_end) > (best_side, best_end)
+                    } else {
+                        dist < best_dist
+                    }
+                }
+            };
+            if is_better {
+                best = Some((i, dist, on_offset_side, at_seg_end));
+            }
+            true
+        };
+
+        index.visit_query_with_stack(
+            point.x - query_dist,
+            point.y - query_dist,
+            point.x + query_dist,
+            point.y + query_dist,
+            &mut visitor,
+            &mut query_stack,
+        );
+
+        match best {
+            Some((i, ..)) => i,
+            // not expected for a valid offset, fall back to the closest segment
+            None => polyline.closest_point(point).unwrap().seg_start_index,
+        }
+    };
+
+    parallel_offset(polyline, offset, spatial_index, None)
+        .into_iter()
+        .map(|pline| {
+            let mut source = Vec::with_capacity(pline.len());
+            for (v1, v2) in pline.iter_segments() {
+                source.push(source_seg_index(seg_midpoint(v1, v2)));
+            }
+
+            if pline.is_closed() {
+                // iter_segments starts with the closing segment (which starts at the last vertex)
+                source.rotate_left(1);
+            } else if let Some(&last) = source.last() {
+                source.push(last);
+            }
+
+            (pline, source)
+        })
+        .collect()
+}
+
+/// Style of end cap used to close the ends of an open polyline when offsetting with
+/// [Polyline::parallel_offset_opt].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum Endcap {
+    /// Ends are closed by a line segment through the open polyline end point.
+    #[default]
+    Butt,
+    /// Ends are closed by a half circle arc with radius equal to the offset distance.
+    Round,
+    /// Ends are extended by the offset distance and then closed, forming a square end.
+    Square,
+}
+
+/// Style of join used to close the gap at outward corners between two line segments when
+/// offsetting with [Polyline::parallel_offset_opt].
+///
+/// Only corners between two line segments are affected, corners involving arc segments are always
+/// joined using an arc.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub enum JoinStyle<T = f64> {
+    /// Corners are joined by an arc with radius equal to the offset distance.
+    #[default]
+    Round,
+    /// Corners are joined by extending the lines until they intersect, forming a sharp corner.
+    ///
+    /// If the distance from the original corner to the sharp corner divided by the offset distance
+    /// is greater than `limit` then the corner is beveled instead (a right angle corner has a ratio
+    /// of `sqrt(2)`).
+    Miter { limit: T },
+    /// Corners are joined by a line segment between the ends of the offset lines.
+    Bevel,
+}
+
+/// Options for [Polyline::parallel_offset_opt].
+///
+/// The epsilon values control the numerical robustness of the offset algorithm, the defaults work
+/// well for polylines with coordinates and features around unit size, inputs with very small
+/// features (relative to the epsilon values) may need smaller values to avoid artifacts.
+///
+/// # Examples
+///
+/// ```
+/// # use cavalier_contours::*;
+/// let mut options: OffsetOptions = OffsetOptions::default();
+/// options.endcap = Endcap::Round;
+/// options.join = JoinStyle::Miter { limit: 2.0 };
+/// options.offset_dist_eps = 1e-6;
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct OffsetOptions<'a, T = f64> {
+    /// End cap style used to close the ends of open polylines (ignored for closed polylines).
+    pub endcap: Endcap,
+    /// Join style used to close outward corners between line segments.
+    pub join: JoinStyle<T>,
+    /// Spatial index of the polyline segments (e.g. from [Polyline::spatial_index]), if `None`
+    /// then the index is created when offsetting. Defaults to `None`.
+    pub spatial_index: Option<&'a StaticAABB2DIndex<T>>,
+    /// Epsilon used for fuzzy position equality when building the offset, if `None` then
+    /// [Polyline::pos_equal_eps] is used. Defaults to `None`.
+    pub pos_equal_eps: Option<T>,
+    /// Epsilon used to join the end points of offset slices together. Defaults to `1e-4`.
+    pub slice_join_eps: T,
+    /// Epsilon used when checking if an offset slice is at the offset distance from the input
+    /// polyline, slices closer than the offset distance minus this value are discarded. Defaults
+    /// to `1e-4`.
+    pub offset_dist_eps: T,
+    /// If true then the self intersects of the raw offset are found and the parts of the raw
+    /// offset which are closer than the offset distance to the input polyline are removed, leaving
+    /// only valid offset loops. Defaults to `true`.
+    ///
+    /// Setting this to false skips that pass and returns the raw offset (segments offset and
+    /// joined), this is mostly useful for debugging. The raw offset may self intersect, contain
+    /// loops going in the opposite direction, and contain parts which are closer than the offset
+    /// distance to the input polyline, so it is generally not suitable for further processing
+    /// (e.g. offsetting again or boolean operations).
+    pub prune_self_intersects: bool,
+}
+
+impl<'a, T> Default for OffsetOptions<'a, T>
+where
+    T: Real,
+{
+    fn default() -> Self {
+        let pline_options = PlineOffsetOptions::<T>::default();
+        OffsetOptions {
+            endcap: Endcap::default(),
+            join: JoinStyle::default(),
+            spatial_index: None,
+            pos_equal_eps: None,
+            slice_join_eps: pline_options.slice_join_eps,
+            offset_dist_eps: pline_options.offset_dist_eps,
+            prune_self_intersects: pline_options.prune_self_intersects,
+        }
+    }
+}
+
+/// Parallel offset `polyline` using the `options` given.
+///
+/// Closed polylines are offset the same as [parallel_offset] (with outward corners joined using
+/// `options.join`). Open polylines are offset by
+/// `offset.abs()` on both sides and the two sides are joined at the ends using `options.endcap`,
+/// the resulting closed polylines are counter clockwise. If the sides are trimmed at the ends (e.g.
+/// due to the polyline curling back on itself) such that they cannot be joined then the unjoined
+/// open polylines are returned.
+pub fn parallel_offset_opt<T>(
+    polyline: &Polyline<T>,
+    offset: T,
+    options: &OffsetOptions<T>,
+) -> Vec<Polyline<T>>
+where
+    T: Real,
+{
+    let pline_options = PlineOffsetOptions {
+        pos_equal_eps: options
+            .pos_equal_eps
+            .unwrap_or_else(|| polyline.pos_equal_eps()),
+        slice_join_eps: options.slice_join_eps,
+        offset_dist_eps: options.offset_dist_eps,
+        join_style: options.join,
+        prune_self_intersects: options.prune_self_intersects,
+    };
+
+    if polyline.is_closed() || polyline.len() < 2 || offset.fuzzy_eq_zero() {
+        return parallel_offset(polyline, offset, options.spatial_index, Some(pline_options));
+    }
+
+    let dist = offset.abs();
+    let index = match options.spatial_index {
+        Some(x) => x,
+        None => polyline.spatial_index().unwrap(),
+    };
+    let join_eps = pline_options.slice_join_eps;
+
+    let last_index = polyline.len() - 1;
+    let start = polyline[0].pos();
+    let start_tangent = seg_tangent_vector(polyline[0], polyline[1], start)
+        .normalize()
+        .scale(dist);
+    let start_normal = start_tangent.perp();
+    let end = polyline[last_index].pos();
+    let end_tangent = seg_tangent_vector(polyline[last_index - 1], polyline[last_index], end)
+        .normalize()
+        .scale(dist);
+    let end_normal = end_tangent.perp();
+
+    // caps go counter clockwise from the right side to the left side at the end and from the left
+    // side to the right side at the start
+    let create_cap = |from: Vector2<T>, to: Vector2<T>, tangent: Vector2<T>| -> Polyline<T> {
+        let mut cap = Polyline::new();
+        match options.endcap {
+            Endcap::Butt => {
+                cap.add(from.x, from.y, T::zero());
+            }
+            Endcap::Round => {
+                cap.add(from.x, from.y, T::one());
+            }
+            Endcap::Square => {
+                cap.add(from.x, from.y, T::zero());
+                let from_corner = from + tangent;
+                cap.add(from_corner.x, from_corner.y, T::zero());
+                let to_corner = to + tangent;
+                cap.add(to_corner.x, to_corner.y, T::zero());
+            }
+        }
+        cap.add(to.x, to.y, T::zero());
+        cap
     };
 
-    let raw_offset = create_raw_offset_polyline(&polyline, offset, opt.pos_equal_eps);
-    let slices = slices_from_raw_offset(&polyline, &raw_offset, index, offset, &opt);
-    stitch_slices_together(&slices, true, raw_offset.len() - 1, &opt)
+    let mut pieces = parallel_offset(polyline, -dist, Some(index), Some(pline_options));
+    pieces.push(create_cap(end - end_normal, end + end_normal, end_tangent));
+    for mut left_side in parallel_offset(polyline, dist, Some(index), Some(pline_options)) {
+        left_side.invert_direction();
+        left_side.last_mut().unwrap().bulge = T::zero();
+        pieces.push(left_side);
+    }
+    pieces.push(create_cap(
+        start + start_normal,
+        start - start_normal,
+        -start_tangent,
+    ));
+
+    // join pieces end to start to form closed loops
+    let mut result = Vec::new();
+    let mut visited = vec![false; pieces.len()];
+    for i in 0..pieces.len() {
+        if visited[i] {
+            continue;
+        }
+
+        visited[i] = true;
+        let mut current_pline = pieces[i].clone();
+        loop {
+            let end_point = current_pline.last().unwrap().pos();
+            if current_pline[0].pos().fuzzy_eq_eps(end_point, join_eps) {
+                current_pline.remove_last();
+                current_pline.set_is_closed(true);
+                break;
+            }
+
+            let next_piece = (0..pieces.len())
+                .find(|&j| !visited[j] && pieces[j][0].pos().fuzzy_eq_eps(end_point, join_eps));
+            match next_piece {
+                Some(j) => {
+                    visited[j] = true;
+                    current_pline.remove_last();
+                    current_pline.extend_vertexes(&pieces[j]);
+                }
+                None => break,
+            }
+        }
+
+        result.push(current_pline);
+    }
+
+    result
+}
+
+/// Parallel offset each polyline in `plines` by `offset` concurrently (using rayon) and collect all
+/// the resulting polylines.
+///
+/// Results are ordered by input polyline (all the results for `plines[0]` come first, followed by
+/// the results for `plines[1]`, etc.) and match offsetting each polyline in sequence. Each input
+/// polyline builds its own spatial index so no state is shared between threads.
+#[cfg(feature = "rayon")]
+pub fn parallel_offset_many<T>(plines: &[Polyline<T>], offset: T) -> Vec<Polyline<T>>
+where
+    T: Real + Send + Sync,
+{
+    use rayon::prelude::*;
+
+    plines
+        .par_iter()
+        .map(|pline| parallel_offset(pline, offset, None, None))
+        .collect::<Vec<_>>()
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{core_math::seg_length, FuzzyEq};
+    use std::f64::consts::PI;
+
+    fn open_segment() -> Polyline<f64> {
+        let mut polyline = Polyline::new();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(10.0, 0.0, 0.0);
+        polyline
+    }
+
+    #[test]
+    fn open_polyline_offset_stays_open() {
+        let mut polyline = Polyline::new();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(10.0, 0.0, 0.0);
+        polyline.add(10.0, 10.0, 0.0);
+
+        let left = parallel_offset(&polyline, 1.0, None, None);
+        assert_eq!(left.len(), 1);
+        assert!(!left[0].is_closed());
+        assert_fuzzy_eq!(left[0][0].pos(), Vector2::new(0.0, 1.0));
+        assert_fuzzy_eq!(left[0].last().unwrap().pos(), Vector2::new(9.0, 10.0));
+
+        let right = parallel_offset(&polyline, -1.0, None, None);
+        assert_eq!(right.len(), 1);
+        assert!(!right[0].is_closed());
+        assert_fuzzy_eq!(right[0].path_length(), 20.0 + PI / 2.0);
+    }
+
+    /// Assert every vertex and segment midpoint of each offset result is at the offset distance
+    /// from `polyline`.
+    fn assert_at_offset_dist(polyline: &Polyline<f64>, offset: f64, results: &[Polyline<f64>]) {
+        for pline in results {
+            for (v1, v2) in pline.iter_segments() {
+                for point in [v1.pos(), seg_midpoint(v1, v2)] {
+                    let dist = polyline.closest_point(point).unwrap().distance;
+                    assert!(
+                        dist.fuzzy_eq_eps(offset.abs(), 1e-5),
+                        "point {:?} is {} from the polyline",
+                        point,
+                        dist
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn offset_line_arc_joins() {
+        // squares with each side a line, a convex arc, or a concave arc, offset inward (lines and
+        // arcs are trimmed at their intersect) and outward (joined by arcs)
+        let bulges = [0.0, 0.3, -0.3];
+        for k in 0..bulges.len().pow(4) {
+            let side_bulge = |side: usize| bulges[(k / bulges.len().pow(side as u32)) % 3];
+            let mut polyline = Polyline::new_closed();
+            polyline.add(0.0, 0.0, side_bulge(0));
+            polyline.add(10.0, 0.0, side_bulge(1));
+            polyline.add(10.0, 10.0, side_bulge(2));
+            polyline.add(0.0, 10.0, side_bulge(3));
+
+            // corners are all trimmed for the inward offset so the raw offset does not loop
+            let mut raw_offset_segs = Vec::new();
+            let mut raw_offset = Polyline::new();
+            create_raw_offset_polyline(
+                &polyline,
+                1.0,
+                JoinStyle::Round,
+                1e-5,
+                &mut raw_offset_segs,
+                &mut raw_offset,
+            );
+            assert!(!raw_offset.self_intersects(None));
+
+            for &offset in &[1.0, -1.0] {
+                let results = parallel_offset(&polyline, offset, None, None);
+                assert_eq!(results.len(), 1, "bulges {:?}", (0..4).map(side_bulge));
+                assert!(results[0].is_closed());
+                assert_at_offset_dist(&polyline, offset, &results);
+            }
+        }
+    }
+
+    #[test]
+    fn line_arc_join_trims_at_arc_end() {
+        // offset line is tangent to the offset arc at the arc end point
+        let line = RawPlineOffsetSeg {
+            v1: PlineVertex::new(-1.0, 2.0, 0.0),
+            v2: PlineVertex::new(1.0, 2.0, 0.0),
+            orig_v2_pos: Vector2::new(1.0, 1.0),
+            collapsed_arc: false,
+        };
+        let arc = RawPlineOffsetSeg {
+            v1: PlineVertex::new(2.0, 0.0, (PI / 8.0).tan()),
+            v2: PlineVertex::new(0.0, 2.0, 0.0),
+            orig_v2_pos: Vector2::new(0.0, 1.0),
+            collapsed_arc: false,
+        };
+        let mut result = Polyline::new();
+        result.add_vertex(line.v1);
+        line_arc_join(&line, &arc, false, 1e-5, &mut result);
+        assert_fuzzy_eq!(result.last().unwrap().pos(), Vector2::new(0.0, 2.0));
+    }
+
+    #[test]
+    fn open_polyline_offset_trimmed_at_end_circle() {
+        // hook shape, the offset of the first segment passes closer than the offset distance to
+        // the end point so it is cut where it crosses the circle around the end point
+        let mut polyline = Polyline::new();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(10.0, 0.0, 0.0);
+        polyline.add(10.0, 3.0, 0.0);
+        polyline.add(5.0, 3.0, 0.0);
+
+        let results = parallel_offset(&polyline, 2.0, None, None);
+        assert_eq!(results.len(), 1);
+        assert!(!results[0].is_closed());
+        assert_fuzzy_eq!(results[0][0].pos(), Vector2::new(0.0, 2.0));
+        assert_fuzzy_eq!(
+            results[0].last().unwrap().pos(),
+            Vector2::new(5.0 - 3.0f64.sqrt(), 2.0)
+        );
+        assert_at_offset_dist(&polyline, 2.0, &results);
+
+        let results = parallel_offset(&polyline, -2.0, None, None);
+        assert_eq!(results.len(), 1);
+        assert!(!results[0].is_closed());
+        assert_at_offset_dist(&polyline, -2.0, &results);
+    }
+
+    #[test]
+    fn offset_opt_round_endcap() {
+        let options = OffsetOptions {
+            endcap: Endcap::Round,
+            ..Default::default()
+        };
+        let result = parallel_offset_opt(&open_segment(), 1.0, &options);
+        assert_eq!(result.len(), 1);
+        let stadium = &result[0];
+        assert!(stadium.is_closed());
+        assert_eq!(stadium.len(), 4);
+        assert_fuzzy_eq!(stadium.area(), 20.0 + PI);
+        assert_fuzzy_eq!(stadium.path_length(), 20.0 + 2.0 * PI);
+        let extents = stadium.extents().unwrap();
+        assert_fuzzy_eq!(extents.min_x, -1.0);
+        assert_fuzzy_eq!(extents.max_x, 11.0);
+        assert_fuzzy_eq!(extents.min_y, -1.0);
+        assert_fuzzy_eq!(extents.max_y, 1.0);
+
+        // sign of offset does not matter
+        let result = parallel_offset_opt(&open_segment(), -1.0, &options);
+        assert_eq!(result.len(), 1);
+        assert_fuzzy_eq!(result[0].area(), 20.0 + PI);
+    }
+
+    #[test]
+    fn offset_opt_butt_and_square_endcaps() {
+        let options = OffsetOptions::default();
+        let result = parallel_offset_opt(&open_segment(), 1.0, &options);
+        assert_eq!(result.len(), 1);
+        assert!(result[0].is_closed());
+        assert_fuzzy_eq!(result[0].area(), 20.0);
+
+        let options = OffsetOptions {
+            endcap: Endcap::Square,
+            ..Default::default()
+        };
+        let result = parallel_offset_opt(&open_segment(), 1.0, &options);
+        assert_eq!(result.len(), 1);
+        assert!(result[0].is_closed());
+        assert_fuzzy_eq!(result[0].area(), 24.0);
+    }
+
+    #[test]
+    fn offset_opt_closed_ignores_endcap() {
+        let mut square = Polyline::new_closed();
+        square.add(0.0, 0.0, 0.0);
+        square.add(4.0, 0.0, 0.0);
+        square.add(4.0, 4.0, 0.0);
+        square.add(0.0, 4.0, 0.0);
+        let options = OffsetOptions {
+            endcap: Endcap::Round,
+            ..Default::default()
+        };
+        let result = parallel_offset_opt(&square, 1.0, &options);
+        let expected = parallel_offset(&square, 1.0, None, None);
+        assert_eq!(result.len(), expected.len());
+        assert!(result[0].fuzzy_eq(&expected[0]));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_offset_many_matches_serial() {
+        let mut plines = Vec::new();
+        for i in 0..50 {
+            let shift = i as f64 * 10.0;
+            let mut rect = Polyline::new_closed();
+            rect.add(shift, 0.0, 0.0);
+            rect.add(shift + 5.0, 0.0, 0.0);
+            rect.add(shift + 5.0, 3.0, 0.0);
+            rect.add(shift, 3.0, 0.0);
+            plines.push(rect);
+
+            let mut circle = Polyline::new_closed();
+            circle.add(shift, 10.0, 1.0);
+            circle.add(shift + 4.0, 10.0, 1.0);
+            plines.push(circle);
+        }
+
+        for &offset in &[0.5, -0.5, 1.0] {
+            let serial: Vec<_> = plines
+                .iter()
+                .flat_map(|pline| parallel_offset(pline, offset, None, None))
+                .collect();
+            let concurrent = parallel_offset_many(&plines, offset);
+            assert_eq!(serial.len(), concurrent.len());
+            for (a, b) in serial.iter().zip(&concurrent) {
+                assert_eq!(a.len(), b.len());
+                assert_eq!(a.is_closed(), b.is_closed());
+                assert!(a.fuzzy_eq(b));
+            }
+        }
+    }
+
+    #[test]
+    fn offset_into_matches_allocating_offset() {
+        let mut polyline = Polyline::new_closed();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(10.0, 0.0, 1.0);
+        polyline.add(10.0, 4.0, 0.0);
+        polyline.add(6.0, 4.0, 0.0);
+        polyline.add(5.0, 1.0, 0.0);
+        polyline.add(4.0, 4.0, 0.0);
+        polyline.add(0.0, 4.0, 0.0);
+        let mut open_polyline = polyline.clone();
+        open_polyline.set_is_closed(false);
+
+        let mut workspace = OffsetWorkspace::new();
+        let mut out = Vec::new();
+        for pline in &[polyline, open_polyline] {
+            for i in -20..20 {
+                let offset = i as f64 * 0.15;
+                let expected = parallel_offset(pline, offset, None, None);
+                parallel_offset_into(pline, offset, None, None, &mut workspace, &mut out);
+                assert_eq!(out.len(), expected.len());
+                // order of results is not defined so match each result to any expected polyline
+                for result in out.iter() {
+                    assert!(expected.iter().any(|e| result.geom_eq(e, 1e-5)));
+                }
+            }
+        }
+
+        // intermediate buffers are kept in the workspace for the next call
+        assert!(workspace.raw_offset_segs.capacity() >= 7);
+        assert!(workspace.raw_offset.len() > 1);
+        let kept_plines = workspace.spare_plines.len() + out.len();
+        let mut polyline = Polyline::new_closed();
+        polyline.add(0.0, 0.0, 1.0);
+        polyline.add(2.0, 0.0, 1.0);
+        parallel_offset_into(&polyline, 0.5, None, None, &mut workspace, &mut out);
+        assert_eq!(out.len(), 1);
+        assert!(workspace.spare_plines.len() + out.len() <= kept_plines + 1);
+    }
+
+    #[test]
+    fn offset_single_segment() {
+        // line segment going down, left is +x
+        let v1 = PlineVertex::new(2.0, 5.0, 0.0);
+        let v2 = PlineVertex::new(2.0, 1.0, 0.0);
+        let (u1, u2) = offset_segment(v1, v2, 1.5);
+        assert_fuzzy_eq!(u1.pos(), Vector2::new(3.5, 5.0));
+        assert_fuzzy_eq!(u2.pos(), Vector2::new(3.5, 1.0));
+        assert_fuzzy_eq!(u1.bulge, 0.0);
+
+        // counter clockwise half circle with center (1, 0) and radius 1
+        let v1 = PlineVertex::new(0.0, 0.0, 1.0);
+        let v2 = PlineVertex::new(2.0, 0.0, 0.0);
+        let center = Vector2::new(1.0, 0.0);
+        let (u1, u2) = offset_segment(v1, v2, 0.25);
+        assert_fuzzy_eq!(u1.bulge, 1.0);
+        assert_fuzzy_eq!((u1.pos() - center).length(), 0.75);
+        assert_fuzzy_eq!((u2.pos() - center).length(), 0.75);
+        assert_fuzzy_eq!(seg_length(u1, u2), 0.75 * PI);
+
+        let (u1, u2) = offset_segment(v1, v2, -0.5);
+        assert_fuzzy_eq!(u1.pos(), Vector2::new(-0.5, 0.0));
+        assert_fuzzy_eq!(u2.pos(), Vector2::new(2.5, 0.0));
+        assert_fuzzy_eq!(seg_length(u1, u2), 1.5 * PI);
+
+        // offset collapses the arc
+        let (u1, u2) = offset_segment(v1, v2, 1.5);
+        assert_fuzzy_eq!(u1.bulge, 0.0);
+        assert_fuzzy_eq!(u1.pos(), Vector2::new(1.5, 0.0));
+        assert_fuzzy_eq!(u2.pos(), Vector2::new(0.5, 0.0));
+    }
+
+    #[test]
+    fn offset_opt_join_styles() {
+        let mut corner = Polyline::new();
+        corner.add(0.0, 0.0, 0.0);
+        corner.add(10.0, 0.0, 0.0);
+        corner.add(10.0, 10.0, 0.0);
+
+        let offset_with_join = |join: JoinStyle| {
+            let options = OffsetOptions {
+                join,
+                ..Default::default()
+            };
+            let result = parallel_offset_opt(&corner, 1.0, &options);
+            assert_eq!(result.len(), 1);
+            assert!(result[0].is_closed());
+            result.into_iter().next().unwrap()
+        };
+
+        let has_vertex_at =
+            |pline: &Polyline<f64>, pos: Vector2<f64>| pline.iter().any(|v| v.pos().fuzzy_eq(pos));
+
+        // outward corner is at (10, 0) on the right side
+        let miter = offset_with_join(JoinStyle::Miter { limit: 2.0 });
+        assert!(has_vertex_at(&miter, Vector2::new(11.0, -1.0)));
+        assert!(miter.iter().all(|v| v.bulge_is_zero()));
+        assert_fuzzy_eq!(miter.area(), 40.0);
+
+        let round = offset_with_join(JoinStyle::Round);
+        assert!(!has_vertex_at(&round, Vector2::new(11.0, -1.0)));
+        assert_eq!(round.iter().filter(|v| !v.bulge_is_zero()).count(), 1);
+        assert_fuzzy_eq!(round.area(), 39.0 + PI / 4.0);
+
+        let bevel = offset_with_join(JoinStyle::Bevel);
+        assert!(bevel.iter().all(|v| v.bulge_is_zero()));
+        assert_fuzzy_eq!(bevel.area(), 39.5);
+
+        // miter limit below sqrt(2) falls back to bevel for right angle corner
+        let limited = offset_with_join(JoinStyle::Miter { limit: 1.4 });
+        assert!(limited.fuzzy_eq(&bevel));
+    }
+
+    #[test]
+    fn offset_opt_tuned_eps() {
+        // thin tooth (0.2 wide) that should be removed by an inward offset just over half its width
+        let mut pline = Polyline::new_closed();
+        pline.add(0.0, 0.0, 0.0);
+        pline.add(10.0, 0.0, 0.0);
+        pline.add(10.0, 1.0, 0.0);
+        pline.add(4.2, 1.0, 0.0);
+        pline.add(4.2, 3.0, 0.0);
+        pline.add(4.0, 3.0, 0.0);
+        pline.add(4.0, 1.0, 0.0);
+        pline.add(0.0, 1.0, 0.0);
+        let offset = 0.10002;
+
+        let max_vertex_y = |pline: &Polyline<f64>| pline.iter().fold(f64::MIN, |y, v| y.max(v.y));
+
+        // default epsilons leave a sliver of the tooth
+        let result = parallel_offset_opt(&pline, offset, &OffsetOptions::default());
+        assert_eq!(result.len(), 1);
+        assert!(max_vertex_y(&result[0]) > 1.0);
+
+        let options = OffsetOptions {
+            spatial_index: pline.spatial_index(),
+            pos_equal_eps: Some(1e-8),
+            slice_join_eps: 1e-7,
+            offset_dist_eps: 1e-7,
+            ..Default::default()
+        };
+        let result = parallel_offset_opt(&pline, offset, &options);
+        assert_eq!(result.len(), 1);
+        // rounded concave corners at the tooth base overlap slightly, meeting just below y = 1
+        assert_eq!(result[0].len(), 7);
+        assert_fuzzy_eq!(
+            max_vertex_y(&result[0]),
+            1.0 - (offset * offset - 0.1 * 0.1).sqrt()
+        );
+    }
+
+    #[test]
+    fn offset_with_source_square() {
+        let mut square = Polyline::new_closed();
+        square.add(0.0, 0.0, 0.0);
+        square.add(10.0, 0.0, 0.0);
+        square.add(10.0, 10.0, 0.0);
+        square.add(0.0, 10.0, 0.0);
+
+        let check_directions = |pline: &Polyline<f64>, source: &[usize]| {
+            assert_eq!(pline.len(), source.len());
+            for i in 0..pline.len() {
+                let j = pline.next_wrapping_index(i);
+                if !pline[i].bulge_is_zero() {
+                    continue;
+                }
+                let dir = (pline[j].pos() - pline[i].pos()).normalize();
+                let s = source[i];
+                let source_dir =
+                    (square[square.next_wrapping_index(s)].pos() - square[s].pos()).normalize();
+                assert_fuzzy_eq!(dir, source_dir);
+            }
+        };
+
+        // inward offset edges each map to a different source edge
+        let result = parallel_offset_with_source(&square, 1.0, None);
+        assert_eq!(result.len(), 1);
+        let (pline, source) = &result[0];
+        assert_eq!(pline.len(), 4);
+        let mut sorted = source.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, vec![0, 1, 2, 3]);
+        check_directions(pline, source);
+
+        // outward offset adds corner arcs which map to the edge ending at the corner
+        let result = parallel_offset_with_source(&square, -1.0, None);
+        assert_eq!(result.len(), 1);
+        let (pline, source) = &result[0];
+        assert_eq!(pline.len(), 8);
+        check_directions(pline, source);
+        for i in 0..pline.len() {
+            if !pline[i].bulge_is_zero() {
+                let prev = pline.prev_wrapping_index(i);
+                assert_eq!(source[i], source[prev]);
+            }
+        }
+    }
+
+    #[test]
+    fn offset_with_source_coincident_segments() {
+        // square with a spike going down into it and back, both spike segments are the same
+        // distance from the offset segments beside the spike so the source is the segment with the
+        // offset segment on its offset side (running in the same direction)
+        let mut pline = Polyline::new_closed();
+        pline.add(0.0, 0.0, 0.0);
+        pline.add(10.0, 0.0, 0.0);
+        pline.add(10.0, 10.0, 0.0);
+        pline.add(5.0, 10.0, 0.0);
+        pline.add(5.0, 5.0, 0.0);
+        pline.add(5.0, 10.0, 0.0);
+        pline.add(0.0, 10.0, 0.0);
+        let result = parallel_offset_with_source(&pline, 1.0, None);
+        assert_eq!(result.len(), 1);
+        let (offset_pline, source) = &result[0];
+        assert_eq!(offset_pline.len(), source.len());
+        let mut spike_seg_count = 0;
+        for i in 0..offset_pline.len() {
+            let v1 = offset_pline[i];
+            let v2 = offset_pline[offset_pline.next_wrapping_index(i)];
+            if !v1.x.fuzzy_eq(v2.x) || !(v1.x.fuzzy_eq(4.0) || v1.x.fuzzy_eq(6.0)) {
+                continue;
+            }
+            // segment going down on the right of the spike and up on the left
+            let expected = if v1.x.fuzzy_eq(6.0) { 3 } else { 4 };
+            assert_eq!(source[i], expected);
+            spike_seg_count += 1;
+        }
+        assert_eq!(spike_seg_count, 2);
+    }
+
+    #[test]
+    fn offset_opt_without_pruning() {
+        // U shape which splits into two loops when offset inward
+        let mut pline = Polyline::new_closed();
+        pline.add(0.0, 0.0, 0.0);
+        pline.add(10.0, 0.0, 0.0);
+        pline.add(10.0, 4.0, 0.0);
+        pline.add(6.0, 4.0, 0.0);
+        pline.add(6.0, 1.0, 0.0);
+        pline.add(4.0, 1.0, 0.0);
+        pline.add(4.0, 4.0, 0.0);
+        pline.add(0.0, 4.0, 0.0);
+
+        let options = OffsetOptions {
+            prune_self_intersects: false,
+            ..Default::default()
+        };
+        let raw = parallel_offset_opt(&pline, 1.5, &options);
+        assert_eq!(raw.len(), 1);
+        assert!(raw[0].is_closed());
+        assert!(raw[0].self_intersects(None));
+
+        let pruned = parallel_offset_opt(&pline, 1.5, &OffsetOptions::default());
+        assert_eq!(pruned.len(), 2);
+        for loop_pline in pruned.iter() {
+            assert!(loop_pline.is_closed());
+            assert!(!loop_pline.self_intersects(None));
+            assert!(loop_pline.area() > 0.0);
+        }
+    }
+
+    #[test]
+    fn offset_error_from_pline_error() {
+        let err = PlineError::NonFiniteVertex { index: 3 };
+        assert_eq!(OffsetError::from(err), OffsetError::InvalidVertex(err));
+        let err = PlineError::DisconnectedPrimitive { index: 1 };
+        assert_eq!(OffsetError::from(err), OffsetError::InvalidPolyline(err));
+        let err = PlineError::NonUniformScaleWithArcs;
+        assert_eq!(OffsetError::from(err), OffsetError::InvalidPolyline(err));
+    }
 }
@@ -0,0 +1,481 @@
+//! Reading and writing polylines as SVG path data strings (the `d` attribute of a path element).
+//!
+//! Arcs are written and read with positive bulge values matching a sweep flag of 1 (arc drawn in
+//! the positive angle direction), coordinates are not flipped.
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+use core::fmt::{self, Write};
+
+use crate::{Polyline, Real, Vector2};
+
+/// Error returned when parsing an SVG path data string fails.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SvgParseError {
+    /// A number was expected but could not be parsed.
+    InvalidNumber {
+        /// Byte position in the path data string.
+        position: usize,
+    },
+    /// A path command which is not supported (only M, L, H, V, A and Z are supported).
+    UnsupportedCommand {
+        /// The command character.
+        command: char,
+        /// Byte position in the path data string.
+        position: usize,
+    },
+    /// Path data must start with a move to command.
+    MissingMoveTo {
+        /// Byte position in the path data string.
+        position: usize,
+    },
+    /// Elliptical arc (arc radii are not equal) which cannot be represented by a bulge value.
+    EllipticalArc {
+        /// Byte position in the path data string of the arc command.
+        position: usize,
+    },
+}
+
+impl fmt::Display for SvgParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SvgParseError::InvalidNumber { position } => {
+                write!(
+                    f,
+                    "invalid number in SVG path data at position {}",
+                    position
+                )
+            }
+            SvgParseError::UnsupportedCommand { command, position } => write!(
+                f,
+                "unsupported SVG path command '{}' at position {}",
+                command, position
+            ),
+            SvgParseError::MissingMoveTo { position } => write!(
+                f,
+                "SVG path data must start with a move to command (position {})",
+                position
+            ),
+            SvgParseError::EllipticalArc { position } => {
+                write!(f, "elliptical SVG arc at position {}", position)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SvgParseError {}
+
+/// Simple cursor over the path data string.
+struct PathCursor<'a> {
+    data: &'a str,
+    position: usize,
+}
+
+impl<'a> PathCursor<'a> {
+    fn skip_separators(&mut self) {
+        let rest = &self.data[self.position..];
+        let trimmed = rest.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+        self.position += rest.len() - trimmed.len();
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_separators();
+        self.data[self.position..].chars().next()
+    }
+
+    fn at_number(&mut self) -> bool {
+        matches!(self.peek(), Some(c) if c.is_ascii_digit() || c == '-' || c == '+' || c == '.')
+    }
+
+    fn next_command(&mut self) -> Option<(char, usize)> {
+        let c = self.peek()?;
+        let position = self.position;
+        self.position += c.len_utf8();
+        Some((c, position))
+    }
+
+    fn number<T>(&mut self) -> Result<T, SvgParseError>
+    where
+        T: Real,
+    {
+        self.skip_separators();
+        let start = self.position;
+        let bytes = self.data.as_bytes();
+        let mut end = start;
+        if end < bytes.len() && (bytes[end] == b'-' || bytes[end] == b'+') {
+            end += 1;
+        }
+        let mut seen_dot = false;
+        while end < bytes.len() {
+            match bytes[end] {
+                b'0'..=b'9' => {}
+                b'.' if !seen_dot => seen_dot = true,
+                b'e' | b'E' => {
+                    // exponent, may have a sign
+                    if end + 1 < bytes.len() && (bytes[end + 1] == b'-' || bytes[end + 1] == b'+') {
+                        end += 1;
+                    }
+                    seen_dot = true;
+                }
+                _ => break,
+            }
+            end += 1;
+        }
+
+        let value = self.data[start..end]
+            .parse::<f64>()
+            .ok()
+            .and_then(T::from)
+            .ok_or(SvgParseError::InvalidNumber { position: start })?;
+        self.position = end;
+        Ok(value)
+    }
+
+    fn flag(&mut self) -> Result<bool, SvgParseError> {
+        // flags may be written without separators (e.g. "a1 1 0 00 1 1")
+        let position = self.position;
+        match self.peek() {
+            Some('0') => {
+                self.position += 1;
+                Ok(false)
+            }
+            Some('1') => {
+                self.position += 1;
+                Ok(true)
+            }
+            _ => Err(SvgParseError::InvalidNumber { position }),
+        }
+    }
+}
+
+/// Compute the bulge for a circular SVG arc going from `p0` to `p1`.
+fn svg_arc_bulge<T>(p0: Vector2<T>, p1: Vector2<T>, radius: T, large_arc: bool, sweep: bool) -> T
+where
+    T: Real,
+{
+    let half_chord = (p1 - p0).length() / T::two();
+    // radius is scaled up if too small to reach the end point (as defined by SVG)
+    let ratio = num_traits::real::Real::min(half_chord / radius, T::one());
+    let minor_sweep = T::two() * ratio.asin();
+    let sweep_angle = if large_arc {
+        T::two() * T::pi() - minor_sweep
+    } else {
+        minor_sweep
+    };
+
+    let bulge = (sweep_angle / T::four()).tan();
+    if sweep {
+        bulge
+    } else {
+        -bulge
+    }
+}
+
+/// Parse the SVG path data string `d` into polylines (one polyline per subpath).
+///
+/// Supports the move to (`M`), line to (`L`, `H`, `V`), arc (`A`) and close path (`Z`) commands
+/// in both absolute (uppercase) and relative (lowercase) forms. Arcs must be circular (equal
+/// radii) and are converted to bulge values, arcs with a zero radius are treated as lines. A
+/// closed subpath ending on its start point does not repeat the start vertex.
+///
+/// # Examples
+///
+/// ```
+/// # use cavalier_contours::*;
+/// # use cavalier_contours::svg::*;
+/// let plines: Vec<Polyline> = from_svg_path("M 0 0 h 2 A 1 1 0 0 1 0 0 Z").unwrap();
+/// assert_eq!(plines.len(), 1);
+/// assert!(plines[0].is_closed());
+/// assert_eq!(plines[0].len(), 2);
+/// assert!(plines[0][1].fuzzy_eq(PlineVertex::new(2.0, 0.0, 1.0)));
+/// ```
+pub fn from_svg_path<T>(d: &str) -> Result<Vec<Polyline<T>>, SvgParseError>
+where
+    T: Real,
+{
+    let mut result = Vec::new();
+    let mut cursor = PathCursor {
+        data: d,
+        position: 0,
+    };
+    let mut current: Option<Polyline<T>> = None;
+    let mut current_point = Vector2::zero();
+    let mut subpath_start = Vector2::zero();
+    let mut has_move_to = false;
+
+    let finish_subpath = |pline: Option<Polyline<T>>, result: &mut Vec<Polyline<T>>| {
+        if let Some(pline) = pline {
+            if pline.len() > 1 {
+                result.push(pline);
+            }
+        }
+    };
+
+    while let Some((command, position)) = cursor.next_command() {
+        let is_relative = command.is_ascii_lowercase();
+        let origin = if is_relative {
+            current_point
+        } else {
+            Vector2::zero()
+        };
+        match command.to_ascii_uppercase() {
+            'M' => {
+                finish_subpath(current.take(), &mut result);
+                has_move_to = true;
+                let x = cursor.number::<T>()?;
+                let y = cursor.number::<T>()?;
+                current_point = origin + Vector2::new(x, y);
+                subpath_start = current_point;
+                let mut pline = Polyline::new();
+                pline.add(current_point.x, current_point.y, T::zero());
+                // subsequent coordinate pairs are implicit line to commands
+                while cursor.at_number() {
+                    let x = cursor.number::<T>()?;
+                    let y = cursor.number::<T>()?;
+                    let base = if is_relative {
+                        current_point
+                    } else {
+                        Vector2::zero()
+                    };
+                    current_point = base + Vector2::new(x, y);
+                    pline.add(current_point.x, current_point.y, T::zero());
+                }
+                current = Some(pline);
+            }
+            'Z' => {
+                if let Some(mut pline) = current.take() {
+                    pline.set_is_closed(true);
+                    pline.remove_repeated_ring_end();
+                    finish_subpath(Some(pline), &mut result);
+                }
+                current_point = subpath_start;
+            }
+            'L' | 'H' | 'V' | 'A' => {
+                let pline = match current.as_mut() {
+                    Some(pline) => pline,
+                    None => {
+                        if !has_move_to {
+                            return Err(SvgParseError::MissingMoveTo { position });
+                        }
+                        // drawing after a close path starts a new subpath at the start point
+                        let mut pline = Polyline::new();
+                        pline.add(current_point.x, current_point.y, T::zero());
+                        current = Some(pline);
+                        current.as_mut().unwrap()
+                    }
+                };
+
+                loop {
+                    let base = if is_relative {
+                        current_point
+                    } else {
+                        Vector2::zero()
+                    };
+                    match command.to_ascii_uppercase() {
+                        'L' => {
+                            let x = cursor.number::<T>()?;
+                            let y = cursor.number::<T>()?;
+                            current_point = base + Vector2::new(x, y);
+                        }
+                        'H' => {
+                            current_point.x = base.x + cursor.number::<T>()?;
+                        }
+                        'V' => {
+                            current_point.y = base.y + cursor.number::<T>()?;
+                        }
+                        _ => {
+                            let rx = cursor.number::<T>()?.abs();
+                            let ry = cursor.number::<T>()?.abs();
+                            let _x_axis_rotation = cursor.number::<T>()?;
+                            let large_arc = cursor.flag()?;
+                            let sweep = cursor.flag()?;
+                            let x = cursor.number::<T>()?;
+                            let y = cursor.number::<T>()?;
+                            let end_point = base + Vector2::new(x, y);
+                            if !rx.fuzzy_eq_zero() && !ry.fuzzy_eq_zero() {
+                                if !rx.fuzzy_eq_eps(ry, T::from(1e-5).unwrap()) {
+                                    return Err(SvgParseError::EllipticalArc { position });
+                                }
+
+                                if !end_point.fuzzy_eq(current_point) {
+                                    pline.last_mut().unwrap().bulge = svg_arc_bulge(
+                                        current_point,
+                                        end_point,
+                                        rx,
+                                        large_arc,
+                                        sweep,
+                                    );
+                                }
+                            }
+                            current_point = end_point;
+                        }
+                    }
+
+                    pline.add(current_point.x, current_point.y, T::zero());
+                    if !cursor.at_number() {
+                        break;
+                    }
+                }
+            }
+            _ => {
+                return Err(SvgParseError::UnsupportedCommand { command, position });
+            }
+        }
+    }
+
+    finish_subpath(current.take(), &mut result);
+    Ok(result)
+}
+
+impl<T> Polyline<T>
+where
+    T: Real,
+{
+    /// Write the polyline as an SVG path data string using absolute move to (`M`), line to (`L`),
+    /// arc (`A`) and close path (`Z`) commands.
+    ///
+    /// Returns an empty string if the polyline is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new_closed();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(2.0, 0.0, 1.0);
+    /// assert_eq!(polyline.to_svg_path(), "M 0 0 L 2 0 A 1 1 0 0 1 0 0 Z");
+    /// ```
+    pub fn to_svg_path(&self) -> String {
+        let mut result = String::new();
+        if self.len() == 0 {
+            return result;
+        }
+
+        let to_f64 = |value: T| value.to_f64().unwrap();
+        let v0 = self[0];
+        // writing to a string cannot fail
+        let _ = write!(result, "M {} {}", to_f64(v0.x), to_f64(v0.y));
+        let seg_count = if self.is_closed() {
+            self.len()
+        } else {
+            self.len() - 1
+        };
+        for i in 0..seg_count {
+            let v1 = self[i];
+            let v2 = self[self.next_wrapping_index(i)];
+            if v1.bulge_is_zero() {
+                if i + 1 < self.len() {
+                    let _ = write!(result, " L {} {}", to_f64(v2.x), to_f64(v2.y));
+                }
+                continue;
+            }
+
+            let sweep_angle = T::four() * v1.bulge.abs().atan();
+            let half_chord = (v2.pos() - v1.pos()).length() / T::two();
+            let radius = half_chord / (sweep_angle / T::two()).sin();
+            let large_arc = if sweep_angle > T::pi() { 1 } else { 0 };
+            let sweep = if v1.bulge_is_pos() { 1 } else { 0 };
+            let _ = write!(
+                result,
+                " A {} {} 0 {} {} {} {}",
+                to_f64(radius),
+                to_f64(radius),
+                large_arc,
+                sweep,
+                to_f64(v2.x),
+                to_f64(v2.y)
+            );
+        }
+
+        if self.is_closed() {
+            result.push_str(" Z");
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FuzzyEq, PlineVertex};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn svg_round_trip() {
+        let mut polyline = Polyline::new_closed();
+        polyline.add(0.0, 0.0, 0.0);
+        polyline.add(4.0, 0.0, 0.5);
+        polyline.add(4.0, 3.0, -0.25);
+        polyline.add(1.0, 3.0, 2.0);
+        polyline.add(0.0, 1.5, 0.0);
+
+        let path = polyline.to_svg_path();
+        let plines: Vec<Polyline> = from_svg_path(&path).unwrap();
+        assert_eq!(plines.len(), 1);
+        assert!(plines[0].is_closed());
+        assert_eq!(plines[0].len(), polyline.len());
+        assert!(plines[0].fuzzy_eq_eps(&polyline, 1e-8));
+        assert_fuzzy_eq!(plines[0].area(), polyline.area());
+
+        // closing arc segment
+        let mut circle = Polyline::new_closed();
+        circle.add(-1.0, 2.0, -1.0);
+        circle.add(1.0, 2.0, -1.0);
+        let plines: Vec<Polyline> = from_svg_path(&circle.to_svg_path()).unwrap();
+        assert_eq!(plines.len(), 1);
+        assert!(plines[0].fuzzy_eq_eps(&circle, 1e-8));
+        assert_fuzzy_eq!(plines[0].area(), -PI);
+    }
+
+    #[test]
+    fn from_svg_path_relative_commands() {
+        let plines: Vec<Polyline> =
+            from_svg_path("m1,1 h3 v2 a1 1 0 0 1 -2,0 l-1-1 z M 10 10 L 11 10 12 11").unwrap();
+        assert_eq!(plines.len(), 2);
+
+        let closed = &plines[0];
+        assert!(closed.is_closed());
+        assert_eq!(closed.len(), 5);
+        assert!(closed[0].fuzzy_eq(PlineVertex::new(1.0, 1.0, 0.0)));
+        assert!(closed[1].fuzzy_eq(PlineVertex::new(4.0, 1.0, 0.0)));
+        assert!(closed[2].fuzzy_eq(PlineVertex::new(4.0, 3.0, 1.0)));
+        assert!(closed[3].fuzzy_eq(PlineVertex::new(2.0, 3.0, 0.0)));
+        assert!(closed[4].fuzzy_eq(PlineVertex::new(1.0, 2.0, 0.0)));
+
+        let open = &plines[1];
+        assert!(!open.is_closed());
+        assert_eq!(open.len(), 3);
+        assert!(open[2].fuzzy_eq(PlineVertex::new(12.0, 11.0, 0.0)));
+    }
+
+    #[test]
+    fn from_svg_path_errors() {
+        let result: Result<Vec<Polyline>, _> = from_svg_path("L 1 1");
+        assert_eq!(
+            result.unwrap_err(),
+            SvgParseError::MissingMoveTo { position: 0 }
+        );
+
+        let result: Result<Vec<Polyline>, _> = from_svg_path("M 0 0 C 1 1 2 2 3 3");
+        assert_eq!(
+            result.unwrap_err(),
+            SvgParseError::UnsupportedCommand {
+                command: 'C',
+                position: 6
+            }
+        );
+
+        let result: Result<Vec<Polyline>, _> = from_svg_path("M 0 0 A 1 2 0 0 1 1 1");
+        assert_eq!(
+            result.unwrap_err(),
+            SvgParseError::EllipticalArc { position: 6 }
+        );
+
+        let result: Result<Vec<Polyline>, _> = from_svg_path("M 0 x");
+        assert_eq!(
+            result.unwrap_err(),
+            SvgParseError::InvalidNumber { position: 4 }
+        );
+    }
+}
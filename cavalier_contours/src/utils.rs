@@ -30,3 +30,27 @@ macro_rules! assert_fuzzy_eq {
         }
     }};
 }
+
+// Map and set types used for internal index lookups: hash based when std is available, ordered
+// (B-tree) collections are used as the fallback for no_std
+#[cfg(feature = "std")]
+pub(crate) type LookupMap<K, V> = std::collections::HashMap<K, V>;
+#[cfg(not(feature = "std"))]
+pub(crate) type LookupMap<K, V> = alloc::collections::BTreeMap<K, V>;
+#[cfg(feature = "std")]
+pub(crate) type LookupSet<K> = std::collections::HashSet<K>;
+#[cfg(not(feature = "std"))]
+pub(crate) type LookupSet<K> = alloc::collections::BTreeSet<K>;
+
+/// Create an empty [LookupSet] with space for at least `capacity` entries (capacity is ignored
+/// without std).
+#[cfg_attr(not(feature = "std"), allow(unused_variables))]
+pub(crate) fn lookup_set_with_capacity<K>(capacity: usize) -> LookupSet<K>
+where
+    K: core::hash::Hash + Ord,
+{
+    #[cfg(feature = "std")]
+    return LookupSet::with_capacity(capacity);
+    #[cfg(not(feature = "std"))]
+    return LookupSet::new();
+}
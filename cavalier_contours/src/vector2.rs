@@ -1,8 +1,9 @@
-use std::ops;
+use core::ops;
 
 use crate::Real;
 
 #[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vector2<T = f64> {
     pub x: T,
     pub y: T,
@@ -0,0 +1,421 @@
+//! Reading and writing polylines as Well-Known Text (WKT) geometries.
+//!
+//! WKT has no arc segments so arcs are approximated by line segments when writing and all
+//! polylines read have zero bulge values.
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+use core::fmt::{self, Write};
+
+use crate::{Polyline, Real};
+
+/// Error returned when parsing a WKT string fails.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WktError {
+    /// Unexpected character or end of input.
+    InvalidSyntax {
+        /// Byte position in the WKT string.
+        position: usize,
+    },
+    /// A coordinate value that could not be parsed.
+    InvalidNumber {
+        /// Byte position in the WKT string.
+        position: usize,
+    },
+    /// Geometry type which is not supported (only LINESTRING, POLYGON, MULTILINESTRING and
+    /// MULTIPOLYGON are supported).
+    UnsupportedGeometry {
+        /// Byte position in the WKT string.
+        position: usize,
+    },
+}
+
+impl fmt::Display for WktError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WktError::InvalidSyntax { position } => {
+                write!(f, "invalid WKT syntax at position {}", position)
+            }
+            WktError::InvalidNumber { position } => {
+                write!(f, "invalid WKT coordinate value at position {}", position)
+            }
+            WktError::UnsupportedGeometry { position } => {
+                write!(f, "unsupported WKT geometry type at position {}", position)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for WktError {}
+
+/// Simple cursor over the WKT string.
+struct WktCursor<'a> {
+    data: &'a str,
+    position: usize,
+}
+
+impl<'a> WktCursor<'a> {
+    fn skip_whitespace(&mut self) {
+        let rest = &self.data[self.position..];
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.data[self.position..].chars().next()
+    }
+
+    fn word(&mut self) -> &'a str {
+        self.skip_whitespace();
+        let rest = &self.data[self.position..];
+        let len = rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        self.position += len;
+        &rest[..len]
+    }
+
+    fn number<T>(&mut self) -> Result<T, WktError>
+    where
+        T: Real,
+    {
+        self.skip_whitespace();
+        let start = self.position;
+        let rest = &self.data[start..];
+        let len = rest
+            .find(|c: char| c.is_whitespace() || c == ',' || c == ')')
+            .unwrap_or(rest.len());
+        let value = rest[..len]
+            .parse::<f64>()
+            .ok()
+            .and_then(T::from)
+            .ok_or(WktError::InvalidNumber { position: start })?;
+        self.position += len;
+        Ok(value)
+    }
+
+    /// Skip the optional dimension tag (`Z`, `M` or `ZM`) following the geometry type.
+    fn skip_dimension_tag(&mut self) {
+        self.skip_whitespace();
+        let position = self.position;
+        let tag = self.word();
+        let is_dimension_tag = ["Z", "M", "ZM"]
+            .iter()
+            .any(|dim| tag.eq_ignore_ascii_case(dim));
+        if !is_dimension_tag {
+            self.position = position;
+        }
+    }
+
+    /// Returns true if the geometry is EMPTY, otherwise consumes the opening parenthesis.
+    fn open_or_empty(&mut self) -> Result<bool, WktError> {
+        if self.peek() == Some('(') {
+            self.position += 1;
+            return Ok(false);
+        }
+
+        let position = self.position;
+        if self.word().eq_ignore_ascii_case("EMPTY") {
+            Ok(true)
+        } else {
+            Err(WktError::InvalidSyntax { position })
+        }
+    }
+
+    /// Returns true if there is another list item (consuming the comma), false if at the end of
+    /// the list (consuming the closing parenthesis).
+    fn next_item(&mut self) -> Result<bool, WktError> {
+        match self.peek() {
+            Some(',') => {
+                self.position += 1;
+                Ok(true)
+            }
+            Some(')') => {
+                self.position += 1;
+                Ok(false)
+            }
+            _ => Err(WktError::InvalidSyntax {
+                position: self.position,
+            }),
+        }
+    }
+
+    /// Parse a coordinate list, e.g. `(0 0, 1 0, 1 1)`, as a polyline. Any Z or M values are
+    /// ignored.
+    fn coordinates<T>(&mut self, is_ring: bool) -> Result<Polyline<T>, WktError>
+    where
+        T: Real,
+    {
+        let mut pline = if is_ring {
+            Polyline::new_closed()
+        } else {
+            Polyline::new()
+        };
+
+        if self.open_or_empty()? {
+            return Ok(pline);
+        }
+
+        loop {
+            let x = self.number()?;
+            let y = self.number()?;
+            while !matches!(self.peek(), Some(',') | Some(')') | None) {
+                self.number::<T>()?;
+            }
+            pline.add(x, y, T::zero());
+            if !self.next_item()? {
+                break;
+            }
+        }
+
+        pline.remove_repeated_ring_end();
+        Ok(pline)
+    }
+
+    /// Parse a list of coordinate lists, e.g. `((0 0, 1 0), (2 2, 3 3))`.
+    fn coordinates_list<T>(
+        &mut self,
+        is_ring: bool,
+        result: &mut Vec<Polyline<T>>,
+    ) -> Result<(), WktError>
+    where
+        T: Real,
+    {
+        if self.open_or_empty()? {
+            return Ok(());
+        }
+
+        loop {
+            let pline = self.coordinates(is_ring)?;
+            if pline.len() > 1 {
+                result.push(pline);
+            }
+            if !self.next_item()? {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Parse the WKT geometry `s` into polylines.
+///
+/// `LINESTRING` and `MULTILINESTRING` geometries produce open polylines, `POLYGON` and
+/// `MULTIPOLYGON` rings produce closed polylines (without repeating the start vertex). All bulge
+/// values are zero. Geometries with less than 2 vertexes are skipped. Z and M values (e.g.
+/// `LINESTRING Z (0 0 1, 1 0 1)`) are ignored.
+///
+/// # Examples
+///
+/// ```
+/// # use cavalier_contours::*;
+/// # use cavalier_contours::wkt::*;
+/// let plines: Vec<Polyline> = from_wkt("POLYGON((0 0, 4 0, 4 3, 0 0))").unwrap();
+/// assert_eq!(plines.len(), 1);
+/// assert!(plines[0].is_closed());
+/// assert_eq!(plines[0].len(), 3);
+/// assert!(plines[0].area().fuzzy_eq(6.0));
+/// ```
+pub fn from_wkt<T>(s: &str) -> Result<Vec<Polyline<T>>, WktError>
+where
+    T: Real,
+{
+    let mut cursor = WktCursor {
+        data: s,
+        position: 0,
+    };
+    let mut result = Vec::new();
+    cursor.skip_whitespace();
+    let tag_position = cursor.position;
+    let tag = cursor.word();
+    cursor.skip_dimension_tag();
+    if tag.eq_ignore_ascii_case("LINESTRING") {
+        let pline = cursor.coordinates(false)?;
+        if pline.len() > 1 {
+            result.push(pline);
+        }
+    } else if tag.eq_ignore_ascii_case("POLYGON") {
+        cursor.coordinates_list(true, &mut result)?;
+    } else if tag.eq_ignore_ascii_case("MULTILINESTRING") {
+        cursor.coordinates_list(false, &mut result)?;
+    } else if tag.eq_ignore_ascii_case("MULTIPOLYGON") {
+        if !cursor.open_or_empty()? {
+            loop {
+                cursor.coordinates_list(true, &mut result)?;
+                if !cursor.next_item()? {
+                    break;
+                }
+            }
+        }
+    } else {
+        return Err(WktError::UnsupportedGeometry {
+            position: tag_position,
+        });
+    }
+
+    if cursor.peek().is_some() {
+        return Err(WktError::InvalidSyntax {
+            position: cursor.position,
+        });
+    }
+
+    Ok(result)
+}
+
+impl<T> Polyline<T>
+where
+    T: Real,
+{
+    /// Write the polyline as a WKT geometry, `POLYGON` if closed or `LINESTRING` if open.
+    ///
+    /// Arc segments are approximated by line segments with `error_distance` (see
+    /// [Polyline::arcs_to_approx_lines]). Polygon rings repeat the start point at the end. Writes
+    /// an `EMPTY` geometry if the polyline has less than 2 vertexes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cavalier_contours::*;
+    /// let mut polyline: Polyline = Polyline::new_closed();
+    /// polyline.add(0.0, 0.0, 0.0);
+    /// polyline.add(4.0, 0.0, 0.0);
+    /// polyline.add(4.0, 3.0, 0.0);
+    /// assert_eq!(polyline.to_wkt(0.01), "POLYGON((0 0, 4 0, 4 3, 0 0))");
+    /// ```
+    pub fn to_wkt(&self, error_distance: T) -> String {
+        let mut result = String::from(if self.is_closed() {
+            "POLYGON"
+        } else {
+            "LINESTRING"
+        });
+
+        // rings repeat the start point at the end
+        let approx = match self.approx_lines_ring_path(error_distance) {
+            Some(approx) => approx,
+            None => {
+                result.push_str(" EMPTY");
+                return result;
+            }
+        };
+
+        result.push_str(if self.is_closed() { "((" } else { "(" });
+        for (i, v) in approx.iter().enumerate() {
+            if i != 0 {
+                result.push_str(", ");
+            }
+            // writing to a string cannot fail
+            let _ = write!(
+                result,
+                "{} {}",
+                v.x.to_f64().unwrap(),
+                v.y.to_f64().unwrap()
+            );
+        }
+        result.push_str(if self.is_closed() { "))" } else { ")" });
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PlineVertex;
+
+    #[test]
+    fn wkt_polygon_round_trip() {
+        let wkt = "POLYGON ((1.5 2, 4 2, 4 5.25, 1.5 2), (2 2.5, 3 2.5, 3 3, 2 2.5))";
+        let plines: Vec<Polyline> = from_wkt(wkt).unwrap();
+        assert_eq!(plines.len(), 2);
+        assert!(plines.iter().all(|p| p.is_closed() && p.len() == 3));
+        assert!(plines[0][0].fuzzy_eq(PlineVertex::new(1.5, 2.0, 0.0)));
+        assert!(plines[0][2].fuzzy_eq(PlineVertex::new(4.0, 5.25, 0.0)));
+        assert!(plines[1][1].fuzzy_eq(PlineVertex::new(3.0, 2.5, 0.0)));
+
+        assert_eq!(
+            plines[0].to_wkt(0.01),
+            "POLYGON((1.5 2, 4 2, 4 5.25, 1.5 2))"
+        );
+        let reparsed: Vec<Polyline> = from_wkt(&plines[0].to_wkt(0.01)).unwrap();
+        assert_eq!(reparsed.len(), 1);
+        assert!(reparsed[0].fuzzy_eq(&plines[0]));
+    }
+
+    #[test]
+    fn wkt_linestring_and_multi() {
+        let plines: Vec<Polyline> = from_wkt("linestring(0 0 1, 1 0 1, 1 1 1)").unwrap();
+        assert_eq!(plines.len(), 1);
+        assert!(!plines[0].is_closed());
+        assert_eq!(plines[0].len(), 3);
+        assert_eq!(plines[0].to_wkt(0.01), "LINESTRING(0 0, 1 0, 1 1)");
+
+        let plines: Vec<Polyline> =
+            from_wkt("MULTIPOLYGON(((0 0, 1 0, 1 1, 0 0)), EMPTY, ((5 5, 6 5, 6 6, 5 5)))")
+                .unwrap();
+        assert_eq!(plines.len(), 2);
+        assert!(plines[1][0].fuzzy_eq(PlineVertex::new(5.0, 5.0, 0.0)));
+
+        let plines: Vec<Polyline> = from_wkt("MULTILINESTRING((0 0, 1 0), (2 2, 3 3))").unwrap();
+        assert_eq!(plines.len(), 2);
+        assert!(plines.iter().all(|p| !p.is_closed()));
+
+        let plines: Vec<Polyline> = from_wkt("POLYGON EMPTY").unwrap();
+        assert!(plines.is_empty());
+    }
+
+    #[test]
+    fn wkt_ignores_z_and_m_values() {
+        for wkt in &[
+            "LINESTRING Z (0 0 1, 1 0 2, 1 1 3)",
+            "LINESTRING M(0 0 1, 1 0 2, 1 1 3)",
+            "linestring zm (0 0 1 5, 1 0 2 5, 1 1 3 5)",
+        ] {
+            let plines: Vec<Polyline> = from_wkt(wkt).unwrap();
+            assert_eq!(plines.len(), 1);
+            assert_eq!(plines[0].to_wkt(0.01), "LINESTRING(0 0, 1 0, 1 1)");
+        }
+
+        let plines: Vec<Polyline> =
+            from_wkt("MULTIPOLYGON Z (((0 0 0, 1 0 0, 1 1 0, 0 0 0)))").unwrap();
+        assert_eq!(plines.len(), 1);
+        assert!(plines[0].is_closed());
+        assert_eq!(plines[0].len(), 3);
+
+        let plines: Vec<Polyline> = from_wkt("POLYGON ZM EMPTY").unwrap();
+        assert!(plines.is_empty());
+    }
+
+    #[test]
+    fn to_wkt_approximates_arcs() {
+        let mut circle = Polyline::new_closed();
+        circle.add(0.0, 0.0, 1.0);
+        circle.add(2.0, 0.0, 1.0);
+        let plines: Vec<Polyline> = from_wkt(&circle.to_wkt(0.001)).unwrap();
+        assert_eq!(plines.len(), 1);
+        assert!(plines[0].len() > 8);
+        assert!(plines[0].iter().all(|v| v.bulge_is_zero()));
+        assert!((plines[0].area() - circle.area()).abs() < 0.01);
+    }
+
+    #[test]
+    fn from_wkt_errors() {
+        let result: Result<Vec<Polyline>, _> = from_wkt("POINT(1 1)");
+        assert_eq!(
+            result.unwrap_err(),
+            WktError::UnsupportedGeometry { position: 0 }
+        );
+
+        let result: Result<Vec<Polyline>, _> = from_wkt("LINESTRING(0 0, 1 x)");
+        assert_eq!(
+            result.unwrap_err(),
+            WktError::InvalidNumber { position: 18 }
+        );
+
+        let result: Result<Vec<Polyline>, _> = from_wkt("LINESTRING(0 0, 1 1");
+        assert_eq!(
+            result.unwrap_err(),
+            WktError::InvalidSyntax { position: 19 }
+        );
+    }
+}